@@ -0,0 +1,9 @@
+// Custom post-processing effect of the post_processing example: params.x is the intensity of the grayscale.
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color: vec3<f32> = source_color(in.texture_coordinates).rgb;
+    let luminance: f32 = dot(color, vec3<f32>(0.299, 0.587, 0.114));
+
+    return vec4<f32>(mix(color, vec3<f32>(luminance), post_process.params.x), 1.0);
+}
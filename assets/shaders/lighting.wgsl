@@ -0,0 +1,99 @@
+// Shader responsible for lighting the rendered frame with the ambient light and the point lights.
+// Each point light is shaded by the normals of the frame and blocked by the occluder edges when casting shadows.
+// The positions, radiuses and heights of the lights are in pixels, starting from the top left corner.
+
+const MAX_POINT_LIGHTS: u32 = 16u;
+const MAX_OCCLUDER_EDGES: u32 = 128u;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) texture_coordinates: vec2<f32>
+};
+
+struct PointLight {
+    position: vec2<f32>,
+    radius: f32,
+    falloff: f32,
+    color: vec4<f32>,
+    height: f32,
+    cast_shadows: u32,
+    padding: vec2<f32>
+};
+
+struct Lighting {
+    ambient: vec4<f32>,
+    resolution: vec2<f32>,
+    light_count: u32,
+    edge_count: u32,
+    lights: array<PointLight, MAX_POINT_LIGHTS>,
+    edges: array<vec4<f32>, MAX_OCCLUDER_EDGES>
+};
+
+@group(0) @binding(0) var scene: texture_2d<f32>;
+@group(0) @binding(1) var scene_sampler: sampler;
+
+@group(1) @binding(0) var<uniform> lighting: Lighting;
+@group(1) @binding(1) var normals: texture_2d<f32>;
+@group(1) @binding(2) var normals_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let position: vec2<f32> = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+
+    out.clip_position = vec4<f32>(position * 2.0 - 1.0, 0.0, 1.0);
+    out.texture_coordinates = vec2<f32>(position.x, 1.0 - position.y);
+    return out;
+}
+
+// Returns if the segment from a pixel to a light crosses an edge.
+fn crosses_edge(pixel: vec2<f32>, light: vec2<f32>, edge: vec4<f32>) -> bool {
+    let ray: vec2<f32> = light - pixel;
+    let segment: vec2<f32> = edge.zw - edge.xy;
+    let denominator: f32 = ray.x * segment.y - ray.y * segment.x;
+
+    if (abs(denominator) < 0.0001) {
+        return false;
+    }
+    let offset: vec2<f32> = edge.xy - pixel;
+    let t: f32 = (offset.x * segment.y - offset.y * segment.x) / denominator;
+    let u: f32 = (offset.x * ray.y - offset.y * ray.x) / denominator;
+    return t > 0.0 && t < 1.0 && u >= 0.0 && u <= 1.0;
+}
+
+// Returns if a light is blocked by any occluder edge before reaching a pixel.
+fn is_shadowed(pixel: vec2<f32>, light: vec2<f32>) -> bool {
+    for (var index: u32 = 0u; index < min(lighting.edge_count, MAX_OCCLUDER_EDGES); index++) {
+        if (crosses_edge(pixel, light, lighting.edges[index])) {
+            return true;
+        }
+    }
+    return false;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color: vec4<f32> = textureSampleLevel(scene, scene_sampler, in.texture_coordinates, 0.0);
+    let normal: vec3<f32> = normalize(textureSampleLevel(normals, normals_sampler, in.texture_coordinates, 0.0).xyz * 2.0 - 1.0);
+    let pixel: vec2<f32> = in.texture_coordinates * lighting.resolution;
+    var light: vec3<f32> = lighting.ambient.rgb;
+
+    for (var index: u32 = 0u; index < min(lighting.light_count, MAX_POINT_LIGHTS); index++) {
+        let point_light: PointLight = lighting.lights[index];
+        let offset: vec2<f32> = point_light.position - pixel;
+        let distance: f32 = length(offset);
+
+        if (distance >= point_light.radius) {
+            continue;
+        }
+
+        if (point_light.cast_shadows != 0u && is_shadowed(pixel, point_light.position)) {
+            continue;
+        }
+        let direction: vec3<f32> = normalize(vec3<f32>(offset.x, -offset.y, point_light.height));
+        let diffuse: f32 = max(dot(normal, direction), 0.0);
+        let attenuation: f32 = pow(1.0 - distance / point_light.radius, point_light.falloff);
+        light += point_light.color.rgb * diffuse * attenuation;
+    }
+    return vec4<f32>(color.rgb * light, color.a);
+}
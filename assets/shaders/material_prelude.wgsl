@@ -0,0 +1,76 @@
+// Prelude of the material shaders, with the bindings and the vertex stage shared by every material.
+// A material shader only declares its fragment stage: fn fs_main(in: VertexOutput) -> @location(0) vec4<f32>.
+
+const SHAPE: u32 = 0u;
+const TEXTURE: u32 = 2u;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) texture_coordinates: vec2<f32>,
+    @location(2) color: vec4<f32>
+};
+
+struct InstanceInput {
+    @location(3) transform_0: vec4<f32>,
+    @location(4) transform_1: vec4<f32>,
+    @location(5) transform_2: vec4<f32>,
+    @location(6) transform_3: vec4<f32>,
+    @location(7) uv_rect: vec4<f32>,
+    @location(8) tint: vec4<f32>,
+    @location(9) rendering_type: u32
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) texture_coordinates: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) @interpolate(flat) rendering_type: u32,
+    @location(3) local_coordinates: vec2<f32>
+};
+
+struct MaterialUniforms {
+    time: f32,
+    color: vec4<f32>,
+    params: vec4<f32>
+};
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+
+@group(1) @binding(0) var<uniform> projection: mat4x4<f32>;
+@group(1) @binding(1) var<uniform> view: mat4x4<f32>;
+
+@group(2) @binding(0) var<uniform> material: MaterialUniforms;
+@group(2) @binding(1) var material_sampler: sampler;
+@group(2) @binding(2) var material_texture_0: texture_2d<f32>;
+@group(2) @binding(3) var material_texture_1: texture_2d<f32>;
+@group(2) @binding(4) var material_texture_2: texture_2d<f32>;
+@group(2) @binding(5) var material_texture_3: texture_2d<f32>;
+
+@vertex
+fn vs_main(in: VertexInput, instance: InstanceInput) -> VertexOutput {
+    var out: VertexOutput;
+    let transform: mat4x4<f32> = mat4x4<f32>(
+        instance.transform_0,
+        instance.transform_1,
+        instance.transform_2,
+        instance.transform_3
+    );
+
+    out.clip_position = projection * view * transform * vec4<f32>(in.position, 1.0);
+    out.texture_coordinates = instance.uv_rect.xy + in.texture_coordinates * instance.uv_rect.zw;
+    out.color = in.color * instance.tint;
+    out.rendering_type = instance.rendering_type;
+    out.local_coordinates = in.texture_coordinates;
+    return out;
+}
+
+// Returns the color that the entity would have without its material.
+fn base_color(in: VertexOutput) -> vec4<f32> {
+    let sampled: vec4<f32> = textureSampleLevel(texture, texture_sampler, in.texture_coordinates, 0.0);
+
+    if (in.rendering_type == TEXTURE) {
+        return sampled * in.color;
+    }
+    return vec4(in.color.rgb, 1.0);
+}
@@ -0,0 +1,34 @@
+// Dissolve material: params.x is the dissolved amount (0.0 to 1.0) and params.y the width of the burning edge.
+// The color of the material is the color of the burning edge.
+
+fn hash(point: vec2<f32>) -> f32 {
+    return fract(sin(dot(point, vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+fn noise(point: vec2<f32>) -> f32 {
+    let cell: vec2<f32> = floor(point);
+    let fraction: vec2<f32> = fract(point);
+    let curve: vec2<f32> = fraction * fraction * (3.0 - 2.0 * fraction);
+
+    return mix(
+        mix(hash(cell), hash(cell + vec2<f32>(1.0, 0.0)), curve.x),
+        mix(hash(cell + vec2<f32>(0.0, 1.0)), hash(cell + vec2<f32>(1.0, 1.0)), curve.x),
+        curve.y
+    );
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color: vec4<f32> = base_color(in);
+    let amount: f32 = material.params.x;
+    let edge: f32 = material.params.y;
+    let value: f32 = noise(in.local_coordinates * 16.0);
+
+    if (value < amount) {
+        discard;
+    }
+    if (value < amount + edge) {
+        return vec4<f32>(material.color.rgb, color.a);
+    }
+    return color;
+}
@@ -0,0 +1,10 @@
+// Flash material: params.x is the intensity of the flash (0.0 to 1.0), usually raised on hit and faded over time.
+// The color of the material is the color of the flash.
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color: vec4<f32> = base_color(in);
+    let intensity: f32 = clamp(material.params.x, 0.0, 1.0);
+
+    return vec4<f32>(mix(color.rgb, material.color.rgb, intensity), color.a);
+}
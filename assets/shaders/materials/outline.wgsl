@@ -0,0 +1,23 @@
+// Outline material: params.x is the thickness of the outline in texels.
+// The color of the material is the color of the outline, drawn around the opaque texels of the texture.
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color: vec4<f32> = base_color(in);
+
+    if (color.a > 0.5) {
+        return color;
+    }
+    let texel: vec2<f32> = material.params.x / vec2<f32>(textureDimensions(texture));
+    var alpha: f32 = 0.0;
+
+    alpha = max(alpha, textureSampleLevel(texture, texture_sampler, in.texture_coordinates + vec2<f32>(texel.x, 0.0), 0.0).a);
+    alpha = max(alpha, textureSampleLevel(texture, texture_sampler, in.texture_coordinates - vec2<f32>(texel.x, 0.0), 0.0).a);
+    alpha = max(alpha, textureSampleLevel(texture, texture_sampler, in.texture_coordinates + vec2<f32>(0.0, texel.y), 0.0).a);
+    alpha = max(alpha, textureSampleLevel(texture, texture_sampler, in.texture_coordinates - vec2<f32>(0.0, texel.y), 0.0).a);
+
+    if (alpha > 0.5) {
+        return material.color;
+    }
+    return color;
+}
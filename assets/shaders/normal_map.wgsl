@@ -0,0 +1,63 @@
+// Shader responsible for rendering the normal maps of batched sprites into the normals of the frame, used by the lighting.
+// The textures are loaded as sRGB, so the normals are encoded back to get their raw values.
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) texture_coordinates: vec2<f32>,
+    @location(2) color: vec4<f32>
+};
+
+struct InstanceInput {
+    @location(3) transform_0: vec4<f32>,
+    @location(4) transform_1: vec4<f32>,
+    @location(5) transform_2: vec4<f32>,
+    @location(6) transform_3: vec4<f32>,
+    @location(7) uv_rect: vec4<f32>,
+    @location(8) tint: vec4<f32>,
+    @location(9) rendering_type: u32
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) texture_coordinates: vec2<f32>,
+    @location(1) alpha: f32
+};
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+
+@group(1) @binding(0) var<uniform> projection: mat4x4<f32>;
+@group(1) @binding(1) var<uniform> view: mat4x4<f32>;
+
+@group(2) @binding(0) var normal_map: texture_2d<f32>;
+@group(2) @binding(1) var normal_map_sampler: sampler;
+
+@vertex
+fn vs_main(in: VertexInput, instance: InstanceInput) -> VertexOutput {
+    var out: VertexOutput;
+    let transform: mat4x4<f32> = mat4x4<f32>(
+        instance.transform_0,
+        instance.transform_1,
+        instance.transform_2,
+        instance.transform_3
+    );
+
+    out.clip_position = projection * view * transform * vec4<f32>(in.position, 1.0);
+    out.texture_coordinates = instance.uv_rect.xy + in.texture_coordinates * instance.uv_rect.zw;
+    out.alpha = in.color.a * instance.tint.a;
+    return out;
+}
+
+// Returns the sRGB encoding of a linear color.
+fn to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let low: vec3<f32> = color * 12.92;
+    let high: vec3<f32> = 1.055 * pow(color, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, color <= vec3<f32>(0.0031308));
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let alpha: f32 = textureSample(texture, texture_sampler, in.texture_coordinates).a * in.alpha;
+    let normal: vec3<f32> = textureSample(normal_map, normal_map_sampler, in.texture_coordinates).rgb;
+    return vec4<f32>(to_srgb(normal), alpha);
+}
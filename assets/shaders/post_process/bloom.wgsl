@@ -0,0 +1,21 @@
+// Bloom effect: params.x is the brightness threshold, params.y the intensity and params.z the radius in pixels.
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color: vec4<f32> = source_color(in.texture_coordinates);
+    let texel: vec2<f32> = max(post_process.params.z, 1.0) / post_process.resolution;
+    var glow: vec3<f32> = vec3<f32>(0.0);
+    var weight: f32 = 0.0;
+
+    for (var x: i32 = -2; x <= 2; x++) {
+        for (var y: i32 = -2; y <= 2; y++) {
+            let sampled: vec3<f32> = source_color(in.texture_coordinates + vec2<f32>(f32(x), f32(y)) * texel).rgb;
+            let brightness: f32 = max(sampled.r, max(sampled.g, sampled.b));
+            let sample_weight: f32 = 1.0 / (1.0 + f32(x * x + y * y));
+
+            glow += sampled * step(post_process.params.x, brightness) * sample_weight;
+            weight += sample_weight;
+        }
+    }
+    return vec4<f32>(color.rgb + glow / weight * post_process.params.y, 1.0);
+}
@@ -0,0 +1,19 @@
+// Color grading effect: params.x is the intensity of the grading.
+// The effect texture is a lookup table (LUT) with its blue slices side by side, like a 256x16 strip for a LUT of size 16.
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color: vec3<f32> = clamp(source_color(in.texture_coordinates).rgb, vec3<f32>(0.0), vec3<f32>(1.0));
+    let size: f32 = f32(textureDimensions(effect_texture).y);
+    let blue: f32 = color.b * (size - 1.0);
+    let first_slice: f32 = floor(blue);
+    let second_slice: f32 = min(first_slice + 1.0, size - 1.0);
+    let x: f32 = (color.r * (size - 1.0) + 0.5) / (size * size);
+    let y: f32 = (color.g * (size - 1.0) + 0.5) / size;
+
+    let first: vec3<f32> = textureSampleLevel(effect_texture, effect_sampler, vec2<f32>(x + first_slice / size, y), 0.0).rgb;
+    let second: vec3<f32> = textureSampleLevel(effect_texture, effect_sampler, vec2<f32>(x + second_slice / size, y), 0.0).rgb;
+    let graded: vec3<f32> = mix(first, second, blue - first_slice);
+
+    return vec4<f32>(mix(color, graded, clamp(post_process.params.x, 0.0, 1.0)), 1.0);
+}
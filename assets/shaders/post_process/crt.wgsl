@@ -0,0 +1,17 @@
+// CRT effect: params.x is the intensity of the scanlines and params.y the curvature of the screen.
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var centered: vec2<f32> = in.texture_coordinates * 2.0 - 1.0;
+    let offset: vec2<f32> = abs(centered.yx) * post_process.params.y;
+    centered = centered + centered * offset * offset;
+
+    let texture_coordinates: vec2<f32> = centered * 0.5 + 0.5;
+    let color: vec3<f32> = source_color(texture_coordinates).rgb;
+
+    if (any(texture_coordinates < vec2<f32>(0.0)) || any(texture_coordinates > vec2<f32>(1.0))) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    let scanline: f32 = sin(texture_coordinates.y * post_process.resolution.y * 3.14159265) * 0.5 + 0.5;
+    return vec4<f32>(color * (1.0 - post_process.params.x * (1.0 - scanline)), 1.0);
+}
@@ -0,0 +1,10 @@
+// Vignette effect: params.x is the intensity and params.y the smoothness (0.0 to 1.0) of the darkened borders.
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color: vec4<f32> = source_color(in.texture_coordinates);
+    let distance: f32 = length(in.texture_coordinates - vec2<f32>(0.5)) * 1.41421356;
+    let vignette: f32 = smoothstep(1.0 - clamp(post_process.params.y, 0.01, 1.0), 1.0, distance) * post_process.params.x;
+
+    return vec4<f32>(color.rgb * (1.0 - vignette), 1.0);
+}
@@ -0,0 +1,35 @@
+// Prelude of the post-processing shaders, with the bindings and the full-screen vertex stage shared by every effect.
+// An effect shader only declares its fragment stage: fn fs_main(in: VertexOutput) -> @location(0) vec4<f32>.
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) texture_coordinates: vec2<f32>
+};
+
+struct PostProcessUniforms {
+    time: f32,
+    resolution: vec2<f32>,
+    params: vec4<f32>
+};
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+
+@group(1) @binding(0) var<uniform> post_process: PostProcessUniforms;
+@group(1) @binding(1) var effect_texture: texture_2d<f32>;
+@group(1) @binding(2) var effect_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let position: vec2<f32> = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+
+    out.clip_position = vec4<f32>(position * 2.0 - 1.0, 0.0, 1.0);
+    out.texture_coordinates = vec2<f32>(position.x, 1.0 - position.y);
+    return out;
+}
+
+// Returns the color of the frame on certain texture coordinates.
+fn source_color(texture_coordinates: vec2<f32>) -> vec4<f32> {
+    return textureSampleLevel(source, source_sampler, texture_coordinates, 0.0);
+}
@@ -28,15 +28,6 @@ impl LittleBallRespawnTimer {
     }
 }
 
-#[derive(Clone, Resource)]
-pub struct NextState(pub GameState);
-
-impl Default for NextState {
-    fn default() -> Self {
-        return Self(GameState::Stopped);
-    }
-}
-
 #[derive(Clone, PartialEq, Debug)]
 pub enum GameState {
     Running,
@@ -89,38 +80,37 @@ fn setup(context: &mut Context) {
     };
 
     context.commands.add_resources(vec![
-        Box::new(LittleBallRespawnTimer::new()),
-        Box::new(NextState::default())
+        Box::new(LittleBallRespawnTimer::new())
     ]);
-    context.commands.spawn(vec![Box::new(start_text)]);
-
-    context.commands.spawn(
-        vec![
-            Box::new(player),
-            Box::new(Player()),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(0.0, -0.85), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.15, 0.10)
-            )),
-            Box::new(Velocity::new(Vector2::new(2.0, 2.0))),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Rectangle)))
-        ]
-    );
-
-    context.commands.spawn(
-        vec![
-            Box::new(little_ball),
-            Box::new(LittleBall()),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(0.0, -0.5), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.10, 0.10)
-            )),
-            Box::new(Velocity::new(Vector2::new(velocity_x, -0.5))),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Square)))
-        ]
-    );
+    context.init_state(GameState::Stopped);
+    context.schedule
+        .add_system(OnExit(GameState::Stopped), System::new("despawn_start_text", despawn_start_text))
+        .add_system(Stage::Update, System::new("play", play).after("update").run_if(in_state(GameState::Running)));
+    context.commands.spawn(start_text);
+
+    context.commands.spawn((
+        player,
+        Player(),
+        Transform::new(
+            Position::new(Vector2::new(0.0, -0.85), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.15, 0.10)
+        ),
+        Velocity::new(Vector2::new(2.0, 2.0)),
+        Collision::new(Collider::new_simple(GeometryType::Rectangle))
+    ));
+
+    context.commands.spawn((
+        little_ball,
+        LittleBall(),
+        Transform::new(
+            Position::new(Vector2::new(0.0, -0.5), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.10, 0.10)
+        ),
+        Velocity::new(Vector2::new(velocity_x, -0.5)),
+        Collision::new(Collider::new_simple(GeometryType::Square))
+    ));
 
     spawn_border(context, Vector2::new(1.05, 0.0));
     spawn_border(context, Vector2::new(-1.05, 0.0));
@@ -136,49 +126,50 @@ fn update(context: &mut Context) {
         keyboard_input.is_key_released(KeyboardKey::Enter) ||
         (mouse_input.is_mouse_button_released(MouseButton::Left) && is_hover)
     {
-        let mut next_state: ResourceRefMut<'_, NextState> = context.world.get_resource_mut::<NextState>().unwrap();
-        next_state.0 = GameState::Running;
+        context.world.get_resource_mut::<NextState<GameState>>().unwrap().set(GameState::Running);
+    }
+}
 
-        let mut query: Query = Query::new(&context.world).with::<Text>();
-        if let Some(entity) = query.entities_with_components().unwrap().first() {
-            context.commands.despawn(entity.clone());
-        }
+fn despawn_start_text(context: &mut Context) {
+    let mut query: Query = Query::new(&context.world).with::<Text>();
+    if let Some(entity) = query.entities_with_components().unwrap().first() {
+        context.commands.despawn(entity.clone());
     }
+}
 
-    if context.world.get_resource::<NextState>().unwrap().0 == GameState::Running {
-        let mut player_query: Query = Query::new(&context.world).with::<Player>();
-        let player_entity: Entity = player_query.entities_with_components().unwrap().first().unwrap().clone();
+fn play(context: &mut Context) {
+    let keyboard_input: KeyboardInput = context.world.get_resource_cloned::<KeyboardInput>().unwrap();
 
-        let mut little_ball_query: Query = Query::new(&context.world).with::<LittleBall>();
-        let little_ball_entity: Entity = little_ball_query.entities_with_components().unwrap().first().unwrap().clone();
+    let mut player_query: Query = Query::new(&context.world).with::<Player>();
+    let player_entity: Entity = player_query.entities_with_components().unwrap().first().unwrap().clone();
 
-        let mut thread_rng: ThreadRng = rand::rng();
-        let random_factor: f32 = thread_rng.random_range(-0.5..0.5);
+    let mut little_ball_query: Query = Query::new(&context.world).with::<LittleBall>();
+    let little_ball_entity: Entity = little_ball_query.entities_with_components().unwrap().first().unwrap().clone();
 
-        move_player(context, keyboard_input, player_entity);
-        move_little_ball(context, little_ball_entity);
-        check_player_little_ball_collision(context, player_entity, little_ball_entity, random_factor);
-        check_little_ball_borders_collision(context, little_ball_entity, random_factor);
-        check_litte_ball_targets_collision(context, little_ball_entity, random_factor);
-        respawn_little_ball_after_outbounds(context, little_ball_entity);
-    }
+    let mut thread_rng: ThreadRng = rand::rng();
+    let random_factor: f32 = thread_rng.random_range(-0.5..0.5);
+
+    move_player(context, keyboard_input, player_entity);
+    move_little_ball(context, little_ball_entity);
+    check_player_little_ball_collision(context, player_entity, little_ball_entity, random_factor);
+    check_little_ball_borders_collision(context, little_ball_entity, random_factor);
+    check_litte_ball_targets_collision(context, little_ball_entity, random_factor);
+    respawn_little_ball_after_outbounds(context, little_ball_entity);
 }
 
 fn spawn_border(context: &mut Context, position: Vector2<f32>) {
     let border: Shape = Shape::new(Orientation::Vertical, GeometryType::Rectangle, Color::by_option(ColorOption::Cyan));
 
-    context.commands.spawn(
-        vec![
-            Box::new(border),
-            Box::new(Border()),
-            Box::new(Transform::new(
-                Position::new(position, Strategy::Normalized),
-                0.0,
-                Vector2::new(0.01, context.window_configuration.height as f32)
-            )),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Rectangle)))
-        ]
-    );
+    context.commands.spawn((
+        border,
+        Border(),
+        Transform::new(
+            Position::new(position, Strategy::Normalized),
+            0.0,
+            Vector2::new(0.01, context.window_configuration.height as f32)
+        ),
+        Collision::new(Collider::new_simple(GeometryType::Rectangle))
+    ));
 }
 
 fn spawn_targets(context: &mut Context) {
@@ -208,18 +199,16 @@ fn spawn_targets(context: &mut Context) {
                 color = Color::by_option(ColorOption::Yellow);
             }
 
-            context.commands.spawn(
-                vec![
-                    Box::new(Shape::new(Orientation::Horizontal, GeometryType::Rectangle, color)),
-                    Box::new(Target()),
-                    Box::new(Transform::new(
-                        Position::new(Vector2::new(x, y), Strategy::Normalized),
-                        0.0,
-                        Vector2::new(width, height)
-                    )),
-                    Box::new(Collision::new(Collider::new_simple(GeometryType::Rectangle))),
-                ]
-            );
+            context.commands.spawn((
+                Shape::new(Orientation::Horizontal, GeometryType::Rectangle, color),
+                Target(),
+                Transform::new(
+                    Position::new(Vector2::new(x, y), Strategy::Normalized),
+                    0.0,
+                    Vector2::new(width, height)
+                ),
+                Collision::new(Collider::new_simple(GeometryType::Rectangle))
+            ));
         }
     }
 }
@@ -15,28 +15,28 @@ fn setup(context: &mut Context) {
     let table: Shape = Shape::new(Orientation::Horizontal, GeometryType::Rectangle, Color::by_option(ColorOption::Black));
     let object: Shape = Shape::new(Orientation::Horizontal, GeometryType::Circle(Circle::default()), Color::by_option(ColorOption::Blue));
 
-    context.commands.spawn(vec![
-        Box::new(table),
-        Box::new(Transform::new(
+    context.commands.spawn((
+        table,
+        Transform::new(
             Position::new(Vector2::new(0.0, -0.70), Strategy::Normalized),
             0.0,
             Vector2::new(0.90, 0.10)
-        )),
-        Box::new(Collision::new(Collider::new_simple(GeometryType::Rectangle)))
-    ]);
+        ),
+        Collision::new(Collider::new_simple(GeometryType::Rectangle))
+    ));
 
-    context.commands.spawn(vec![
-        Box::new(object),
-        Box::new(Transform::new(
+    context.commands.spawn((
+        object,
+        Transform::new(
             Position::new(Vector2::new(400.0, 100.0), Strategy::Pixelated),
             0.0,
             Vector2::new(0.50, 0.50)
-        )),
-        Box::new(Collision::new(Collider::new_simple(GeometryType::Square))),
-        Box::new(Gravity::new(0.0)),
-        Box::new(Velocity::new(Vector2::new(0.2, 0.2))),
-        Box::new(RigidBody::new(BodyType::Dynamic, 1.0, 0.9, 1.0))
-    ]);
+        ),
+        Collision::new(Collider::new_simple(GeometryType::Square)),
+        Gravity::new(0.0),
+        Velocity::new(Vector2::new(0.2, 0.2)),
+        RigidBody::new(BodyType::Dynamic, 1.0, 0.9, 1.0)
+    ));
 }
 
 fn update(context: &mut Context) {
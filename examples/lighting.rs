@@ -0,0 +1,64 @@
+//! This example is a show off about the 2D lighting, with point lights, an ambient light, normal maps and hard shadows.
+//! A torch follows the mouse cursor and casts shadows from the pillars, which are occluders shaped by their colliders.
+//! The lotus sprite has a normal map, so it is shaded by the direction of the lights around it.
+//! Press the space key to toggle the shadows of the torch.
+
+use lotus_engine::*;
+
+#[derive(Clone, Component)]
+struct Torch();
+
+your_game!(
+    WindowConfiguration::default(),
+    setup,
+    update
+);
+
+fn setup(context: &mut Context) {
+    context.world.add_resource(Box::new(AmbientLight::new(Color::by_option(ColorOption::Blue), 0.15)));
+
+    context.commands.spawn(
+        SpriteBundle::new(
+            Sprite::new("textures/lotus_pink_128x128.png".to_string()).normal_map("textures/dome_normal_128x128.png".to_string()),
+            Transform::new_simple(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized))
+        )
+    );
+    spawn_pillar(context, Vector2::new(-0.8, 0.4));
+    spawn_pillar(context, Vector2::new(0.7, 0.5));
+    spawn_pillar(context, Vector2::new(-0.5, -0.5));
+    spawn_pillar(context, Vector2::new(0.6, -0.4));
+
+    context.commands.spawn((
+        Torch(),
+        PointLight2d::new().color(Color::by_option(ColorOption::Orange)).intensity(1.5).radius(1.2).cast_shadows(true),
+        Transform::new_simple(Position::new(Vector2::new(400.0, 300.0), Strategy::Pixelated))
+    ));
+    context.commands.spawn((
+        PointLight2d::new().color(Color::by_option(ColorOption::Purple)).radius(0.6).height(0.3),
+        Transform::new_simple(Position::new(Vector2::new(1.0, -0.8), Strategy::Normalized))
+    ));
+}
+
+fn update(context: &mut Context) {
+    let mouse_input: MouseInput = context.world.get_resource_cloned::<MouseInput>().unwrap();
+    let keyboard_input: KeyboardInput = context.world.get_resource_cloned::<KeyboardInput>().unwrap();
+
+    for (_, mut point_light, mut transform) in context.world.query::<(&Torch, &mut PointLight2d, &mut Transform)>() {
+        transform.position = Position::new(Vector2::new(mouse_input.mouse_position.x, mouse_input.mouse_position.y), Strategy::Pixelated);
+
+        if keyboard_input.is_key_released(KeyboardKey::Space) {
+            point_light.cast_shadows = !point_light.cast_shadows;
+        }
+    }
+}
+
+fn spawn_pillar(context: &mut Context, position: Vector2<f32>) {
+    let scale: Vector2<f32> = Vector2::new(0.08, 0.08);
+
+    context.commands.spawn((
+        Shape::new(Orientation::Horizontal, GeometryType::Square, Color::by_option(ColorOption::Gray)),
+        Transform::new(Position::new(position, Strategy::Normalized), 0.0, scale),
+        Collision::new(Collider::new(GeometryType::Square, position, scale)),
+        Occluder::new()
+    ));
+}
@@ -0,0 +1,58 @@
+//! This example is a show off about rendering sprites with materials, custom WGSL shaders applied per entity.
+//! The left sprite dissolves and comes back over time, the middle one flashes when the space key is pressed.
+//! And the right one is drawn with an outline.
+//! Custom materials are created with 'Material::new', by the path of a WGSL file with a 'fs_main' function.
+
+use lotus_engine::*;
+
+#[derive(Clone, Component)]
+struct Dissolving();
+
+#[derive(Clone, Component)]
+struct Flashing();
+
+your_game!(
+    WindowConfiguration::default(),
+    setup,
+    update
+);
+
+fn setup(context: &mut Context) {
+    context.commands.spawn((
+        SpriteBundle::new(
+            Sprite::new("textures/lotus_pink_128x128.png".to_string()),
+            Transform::new_simple(Position::new(Vector2::new(-0.75, 0.0), Strategy::Normalized))
+        ),
+        Material::dissolve(0.0, Color::by_option(ColorOption::Orange)),
+        Dissolving()
+    ));
+    context.commands.spawn((
+        SpriteBundle::new(
+            Sprite::new("textures/lotus_pink_128x128.png".to_string()),
+            Transform::new_simple(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized))
+        ),
+        Material::flash(Color::by_option(ColorOption::White)),
+        Flashing()
+    ));
+    context.commands.spawn((
+        SpriteBundle::new(
+            Sprite::new("textures/lotus_pink_128x128.png".to_string()),
+            Transform::new_simple(Position::new(Vector2::new(0.75, 0.0), Strategy::Normalized))
+        ),
+        Material::outline(Color::by_option(ColorOption::Black), 2.0)
+    ));
+}
+
+fn update(context: &mut Context) {
+    let is_space_pressed: bool = context.world.get_resource::<KeyboardInput>().unwrap().is_key_pressed(KeyboardKey::Space);
+    let time: f32 = context.render_state.elapsed_time;
+
+    for (mut material, _) in context.world.query::<(&mut Material, &Dissolving)>() {
+        material.set_param(0, (time.sin() + 1.0) / 2.0);
+    }
+
+    for (mut material, _) in context.world.query::<(&mut Material, &Flashing)>() {
+        let intensity: f32 = if is_space_pressed { 1.0 } else { (material.params[0] - context.delta * 4.0).max(0.0) };
+        material.set_param(0, intensity);
+    }
+}
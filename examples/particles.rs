@@ -0,0 +1,80 @@
+//! This example is a show off about the particle emitters, drawing thousands of particles without spawning them as entities.
+//! A campfire emits smoke on the bottom of the screen, while sparks follow the mouse cursor leaving a trail behind.
+//! Click with the left mouse button to spawn an explosion, which is despawned after its particles die.
+
+use lotus_engine::*;
+
+#[derive(Clone, Component)]
+struct Sparks();
+
+#[derive(Clone, Component)]
+struct Explosion();
+
+your_game!(
+    WindowConfiguration::default(),
+    setup,
+    update
+);
+
+fn setup(context: &mut Context) {
+    context.commands.spawn((
+        ParticleEmitter::shape(GeometryType::Circle(Circle::new(16, 0.5)))
+            .spawn_rate(60.0)
+            .lifetime(2.0, 3.5)
+            .velocity(Vector2::new(-0.05, 0.15), Vector2::new(0.05, 0.3))
+            .gravity(Vector2::new(0.04, 0.0))
+            .spawn_radius(0.05)
+            .color(
+                ParticleGradient::linear(Color::new(0.4, 0.4, 0.4, 0.8), Color::new(0.8, 0.8, 0.8, 0.0))
+                    .key(0.1, Color::by_option(ColorOption::Orange))
+            )
+            .size(ParticleCurve::linear(0.05, 0.25)),
+        Transform::new_simple(Position::new(Vector2::new(0.0, -0.8), Strategy::Normalized))
+    ));
+    context.commands.spawn((
+        Sparks(),
+        ParticleEmitter::texture("textures/lotus_pink_32x32.png".to_string())
+            .spawn_rate(120.0)
+            .lifetime(0.5, 1.0)
+            .velocity(Vector2::new(-0.3, -0.3), Vector2::new(0.3, 0.3))
+            .color(ParticleGradient::linear(Color::by_option(ColorOption::White), Color::new(1.0, 1.0, 1.0, 0.0)))
+            .size(ParticleCurve::linear(0.04, 0.0)),
+        Transform::new_simple(Position::new(Vector2::new(400.0, 300.0), Strategy::Pixelated))
+    ));
+}
+
+fn update(context: &mut Context) {
+    let mouse_input: MouseInput = context.world.get_resource_cloned::<MouseInput>().unwrap();
+    let mouse_position: Vector2<f32> = Vector2::new(mouse_input.mouse_position.x, mouse_input.mouse_position.y);
+
+    for (_, mut transform) in context.world.query::<(&Sparks, &mut Transform)>() {
+        transform.position = Position::new(mouse_position, Strategy::Pixelated);
+    }
+
+    if mouse_input.is_mouse_button_released(MouseButton::Left) {
+        context.commands.spawn((
+            Explosion(),
+            ParticleEmitter::shape(GeometryType::Square)
+                .emitting(false)
+                .burst(500)
+                .lifetime(0.4, 1.2)
+                .velocity(Vector2::new(-1.0, -1.0), Vector2::new(1.0, 1.0))
+                .gravity(Vector2::new(0.0, -1.5))
+                .color(
+                    ParticleGradient::linear(Color::by_option(ColorOption::Yellow), Color::new(0.2, 0.0, 0.0, 0.0))
+                        .key(0.3, Color::by_option(ColorOption::Red))
+                )
+                .size(ParticleCurve::linear(0.015, 0.005)),
+            Transform::new_simple(Position::new(mouse_position, Strategy::Pixelated))
+        ));
+    }
+
+    let finished_explosions: Vec<Entity> = context.world.query::<(Entity, &Explosion, &ParticleEmitter)>()
+        .filter(|(_, _, particle_emitter)| particle_emitter.is_finished())
+        .map(|(entity, _, _)| entity)
+        .collect();
+
+    for entity in finished_explosions {
+        context.commands.despawn(entity);
+    }
+}
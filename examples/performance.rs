@@ -7,6 +7,10 @@ struct Object();
 #[derive(Clone, Component)]
 struct Border();
 
+// Update the number of spawns for performance benchmarking.
+// Sprites sharing a texture and shapes sharing a geometry are drawn in batches, so it scales to tens of thousands.
+const NUMBER_OF_OBJECTS: u32 = 20_000;
+
 your_game!(
     WindowConfiguration::default(),
     setup,
@@ -22,27 +26,36 @@ fn setup(context: &mut Context) {
     let bottom_border_perimeter: f64 = -0.9;
     let top_border_perimeter: f64 = 0.9;
 
-    // Update the number of spawns for performance benchmarking.
-    for _ in 1..=10 {
+    for index in 1..=NUMBER_OF_OBJECTS {
         let randomic_position_x: f32 = thread_rng.random_range(left_border_perimeter..right_border_perimiter) as f32;
         let randomic_position_y: f32 = thread_rng.random_range(bottom_border_perimeter..top_border_perimeter) as f32;
 
         let randomic_velocity_x: f32 = thread_rng.random_range(-0.5..0.5) as f32;
         let randomic_velocity_y: f32 = thread_rng.random_range(-0.5..0.5) as f32;
 
-        context.commands.spawn(
-            vec![
-                Box::new(Shape::new(Orientation::Horizontal, GeometryType::Circle(circle.clone()), Color::by_option(ColorOption::Blue))),
-                Box::new(Object()),
-                Box::new(Transform::new(
-                    Position::new(Vector2::new(randomic_position_x, randomic_position_y), Strategy::Normalized),
-                    0.0,
-                    Vector2::new(0.15, 0.15)
-                )),
-                Box::new(Velocity::new(Vector2::new(randomic_velocity_x, randomic_velocity_y))),
-                Box::new(Collision::new(Collider::new_simple(GeometryType::Square)))
-            ]
-        );
+        let position: Position = Position::new(Vector2::new(randomic_position_x, randomic_position_y), Strategy::Normalized);
+        let velocity: Velocity = Velocity::new(Vector2::new(randomic_velocity_x, randomic_velocity_y));
+        let collision: Collision = Collision::new(Collider::new_simple(GeometryType::Square));
+
+        // The circles are drawn above the sprites, so each kind is grouped on a single instanced draw.
+        if index % 2 == 0 {
+            context.commands.spawn((
+                Sprite::new("textures/lotus_pink_32x32.png".to_string()),
+                Object(),
+                Transform::new(position, 0.0, Vector2::new(0.5, 0.5)),
+                velocity,
+                collision
+            ));
+        } else {
+            context.commands.spawn((
+                Shape::new(Orientation::Horizontal, GeometryType::Circle(circle.clone()), Color::by_option(ColorOption::Blue)),
+                Object(),
+                Transform::new(position, 0.0, Vector2::new(0.02, 0.02)),
+                DrawOrder(1),
+                velocity,
+                collision
+            ));
+        }
     }
 
     spawn_border(context, Orientation::Horizontal, Vector2::new(0.0, -1.), Vector2::new(context.window_configuration.width as f32, 0.01));
@@ -64,18 +77,16 @@ fn update(context: &mut Context) {
 fn spawn_border(context: &mut Context, orientation: Orientation, position: Vector2<f32>, scale: Vector2<f32>) {
     let border: Shape = Shape::new(orientation, GeometryType::Rectangle, Color::by_option(ColorOption::White));
 
-    context.commands.spawn(
-        vec![
-            Box::new(border),
-            Box::new(Border()),
-            Box::new(Transform::new(
-                Position::new(position, Strategy::Normalized),
-                0.0,
-                scale
-            )),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Rectangle)))
-        ]
-    );
+    context.commands.spawn((
+        border,
+        Border(),
+        Transform::new(
+            Position::new(position, Strategy::Normalized),
+            0.0,
+            scale
+        ),
+        Collision::new(Collider::new_simple(GeometryType::Rectangle))
+    ));
 }
 
 fn check_border_collision(context: &mut Context, entities: &Vec<Entity>) {
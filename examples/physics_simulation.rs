@@ -22,33 +22,29 @@ fn setup(context: &mut Context) {
     let red_object: Shape = Shape::new(Orientation::Horizontal, GeometryType::Circle(circle.clone()), Color::by_option(ColorOption::Red));
     let blue_object: Shape = Shape::new(Orientation::Horizontal, GeometryType::Circle(circle.clone()), Color::by_option(ColorOption::Blue));
 
-    context.commands.spawn(
-        vec![
-            Box::new(red_object),
-            Box::new(Object()),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.30, 0.30)
-            )),
-            Box::new(Velocity::new(Vector2::new(0.45, 0.45))),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Square)))
-        ]
-    );
-
-    context.commands.spawn(
-        vec![
-            Box::new(blue_object),
-            Box::new(Object()),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(-0.45, 0.0), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.30, 0.30)
-            )),
-            Box::new(Velocity::new(Vector2::new(0.45, 0.45))),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Square)))
-        ]
-    );
+    context.commands.spawn((
+        red_object,
+        Object(),
+        Transform::new(
+            Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.30, 0.30)
+        ),
+        Velocity::new(Vector2::new(0.45, 0.45)),
+        Collision::new(Collider::new_simple(GeometryType::Square))
+    ));
+
+    context.commands.spawn((
+        blue_object,
+        Object(),
+        Transform::new(
+            Position::new(Vector2::new(-0.45, 0.0), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.30, 0.30)
+        ),
+        Velocity::new(Vector2::new(0.45, 0.45)),
+        Collision::new(Collider::new_simple(GeometryType::Square))
+    ));
 
     spawn_border(context, Orientation::Horizontal, Vector2::new(0.0, -1.), Vector2::new(context.window_configuration.width as f32, 0.01));
     spawn_border(context, Orientation::Horizontal, Vector2::new(0.0, 1.), Vector2::new(context.window_configuration.width as f32, 0.01));
@@ -68,18 +64,16 @@ fn update(context: &mut Context) {
 fn spawn_border(context: &mut Context, orientation: Orientation, position: Vector2<f32>, scale: Vector2<f32>) {
     let border: Shape = Shape::new(orientation, GeometryType::Rectangle, Color::by_option(ColorOption::White));
 
-    context.commands.spawn(
-        vec![
-            Box::new(border),
-            Box::new(Border()),
-            Box::new(Transform::new(
-                Position::new(position, Strategy::Normalized),
-                0.0,
-                scale
-            )),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Rectangle)))
-        ]
-    );
+    context.commands.spawn((
+        border,
+        Border(),
+        Transform::new(
+            Position::new(position, Strategy::Normalized),
+            0.0,
+            scale
+        ),
+        Collision::new(Collider::new_simple(GeometryType::Rectangle))
+    ));
 }
 
 fn check_border_collision(context: &mut Context, entities: &Vec<Entity>) {
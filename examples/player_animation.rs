@@ -54,10 +54,10 @@ fn setup(context: &mut Context) {
     let mut animation: Animation = Animation::new(my_animations);
     animation.play("idle".to_string());
 
-    context.commands.spawn(vec![
-        Box::new(animation),
-        Box::new(Velocity::new(Vector2::new(0.5, 0.5)))
-    ]);
+    context.commands.spawn((
+        animation,
+        Velocity::new(Vector2::new(0.5, 0.5))
+    ));
 }
 
 fn update(context: &mut Context) {
@@ -57,7 +57,8 @@ your_game!(
         transparent: false,
         active: true,
         enabled_buttons: WindowButtons::CLOSE | WindowButtons::MINIMIZE,
-        present_mode: PresentMode::AutoNoVsync
+        present_mode: PresentMode::AutoNoVsync,
+        ..Default::default()
     },
     setup,
     update
@@ -90,49 +91,43 @@ fn setup(context: &mut Context) {
     spawn_border(context, Vector2::new(0.0, -1.0));
     spawn_border(context, Vector2::new(0.0, 1.0));
 
-    context.commands.spawn(
-        vec![
-            Box::new(gray_racket_sprite),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(-1.0, 0.23), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.55, 0.55)
-            )),
-            Box::new(Racket()),
-            Box::new(GrayRacket()),
-            Box::new(Velocity::new(Vector2::new(1.5, 1.5))),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Square)))
-        ]
-    );
-
-    context.commands.spawn(
-        vec![
-            Box::new(pink_racket_sprite),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(1.0, 0.25), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.55, 0.55)
-            )),
-            Box::new(Racket()),
-            Box::new(PinkRacket()),
-            Box::new(Velocity::new(Vector2::new(1.5, 1.5))),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Square)))
-        ]
-    );
-
-    context.commands.spawn(
-        vec![
-            Box::new(pong_ball_sprite),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.55, 0.55)
-            )),
-            Box::new(PongBall()),
-            Box::new(Velocity::new(Vector2::new(1.0, 1.0))),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Square)))
-        ]
-    );
+    context.commands.spawn((
+        gray_racket_sprite,
+        Transform::new(
+            Position::new(Vector2::new(-1.0, 0.23), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.55, 0.55)
+        ),
+        Racket(),
+        GrayRacket(),
+        Velocity::new(Vector2::new(1.5, 1.5)),
+        Collision::new(Collider::new_simple(GeometryType::Square))
+    ));
+
+    context.commands.spawn((
+        pink_racket_sprite,
+        Transform::new(
+            Position::new(Vector2::new(1.0, 0.25), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.55, 0.55)
+        ),
+        Racket(),
+        PinkRacket(),
+        Velocity::new(Vector2::new(1.5, 1.5)),
+        Collision::new(Collider::new_simple(GeometryType::Square))
+    ));
+
+    context.commands.spawn((
+        pong_ball_sprite,
+        Transform::new(
+            Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.55, 0.55)
+        ),
+        PongBall(),
+        Velocity::new(Vector2::new(1.0, 1.0)),
+        Collision::new(Collider::new_simple(GeometryType::Square))
+    ));
 }
 
 fn update(context: &mut Context) {
@@ -155,18 +150,16 @@ fn update(context: &mut Context) {
 fn spawn_border(context: &mut Context, position: Vector2<f32>) {
     let border: Shape = Shape::new(Orientation::Horizontal, GeometryType::Rectangle, Color::by_option(ColorOption::Black));
 
-    context.commands.spawn(
-        vec![
-            Box::new(border),
-            Box::new(Border()),
-            Box::new(Transform::new(
-                Position::new(position, Strategy::Normalized),
-                0.0,
-                Vector2::new(context.window_configuration.width as f32, 0.01)
-            )),
-            Box::new(Collision::new(Collider::new_simple(GeometryType::Rectangle)))
-        ]
-    );
+    context.commands.spawn((
+        border,
+        Border(),
+        Transform::new(
+            Position::new(position, Strategy::Normalized),
+            0.0,
+            Vector2::new(context.window_configuration.width as f32, 0.01)
+        ),
+        Collision::new(Collider::new_simple(GeometryType::Rectangle))
+    ));
 }
 
 fn move_gray_racket(context: &mut Context, keyboard_input: KeyboardInput) {
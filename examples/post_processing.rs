@@ -0,0 +1,60 @@
+//! This example is a show off about the post-processing chain, full-screen effects applied on the whole frame.
+//! The frame starts with bloom, vignette and CRT scanlines set on the window configuration.
+//! Press the space key to toggle the chain and the V key to toggle a custom grayscale effect.
+//! Custom effects are created with 'PostEffect::custom', by the path of a WGSL file with a 'fs_main' function.
+
+use lotus_engine::*;
+
+your_game!(
+    WindowConfiguration::default()
+        .background_color(Some(Color::by_option(ColorOption::Black)))
+        .post_process(
+            PostProcess::new()
+                .effect(PostEffect::bloom(0.7, 1.2))
+                .effect(PostEffect::vignette(0.6, 0.5))
+                .effect(PostEffect::crt(0.35, 0.12))
+        ),
+    setup,
+    update
+);
+
+fn setup(context: &mut Context) {
+    context.commands.spawn(
+        SpriteBundle::new(
+            Sprite::new("textures/lotus_pink_256x256.png".to_string()),
+            Transform::new_simple(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized))
+        )
+    );
+    context.commands.spawn(
+        ShapeBundle::new(
+            Shape::new(Orientation::Horizontal, GeometryType::Circle(Circle::new(64, 0.5)), Color::by_option(ColorOption::Yellow)),
+            Transform::new(
+                Position::new(Vector2::new(-0.6, 0.4), Strategy::Normalized),
+                0.0,
+                Vector2::new(0.25, 0.25)
+            )
+        )
+    );
+}
+
+fn update(context: &mut Context) {
+    let (is_space_pressed, is_v_pressed): (bool, bool) = {
+        let keyboard_input: ResourceRef<'_, KeyboardInput> = context.world.get_resource::<KeyboardInput>().unwrap();
+        (keyboard_input.is_key_released(KeyboardKey::Space), keyboard_input.is_key_released(KeyboardKey::KeyV))
+    };
+    let mut post_process: ResourceRefMut<'_, PostProcess> = context.world.get_resource_mut::<PostProcess>().unwrap();
+
+    if is_space_pressed {
+        post_process.enabled = !post_process.enabled;
+    }
+
+    if is_v_pressed {
+        let grayscale: PostEffect = PostEffect::custom("shaders/grayscale.wgsl".to_string(), vec![1.0]);
+
+        if let Some(index) = post_process.effects.iter().position(|effect| *effect == grayscale) {
+            post_process.effects.remove(index);
+        } else {
+            post_process.push(grayscale);
+        }
+    }
+}
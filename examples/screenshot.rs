@@ -0,0 +1,56 @@
+//! This example is a show off about capturing screenshots and recording frame sequences at runtime.
+//! Pressing 'F12' saves a screenshot of the next frame as a PNG file.
+//! Pressing 'R' starts or stops recording every other frame, to be turned into a GIF by external tools.
+
+use lotus_engine::*;
+
+your_game!(
+    WindowConfiguration::default(),
+    setup,
+    update
+);
+
+fn setup(context: &mut Context) {
+    let sprite: Sprite = Sprite::new("textures/lotus_pink_256x256.png".to_string());
+
+    context.commands.spawn((
+        SpriteBundle::new(sprite, Transform::new_simple(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized))),
+        Velocity::new(Vector2::new(0.50, 0.50))
+    ));
+}
+
+fn update(context: &mut Context) {
+    let (is_screenshot_requested, is_recording_toggled) = {
+        let keyboard_input: ResourceRef<'_, KeyboardInput> = context.world.get_resource::<KeyboardInput>().unwrap();
+        (keyboard_input.is_key_released(KeyboardKey::F12), keyboard_input.is_key_released(KeyboardKey::KeyR))
+    };
+
+    if is_screenshot_requested {
+        context.capture_screenshot("captures/screenshot.png".to_string());
+    }
+
+    if is_recording_toggled {
+        if context.is_recording() {
+            let frames_recorded: u32 = context.stop_recording();
+            println!("Recorded {} frames.", frames_recorded);
+        } else {
+            context.start_recording(FrameRecorder::new("captures/frames".to_string()).frame_interval(2).max_frames(600));
+        }
+    }
+
+    let mut query: Query = Query::new(&context.world).with::<Sprite>();
+    let results: Vec<Entity> = query.entities_with_components().unwrap();
+    let entity: &Entity = results.first().unwrap();
+
+    let mut transform: ComponentRefMut<'_, Transform> = context.world.get_entity_component_mut::<Transform>(entity).unwrap();
+    let mut velocity: ComponentRefMut<'_, Velocity> = context.world.get_entity_component_mut::<Velocity>(entity).unwrap();
+
+    if transform.position.x.abs() > 0.75 {
+        velocity.x *= -1.0;
+    }
+    if transform.position.y.abs() > 0.75 {
+        velocity.y *= -1.0;
+    }
+    transform.position.x += velocity.x * context.delta;
+    transform.position.y += velocity.y * context.delta;
+}
@@ -26,7 +26,7 @@ fn setup(context: &mut Context) {
 
     let mut animation: Animation = Animation::default();
     animation.add_sprite_sheet("run".to_string(), scarfy_sprite_sheet);
-    context.commands.spawn(vec![Box::new(dummy), Box::new(animation)]);
+    context.commands.spawn((dummy, animation));
 }
 
 fn update(context: &mut Context) {
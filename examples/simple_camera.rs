@@ -21,46 +21,38 @@ fn setup(context: &mut Context) {
         "Boiler Plate".to_string()
     );
 
-    context.commands.spawn(
-        vec![
-            Box::new(player),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.25, 0.25)
-            )),
-            Box::new(Velocity::new(Vector2::new(1.0, 1.0)))
-        ]
-    );
+    context.commands.spawn((
+        player,
+        Transform::new(
+            Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.25, 0.25)
+        ),
+        Velocity::new(Vector2::new(1.0, 1.0))
+    ));
 
-    context.commands.spawn(
-        vec![
-            Box::new(secondary_sprite),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(-0.25, 0.0), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.25, 0.25)
-            ))
-        ]
-    );
+    context.commands.spawn((
+        secondary_sprite,
+        Transform::new(
+            Position::new(Vector2::new(-0.25, 0.0), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.25, 0.25)
+        )
+    ));
 
-    context.commands.spawn(
-        vec![
-            Box::new(shape),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(-0.75, 0.0), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.25, 0.25)
-            )),
-            Box::new(Velocity::new(Vector2::new(1.0, 1.0)))
-        ]
-    );
+    context.commands.spawn((
+        shape,
+        Transform::new(
+            Position::new(Vector2::new(-0.75, 0.0), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.25, 0.25)
+        ),
+        Velocity::new(Vector2::new(1.0, 1.0))
+    ));
 
-    context.commands.spawn(
-        vec![
-            Box::new(text)
-        ]
-    );
+    context.commands.spawn((
+        text,
+    ));
 }
 
 fn update(context: &mut Context) {
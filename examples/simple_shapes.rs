@@ -35,60 +35,48 @@ fn setup(context: &mut Context) {
         Color::by_option(ColorOption::Black)
     );
 
-    context.commands.spawn(
-        vec![
-            Box::new(my_square),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(-0.60, -0.25), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.10, 0.10)
-            )),
-            Box::new(MySquare())
-        ]
-    );
-    context.commands.spawn(
-        vec![
-            Box::new(my_rectangle),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(-0.35, 0.20), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.50, 0.50)
-            )),
-            Box::new(MyRectangle())
-        ]
-    );
-    context.commands.spawn(
-        vec![
-            Box::new(my_triangle),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(0.50, 0.50), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.25, 0.25)
-            )),
-            Box::new(MyTriangle())
-        ]
-    );
-    context.commands.spawn(
-        vec![
-            Box::new(my_circle),
-            Box::new(Transform::new(
-                Position::new(Vector2::new(0.80, 0.50), Strategy::Normalized),
-                0.0,
-                Vector2::new(0.25, 0.25)
-            )),
-            Box::new(MyCircle())
-        ]
-    );
+    context.commands.spawn((
+        my_square,
+        Transform::new(
+            Position::new(Vector2::new(-0.60, -0.25), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.10, 0.10)
+        ),
+        MySquare()
+    ));
+    context.commands.spawn((
+        my_rectangle,
+        Transform::new(
+            Position::new(Vector2::new(-0.35, 0.20), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.50, 0.50)
+        ),
+        MyRectangle()
+    ));
+    context.commands.spawn((
+        my_triangle,
+        Transform::new(
+            Position::new(Vector2::new(0.50, 0.50), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.25, 0.25)
+        ),
+        MyTriangle()
+    ));
+    context.commands.spawn((
+        my_circle,
+        Transform::new(
+            Position::new(Vector2::new(0.80, 0.50), Strategy::Normalized),
+            0.0,
+            Vector2::new(0.25, 0.25)
+        ),
+        MyCircle()
+    ));
 }
 
 fn update(context: &mut Context) {
     context.commands.show_fps(context.game_loop_listener.current_fps, Color::by_option(ColorOption::Black));
 
-    let mut query: Query = Query::new(&context.world).with::<Shape>();
-    let entities: Vec<Entity> = query.entities_with_components().unwrap();
-
-    for entity in &entities {
-        let mut transform: ComponentRefMut<'_, Transform> = context.world.get_entity_component_mut::<Transform>(entity).unwrap();
+    for (_, mut transform) in context.world.query::<(&Shape, &mut Transform)>() {
         let rotation: f32 = transform.get_rotation() + 100.0 * context.delta;
         transform.set_rotation(&context.render_state, rotation);
     }
@@ -14,13 +14,10 @@ your_game!(
 fn setup(context: &mut Context) {
     let sprite: Sprite = Sprite::new("textures/lotus_pink_256x256.png".to_string());
 
-    context.commands.spawn(
-        vec![
-            Box::new(sprite),
-            Box::new(Transform::new_simple(Position::new(Vector2::new(-0.50, -0.50), Strategy::Normalized))),
-            Box::new(Velocity::new(Vector2::new(0.50, 0.50)))
-        ]
-    );
+    context.commands.spawn((
+        SpriteBundle::new(sprite, Transform::new_simple(Position::new(Vector2::new(-0.50, -0.50), Strategy::Normalized))),
+        Velocity::new(Vector2::new(0.50, 0.50))
+    ));
 }
 
 fn update(context: &mut Context) {
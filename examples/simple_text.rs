@@ -19,7 +19,7 @@ fn setup(context: &mut Context) {
         Color::new(0.0, 0.0, 0.0, 1.0),
         "Hello Text!".to_string()
     );
-    context.commands.spawn(vec![Box::new(underdog_regular)]);
+    context.commands.spawn(underdog_regular);
 
     let codystar_light: Text = Text::new(
         &mut context.render_state,
@@ -28,7 +28,7 @@ fn setup(context: &mut Context) {
         Color::by_option(ColorOption::Blue),
         "Hello Text!".to_string()
     );
-    context.commands.spawn(vec![Box::new(codystar_light)]);
+    context.commands.spawn(codystar_light);
 
     let codystar_regular: Text = Text::new(
         &mut context.render_state,
@@ -37,7 +37,7 @@ fn setup(context: &mut Context) {
         Color::by_option(ColorOption::Magenta),
         "Hello Text!".to_string()
     );
-    context.commands.spawn(vec![Box::new(codystar_regular)]);
+    context.commands.spawn(codystar_regular);
 
     let roboto_mono: Text = Text::new(
         &mut context.render_state,
@@ -46,7 +46,7 @@ fn setup(context: &mut Context) {
         Color::by_option(ColorOption::Brown),
         "Hello Text!".to_string()
     );
-    context.commands.spawn(vec![Box::new(roboto_mono)]);
+    context.commands.spawn(roboto_mono);
 
     let roboto_mono_italic: Text = Text::new(
         &mut context.render_state,
@@ -55,7 +55,7 @@ fn setup(context: &mut Context) {
         Color::by_option(ColorOption::Red),
         "Hello Text!".to_string()
     );
-    context.commands.spawn(vec![Box::new(roboto_mono_italic)]);
+    context.commands.spawn(roboto_mono_italic);
 }
 
 fn update(_context: &mut Context) {}
@@ -0,0 +1,92 @@
+//! This example is a show off about camera entities, with split-screen local multiplayer and a minimap.
+//! The left player moves with WASD and the right player with the arrow keys, each one followed by its own camera.
+//! A third camera renders the whole arena into the 'minimap' texture, drawn by a sprite far from the arena.
+//! And a small camera on the top of the window looks at that sprite.
+
+use lotus_engine::*;
+
+#[derive(Clone, Component)]
+struct Player(KeyboardKey, KeyboardKey, KeyboardKey, KeyboardKey);
+
+your_game!(
+    WindowConfiguration::default().width(1000.0).height(500.0),
+    setup,
+    update
+);
+
+fn setup(context: &mut Context) {
+    for (x, color) in [(-0.5, ColorOption::Red), (0.5, ColorOption::Blue)] {
+        context.commands.spawn((
+            Shape::new(Orientation::Horizontal, GeometryType::Square, Color::by_option(ColorOption::Lightgray)),
+            Transform::new(Position::new(Vector2::new(x, 0.0), Strategy::Normalized), 0.0, Vector2::new(0.2, 0.2))
+        ));
+        context.commands.spawn((
+            Shape::new(Orientation::Horizontal, GeometryType::Circle(Circle::new(64, 0.5)), Color::by_option(color)),
+            Transform::new(Position::new(Vector2::new(x * 2.0, x), Strategy::Normalized), 0.0, Vector2::new(0.1, 0.1)),
+            DrawOrder(1)
+        ));
+    }
+
+    let left_player: Entity = context.commands.spawn((
+        Sprite::new("textures/lotus_pink_128x128.png".to_string()),
+        Transform::new(Position::new(Vector2::new(-0.25, 0.0), Strategy::Normalized), 0.0, Vector2::new(0.5, 0.5)),
+        Player(KeyboardKey::KeyW, KeyboardKey::KeyA, KeyboardKey::KeyS, KeyboardKey::KeyD),
+        DrawOrder(2)
+    ));
+    let right_player: Entity = context.commands.spawn((
+        Sprite::new("textures/lotus_white_32x32.png".to_string()),
+        Transform::new(Position::new(Vector2::new(0.25, 0.0), Strategy::Normalized), 0.0, Vector2::new(2.0, 2.0)),
+        Player(KeyboardKey::ArrowUp, KeyboardKey::ArrowLeft, KeyboardKey::ArrowDown, KeyboardKey::ArrowRight),
+        DrawOrder(2)
+    ));
+
+    context.commands.spawn(Camera::new()
+        .viewport(Viewport::new(0.0, 0.0, 0.5, 1.0))
+        .follow(left_player)
+        .clear_color(Some(Color::by_option(ColorOption::White)))
+    );
+    context.commands.spawn(Camera::new()
+        .viewport(Viewport::new(0.5, 0.0, 0.5, 1.0))
+        .follow(right_player)
+        .clear_color(Some(Color::by_option(ColorOption::Black)))
+    );
+    context.commands.spawn(Camera::new()
+        .target(CameraTarget::texture("minimap".to_string(), 200, 100))
+        .zoom(1.5)
+        .clear_color(Some(Color::by_option(ColorOption::Darkgray)))
+    );
+    context.commands.spawn((
+        Sprite::new("minimap".to_string()),
+        Transform::new_simple(Position::new(Vector2::new(10.0, 0.0), Strategy::Normalized))
+    ));
+    context.commands.spawn((
+        Camera::new()
+            .viewport(Viewport::new(0.4, 0.0, 0.2, 0.2))
+            .zoom(0.2)
+            .order(1),
+        Transform::new_simple(Position::new(Vector2::new(10.0, 0.0), Strategy::Normalized))
+    ));
+}
+
+fn update(context: &mut Context) {
+    let keyboard_input: ResourceRef<'_, KeyboardInput> = context.world.get_resource::<KeyboardInput>().unwrap();
+    let step: f32 = context.delta;
+
+    for (mut transform, player) in context.world.query::<(&mut Transform, &Player)>() {
+        let mut position: Vector2<f32> = Vector2::new(transform.position.x, transform.position.y);
+
+        if keyboard_input.is_key_pressed(player.0.clone()) {
+            position.y += step;
+        }
+        if keyboard_input.is_key_pressed(player.1.clone()) {
+            position.x -= step;
+        }
+        if keyboard_input.is_key_pressed(player.2.clone()) {
+            position.y -= step;
+        }
+        if keyboard_input.is_key_pressed(player.3.clone()) {
+            position.x += step;
+        }
+        transform.set_position(&context.render_state, position);
+    }
+}
@@ -23,7 +23,7 @@ fn setup(context: &mut Context) {
         Color::by_option(ColorOption::Black),
         "Hello Text!".to_string()
     );
-    context.commands.spawn(vec![Box::new(underdog_regular)]);
+    context.commands.spawn(underdog_regular);
 
     let codystar_light: Text = Text::new(
         &mut context.render_state,
@@ -32,7 +32,7 @@ fn setup(context: &mut Context) {
         Color::by_option(ColorOption::Blue),
         "Hello Text!".to_string()
     );
-    context.commands.spawn(vec![Box::new(codystar_light)]);
+    context.commands.spawn(codystar_light);
 
     let codystar_regular: Text = Text::new(
         &mut context.render_state,
@@ -41,7 +41,7 @@ fn setup(context: &mut Context) {
         Color::by_option(ColorOption::Magenta),
         "Hello Text!".to_string()
     );
-    context.commands.spawn(vec![Box::new(codystar_regular)]);
+    context.commands.spawn(codystar_regular);
 
     let roboto_mono: Text = Text::new(
         &mut context.render_state,
@@ -50,7 +50,7 @@ fn setup(context: &mut Context) {
         Color::by_option(ColorOption::Brown),
         "Hello Text!".to_string()
     );
-    context.commands.spawn(vec![Box::new(roboto_mono)]);
+    context.commands.spawn(roboto_mono);
 }
 
 fn update(context: &mut Context) {
@@ -0,0 +1,33 @@
+extern crate proc_macro;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Index};
+
+pub fn derive_bundle_impl(token_stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derive_input: DeriveInput = parse_macro_input!(token_stream as DeriveInput);
+    let name: &syn::Ident = &derive_input.ident;
+    let (impl_generics, type_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    let Data::Struct(data_struct) = &derive_input.data else {
+        return syn::Error::new_spanned(name, "Bundle can only be derived for structs.").to_compile_error().into();
+    };
+
+    let fields: Vec<proc_macro2::TokenStream> = data_struct.fields.iter().enumerate().map(|(index, field)| {
+        if let Some(ident) = &field.ident {
+            return quote! { self.#ident };
+        } else {
+            let index: Index = Index::from(index);
+            return quote! { self.#index };
+        }
+    }).collect();
+
+    let gen: proc_macro2::TokenStream = quote! {
+        impl #impl_generics crate::core::ecs::bundle::Bundle for #name #type_generics #where_clause {
+            fn into_components(self) -> Vec<Box<dyn crate::core::ecs::component::Component>> {
+                let mut components: Vec<Box<dyn crate::core::ecs::component::Component>> = Vec::new();
+                #(components.extend(crate::core::ecs::bundle::Bundle::into_components(#fields));)*
+                return components;
+            }
+        }
+    };
+    return gen.into();
+}
@@ -15,6 +15,14 @@ pub fn derive_component_impl(token_stream: proc_macro::TokenStream) -> proc_macr
             fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
                 return self;
             }
+
+            fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+                return self;
+            }
+
+            fn create_column(&self) -> Box<dyn crate::core::ecs::column::AnyColumn> {
+                return Box::new(crate::core::ecs::column::Column::<Self>::new());
+            }
         }
     };
     return gen.into();
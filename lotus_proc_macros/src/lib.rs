@@ -1,5 +1,7 @@
 mod component;
 mod resource;
+mod bundle;
+mod reflect;
 
 extern crate proc_macro;
 
@@ -14,3 +16,17 @@ pub fn derive_component(token_stream: proc_macro::TokenStream) -> proc_macro::To
 pub fn derive_resource(token_stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
     return resource::derive_resource_impl(token_stream);
 }
+
+/// Derive Macro created to group Components, or other Bundles, that are usually spawned together in our ECS World.
+#[proc_macro_derive(Bundle)]
+pub fn derive_bundle(token_stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    return bundle::derive_bundle_impl(token_stream);
+}
+
+/// Derive Macro created to expose the fields of Components, Resources or any other object to reflection.
+///
+/// Fields marked with *#[reflect(ignore)]* are not reflected, and *#[reflect(default)]* fills the missing fields from Default.
+#[proc_macro_derive(Reflect, attributes(reflect))]
+pub fn derive_reflect(token_stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    return reflect::derive_reflect_impl(token_stream);
+}
@@ -0,0 +1,481 @@
+extern crate proc_macro;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Data, DataEnum, DeriveInput, Fields, Ident, Index, Type};
+
+/// Struct to represent a field of a struct or variant being reflected.
+struct ReflectField {
+    name: String,
+    accessor: proc_macro2::TokenStream,
+    binding: Ident,
+    ty: Type,
+    ignored: bool
+}
+
+pub fn derive_reflect_impl(token_stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut derive_input: DeriveInput = parse_macro_input!(token_stream as DeriveInput);
+    let name: Ident = derive_input.ident.clone();
+
+    let type_parameters: Vec<Ident> = derive_input.generics.type_params().map(|type_param| type_param.ident.clone()).collect();
+    let where_clause: &mut syn::WhereClause = derive_input.generics.make_where_clause();
+    for type_parameter in &type_parameters {
+        where_clause.predicates.push(syn::parse_quote! { #type_parameter: crate::core::ecs::reflect::Reflect });
+    }
+    let (impl_generics, type_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    let container_default: bool = match has_reflect_flag(&derive_input.attrs, "default") {
+        Ok(value) => value,
+        Err(error) => return error.to_compile_error().into()
+    };
+
+    let body: syn::Result<proc_macro2::TokenStream> = match &derive_input.data {
+        Data::Struct(data_struct) => reflect_struct(&data_struct.fields, container_default),
+        Data::Enum(data_enum) => reflect_enum(&name, data_enum),
+        Data::Union(_) => Err(syn::Error::new_spanned(&name, "Reflect can't be derived for unions."))
+    };
+
+    let body: proc_macro2::TokenStream = match body {
+        Ok(body) => body,
+        Err(error) => return error.to_compile_error().into()
+    };
+
+    let gen: proc_macro2::TokenStream = quote! {
+        impl #impl_generics crate::core::ecs::reflect::Reflect for #name #type_generics #where_clause {
+            fn static_type_name() -> &'static str {
+                return stringify!(#name);
+            }
+
+            fn type_name(&self) -> &'static str {
+                return stringify!(#name);
+            }
+
+            #body
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                return self;
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+                return self;
+            }
+        }
+    };
+    return gen.into();
+}
+
+/// Returns if a list of attributes has a specific flag inside a 'reflect' attribute.
+fn has_reflect_flag(attributes: &[Attribute], flag: &str) -> syn::Result<bool> {
+    let mut found: bool = false;
+
+    for attribute in attributes.iter().filter(|attribute| attribute.path().is_ident("reflect")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("ignore") || meta.path.is_ident("default") {
+                found |= meta.path.is_ident(flag);
+                return Ok(());
+            }
+            return Err(meta.error("Unsupported reflect attribute, expected 'ignore' or 'default'."));
+        })?;
+    }
+    return Ok(found);
+}
+
+/// Returns the fields of a struct or variant prepared to be reflected.
+fn collect_fields(fields: &Fields) -> syn::Result<Vec<ReflectField>> {
+    let mut reflect_fields: Vec<ReflectField> = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let ignored: bool = has_reflect_flag(&field.attrs, "ignore")?;
+
+        let reflect_field: ReflectField = if let Some(ident) = &field.ident {
+            ReflectField {
+                name: ident.to_string(),
+                accessor: quote! { #ident },
+                binding: format_ident!("field_{}", ident),
+                ty: field.ty.clone(),
+                ignored
+            }
+        } else {
+            let accessor: Index = Index::from(index);
+            ReflectField {
+                name: index.to_string(),
+                accessor: quote! { #accessor },
+                binding: format_ident!("field_{}", index),
+                ty: field.ty.clone(),
+                ignored
+            }
+        };
+        reflect_fields.push(reflect_field);
+    }
+    return Ok(reflect_fields);
+}
+
+/// Generates the reflection of a struct.
+///
+/// Named structs are reflected as maps, newtypes as their inner value, tuples as lists and unit structs as unit.
+fn reflect_struct(fields: &Fields, container_default: bool) -> syn::Result<proc_macro2::TokenStream> {
+    let all_fields: Vec<ReflectField> = collect_fields(fields)?;
+    let reflected: Vec<&ReflectField> = all_fields.iter().filter(|field| !field.ignored).collect();
+
+    let names: Vec<&String> = reflected.iter().map(|field| &field.name).collect();
+    let accessors: Vec<&proc_macro2::TokenStream> = reflected.iter().map(|field| &field.accessor).collect();
+
+    let fields_fn: proc_macro2::TokenStream = quote! {
+        fn fields(&self) -> Vec<crate::core::ecs::reflect::FieldInfo> {
+            return vec![#(crate::core::ecs::reflect::FieldInfo::new(#names, crate::core::ecs::reflect::Reflect::type_name(&self.#accessors))),*];
+        }
+
+        #[allow(unused_variables)]
+        fn field(&self, name: &str) -> Option<&dyn crate::core::ecs::reflect::Reflect> {
+            return match name {
+                #(#names => Some(&self.#accessors as &dyn crate::core::ecs::reflect::Reflect),)*
+                _ => None
+            };
+        }
+
+        #[allow(unused_variables)]
+        fn field_mut(&mut self, name: &str) -> Option<&mut dyn crate::core::ecs::reflect::Reflect> {
+            return match name {
+                #(#names => Some(&mut self.#accessors as &mut dyn crate::core::ecs::reflect::Reflect),)*
+                _ => None
+            };
+        }
+    };
+
+    let default_binding: proc_macro2::TokenStream = if container_default {
+        quote! { let default: Self = Default::default(); }
+    } else {
+        quote! {}
+    };
+
+    let value_fns: proc_macro2::TokenStream = match fields {
+        Fields::Named(_) => {
+            let initializers: Vec<proc_macro2::TokenStream> = all_fields.iter().map(|field| {
+                let accessor: &proc_macro2::TokenStream = &field.accessor;
+                let ty: &Type = &field.ty;
+                let name: &String = &field.name;
+                let missing: proc_macro2::TokenStream = missing_value(name, accessor, field.ignored, container_default);
+
+                if field.ignored {
+                    return quote! { #accessor: #missing };
+                }
+                return quote! {
+                    #accessor: match value.get(#name) {
+                        Some(entry) => <#ty as crate::core::ecs::reflect::Reflect>::from_value(entry)?,
+                        None => #missing
+                    }
+                };
+            }).collect();
+
+            quote! {
+                fn to_value(&self) -> Result<crate::core::ecs::reflect::ReflectValue, crate::core::ecs::reflect::ReflectError> {
+                    return Ok(crate::core::ecs::reflect::ReflectValue::Map(vec![
+                        #((#names.to_string(), crate::core::ecs::reflect::Reflect::to_value(&self.#accessors)?)),*
+                    ]));
+                }
+
+                fn set_value(&mut self, value: &crate::core::ecs::reflect::ReflectValue) -> Result<(), crate::core::ecs::reflect::ReflectError> {
+                    let crate::core::ecs::reflect::ReflectValue::Map(entries) = value else {
+                        return Err(crate::core::ecs::reflect::ReflectError::TypeMismatch { expected: "map", found: value.kind() });
+                    };
+
+                    for (key, entry) in entries {
+                        match self.field_mut(key) {
+                            Some(field) => field.set_value(entry)?,
+                            None => return Err(crate::core::ecs::reflect::ReflectError::FieldNotFound(key.clone()))
+                        }
+                    }
+                    return Ok(());
+                }
+
+                fn from_value(value: &crate::core::ecs::reflect::ReflectValue) -> Result<Self, crate::core::ecs::reflect::ReflectError> {
+                    if !matches!(value, crate::core::ecs::reflect::ReflectValue::Map(_)) {
+                        return Err(crate::core::ecs::reflect::ReflectError::TypeMismatch { expected: "map", found: value.kind() });
+                    }
+                    #default_binding
+                    return Ok(Self {
+                        #(#initializers),*
+                    });
+                }
+            }
+        },
+        Fields::Unnamed(_) if all_fields.len() == 1 && reflected.len() == 1 => {
+            let ty: &Type = &all_fields[0].ty;
+
+            quote! {
+                fn to_value(&self) -> Result<crate::core::ecs::reflect::ReflectValue, crate::core::ecs::reflect::ReflectError> {
+                    return crate::core::ecs::reflect::Reflect::to_value(&self.0);
+                }
+
+                fn set_value(&mut self, value: &crate::core::ecs::reflect::ReflectValue) -> Result<(), crate::core::ecs::reflect::ReflectError> {
+                    return crate::core::ecs::reflect::Reflect::set_value(&mut self.0, value);
+                }
+
+                fn from_value(value: &crate::core::ecs::reflect::ReflectValue) -> Result<Self, crate::core::ecs::reflect::ReflectError> {
+                    return Ok(Self(<#ty as crate::core::ecs::reflect::Reflect>::from_value(value)?));
+                }
+            }
+        },
+        Fields::Unnamed(_) => {
+            let mut position: usize = 0;
+            let initializers: Vec<proc_macro2::TokenStream> = all_fields.iter().map(|field| {
+                let accessor: &proc_macro2::TokenStream = &field.accessor;
+                let ty: &Type = &field.ty;
+                let missing: proc_macro2::TokenStream = missing_value(&field.name, accessor, field.ignored, container_default);
+
+                if field.ignored {
+                    return missing;
+                }
+                let initializer: proc_macro2::TokenStream = quote! {
+                    match items.get(#position) {
+                        Some(entry) => <#ty as crate::core::ecs::reflect::Reflect>::from_value(entry)?,
+                        None => #missing
+                    }
+                };
+                position += 1;
+                return initializer;
+            }).collect();
+            let positions: Vec<usize> = (0..reflected.len()).collect();
+
+            quote! {
+                fn to_value(&self) -> Result<crate::core::ecs::reflect::ReflectValue, crate::core::ecs::reflect::ReflectError> {
+                    return Ok(crate::core::ecs::reflect::ReflectValue::List(vec![
+                        #(crate::core::ecs::reflect::Reflect::to_value(&self.#accessors)?),*
+                    ]));
+                }
+
+                #[allow(unused_variables)]
+                fn set_value(&mut self, value: &crate::core::ecs::reflect::ReflectValue) -> Result<(), crate::core::ecs::reflect::ReflectError> {
+                    let crate::core::ecs::reflect::ReflectValue::List(items) = value else {
+                        return Err(crate::core::ecs::reflect::ReflectError::TypeMismatch { expected: "list", found: value.kind() });
+                    };
+
+                    #(
+                        if let Some(entry) = items.get(#positions) {
+                            crate::core::ecs::reflect::Reflect::set_value(&mut self.#accessors, entry)?;
+                        }
+                    )*
+                    return Ok(());
+                }
+
+                #[allow(unused_variables)]
+                fn from_value(value: &crate::core::ecs::reflect::ReflectValue) -> Result<Self, crate::core::ecs::reflect::ReflectError> {
+                    let crate::core::ecs::reflect::ReflectValue::List(items) = value else {
+                        return Err(crate::core::ecs::reflect::ReflectError::TypeMismatch { expected: "list", found: value.kind() });
+                    };
+                    #default_binding
+                    return Ok(Self(#(#initializers),*));
+                }
+            }
+        },
+        Fields::Unit => {
+            quote! {
+                fn to_value(&self) -> Result<crate::core::ecs::reflect::ReflectValue, crate::core::ecs::reflect::ReflectError> {
+                    return Ok(crate::core::ecs::reflect::ReflectValue::Unit);
+                }
+
+                fn set_value(&mut self, _value: &crate::core::ecs::reflect::ReflectValue) -> Result<(), crate::core::ecs::reflect::ReflectError> {
+                    return Ok(());
+                }
+
+                fn from_value(_value: &crate::core::ecs::reflect::ReflectValue) -> Result<Self, crate::core::ecs::reflect::ReflectError> {
+                    return Ok(Self);
+                }
+            }
+        }
+    };
+
+    return Ok(quote! {
+        #fields_fn
+        #value_fns
+    });
+}
+
+/// Generates the value used when a field is ignored or missing from the reflected value.
+fn missing_value(name: &str, accessor: &proc_macro2::TokenStream, ignored: bool, container_default: bool) -> proc_macro2::TokenStream {
+    if container_default {
+        return quote! { default.#accessor };
+    } else if ignored {
+        return quote! { Default::default() };
+    } else {
+        return quote! { return Err(crate::core::ecs::reflect::ReflectError::MissingField(#name.to_string())) };
+    }
+}
+
+/// Generates the reflection of an enum.
+///
+/// Unit variants are reflected as their names, and the other ones as a map of their name to their fields.
+fn reflect_enum(name: &Ident, data_enum: &DataEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let mut fields_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut field_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut field_mut_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut to_value_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut unit_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut data_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+
+    for variant in &data_enum.variants {
+        let variant_ident: &Ident = &variant.ident;
+        let variant_name: String = variant_ident.to_string();
+        let fields: Vec<ReflectField> = collect_fields(&variant.fields)?;
+
+        if let Some(field) = fields.iter().find(|field| field.ignored) {
+            return Err(syn::Error::new_spanned(&field.ty, "Fields of enum variants can't be ignored by reflection."));
+        }
+
+        let names: Vec<&String> = fields.iter().map(|field| &field.name).collect();
+        let bindings: Vec<&Ident> = fields.iter().map(|field| &field.binding).collect();
+        let types: Vec<&Type> = fields.iter().map(|field| &field.ty).collect();
+        let accessors: Vec<&proc_macro2::TokenStream> = fields.iter().map(|field| &field.accessor).collect();
+
+        let pattern: proc_macro2::TokenStream = match &variant.fields {
+            Fields::Named(_) => quote! { Self::#variant_ident { #(#accessors: #bindings),* } },
+            Fields::Unnamed(_) => quote! { Self::#variant_ident(#(#bindings),*) },
+            Fields::Unit => quote! { Self::#variant_ident }
+        };
+
+        fields_arms.push(quote! {
+            #pattern => vec![#(crate::core::ecs::reflect::FieldInfo::new(#names, crate::core::ecs::reflect::Reflect::type_name(#bindings))),*]
+        });
+        field_arms.push(quote! {
+            #pattern => match name {
+                #(#names => Some(#bindings as &dyn crate::core::ecs::reflect::Reflect),)*
+                _ => None
+            }
+        });
+        field_mut_arms.push(quote! {
+            #pattern => match name {
+                #(#names => Some(#bindings as &mut dyn crate::core::ecs::reflect::Reflect),)*
+                _ => None
+            }
+        });
+
+        match &variant.fields {
+            Fields::Unit => {
+                to_value_arms.push(quote! {
+                    #pattern => Ok(crate::core::ecs::reflect::ReflectValue::String(#variant_name.to_string()))
+                });
+                unit_arms.push(quote! {
+                    #variant_name => Ok(Self::#variant_ident)
+                });
+            },
+            Fields::Unnamed(_) if fields.len() == 1 => {
+                let ty: &Type = types[0];
+                to_value_arms.push(quote! {
+                    #pattern => Ok(crate::core::ecs::reflect::ReflectValue::Map(vec![
+                        (#variant_name.to_string(), crate::core::ecs::reflect::Reflect::to_value(field_0)?)
+                    ]))
+                });
+                data_arms.push(quote! {
+                    #variant_name => Ok(Self::#variant_ident(<#ty as crate::core::ecs::reflect::Reflect>::from_value(inner)?))
+                });
+            },
+            Fields::Unnamed(_) => {
+                let positions: Vec<usize> = (0..fields.len()).collect();
+                to_value_arms.push(quote! {
+                    #pattern => Ok(crate::core::ecs::reflect::ReflectValue::Map(vec![
+                        (#variant_name.to_string(), crate::core::ecs::reflect::ReflectValue::List(vec![
+                            #(crate::core::ecs::reflect::Reflect::to_value(#bindings)?),*
+                        ]))
+                    ]))
+                });
+                data_arms.push(quote! {
+                    #variant_name => {
+                        let crate::core::ecs::reflect::ReflectValue::List(items) = inner else {
+                            return Err(crate::core::ecs::reflect::ReflectError::TypeMismatch { expected: "list", found: inner.kind() });
+                        };
+                        Ok(Self::#variant_ident(#(
+                            <#types as crate::core::ecs::reflect::Reflect>::from_value(
+                                items.get(#positions).ok_or_else(|| crate::core::ecs::reflect::ReflectError::MissingField(#names.to_string()))?
+                            )?
+                        ),*))
+                    }
+                });
+            },
+            Fields::Named(_) => {
+                to_value_arms.push(quote! {
+                    #pattern => Ok(crate::core::ecs::reflect::ReflectValue::Map(vec![
+                        (#variant_name.to_string(), crate::core::ecs::reflect::ReflectValue::Map(vec![
+                            #((#names.to_string(), crate::core::ecs::reflect::Reflect::to_value(#bindings)?)),*
+                        ]))
+                    ]))
+                });
+                data_arms.push(quote! {
+                    #variant_name => Ok(Self::#variant_ident {
+                        #(#accessors: <#types as crate::core::ecs::reflect::Reflect>::from_value(
+                            inner.get(#names).ok_or_else(|| crate::core::ecs::reflect::ReflectError::MissingField(#names.to_string()))?
+                        )?),*
+                    })
+                });
+            }
+        }
+    }
+
+    if data_enum.variants.is_empty() {
+        return Err(syn::Error::new_spanned(name, "Reflect can't be derived for enums without variants."));
+    }
+
+    let unit_match: proc_macro2::TokenStream = if unit_arms.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            crate::core::ecs::reflect::ReflectValue::String(variant) => match variant.as_str() {
+                #(#unit_arms,)*
+                _ => Err(crate::core::ecs::reflect::ReflectError::UnknownVariant(variant.clone()))
+            },
+        }
+    };
+
+    let data_match: proc_macro2::TokenStream = if data_arms.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            crate::core::ecs::reflect::ReflectValue::Map(entries) if entries.len() == 1 => {
+                let (variant, inner) = &entries[0];
+                match variant.as_str() {
+                    #(#data_arms,)*
+                    _ => Err(crate::core::ecs::reflect::ReflectError::UnknownVariant(variant.clone()))
+                }
+            },
+        }
+    };
+
+    return Ok(quote! {
+        #[allow(unused_variables)]
+        fn fields(&self) -> Vec<crate::core::ecs::reflect::FieldInfo> {
+            return match self {
+                #(#fields_arms),*
+            };
+        }
+
+        #[allow(unused_variables)]
+        fn field(&self, name: &str) -> Option<&dyn crate::core::ecs::reflect::Reflect> {
+            return match self {
+                #(#field_arms),*
+            };
+        }
+
+        #[allow(unused_variables)]
+        fn field_mut(&mut self, name: &str) -> Option<&mut dyn crate::core::ecs::reflect::Reflect> {
+            return match self {
+                #(#field_mut_arms),*
+            };
+        }
+
+        fn to_value(&self) -> Result<crate::core::ecs::reflect::ReflectValue, crate::core::ecs::reflect::ReflectError> {
+            return match self {
+                #(#to_value_arms),*
+            };
+        }
+
+        fn set_value(&mut self, value: &crate::core::ecs::reflect::ReflectValue) -> Result<(), crate::core::ecs::reflect::ReflectError> {
+            *self = <Self as crate::core::ecs::reflect::Reflect>::from_value(value)?;
+            return Ok(());
+        }
+
+        fn from_value(value: &crate::core::ecs::reflect::ReflectValue) -> Result<Self, crate::core::ecs::reflect::ReflectError> {
+            return match value {
+                #unit_match
+                #data_match
+                _ => Err(crate::core::ecs::reflect::ReflectError::TypeMismatch { expected: "variant", found: value.kind() })
+            };
+        }
+    });
+}
@@ -0,0 +1,150 @@
+use cgmath::Vector2;
+use lotus_proc_macros::Bundle;
+use super::{
+    draw_order::DrawOrder,
+    visibility::Visibility,
+    texture::sprite::Sprite,
+    shape::shape::Shape,
+    text::text::Text,
+    physics::{transform::Transform, velocity::Velocity, collision::Collision, rigid_body::RigidBody}
+};
+
+/// Struct to represent the components of an entity rendered as a sprite.
+#[derive(Clone, Bundle)]
+pub struct SpriteBundle {
+    pub sprite: Sprite,
+    pub transform: Transform,
+    pub visibility: Visibility,
+    pub draw_order: DrawOrder
+}
+
+impl SpriteBundle {
+    /// Create a new sprite bundle with its sprite and transform.
+    pub fn new(sprite: Sprite, transform: Transform) -> Self {
+        return Self {
+            sprite,
+            transform,
+            visibility: Visibility::default(),
+            draw_order: DrawOrder::default()
+        };
+    }
+
+    /// Set the visibility on initialization.
+    pub fn visibility(self, visibility: Visibility) -> Self {
+        return Self {
+            visibility,
+            ..self
+        };
+    }
+
+    /// Set the draw order on initialization.
+    pub fn draw_order(self, draw_order: DrawOrder) -> Self {
+        return Self {
+            draw_order,
+            ..self
+        };
+    }
+}
+
+/// Struct to represent the components of an entity rendered as a geometric form.
+#[derive(Clone, Bundle)]
+pub struct ShapeBundle {
+    pub shape: Shape,
+    pub transform: Transform,
+    pub visibility: Visibility,
+    pub draw_order: DrawOrder
+}
+
+impl ShapeBundle {
+    /// Create a new shape bundle with its shape and transform.
+    pub fn new(shape: Shape, transform: Transform) -> Self {
+        return Self {
+            shape,
+            transform,
+            visibility: Visibility::default(),
+            draw_order: DrawOrder::default()
+        };
+    }
+
+    /// Set the visibility on initialization.
+    pub fn visibility(self, visibility: Visibility) -> Self {
+        return Self {
+            visibility,
+            ..self
+        };
+    }
+
+    /// Set the draw order on initialization.
+    pub fn draw_order(self, draw_order: DrawOrder) -> Self {
+        return Self {
+            draw_order,
+            ..self
+        };
+    }
+}
+
+/// Struct to represent the components of an entity rendered as a text.
+///
+/// The text is positioned by its own position, not by a transform.
+#[derive(Clone, Bundle)]
+pub struct TextBundle {
+    pub text: Text,
+    pub visibility: Visibility,
+    pub draw_order: DrawOrder
+}
+
+impl TextBundle {
+    /// Create a new text bundle with its text.
+    pub fn new(text: Text) -> Self {
+        return Self {
+            text,
+            visibility: Visibility::default(),
+            draw_order: DrawOrder::default()
+        };
+    }
+
+    /// Set the visibility on initialization.
+    pub fn visibility(self, visibility: Visibility) -> Self {
+        return Self {
+            visibility,
+            ..self
+        };
+    }
+
+    /// Set the draw order on initialization.
+    pub fn draw_order(self, draw_order: DrawOrder) -> Self {
+        return Self {
+            draw_order,
+            ..self
+        };
+    }
+}
+
+/// Struct to represent the components of an entity that moves and collides.
+///
+/// It has no transform, so it can be combined with a sprite or shape bundle to render the body.
+#[derive(Clone, Bundle)]
+pub struct PhysicsBodyBundle {
+    pub velocity: Velocity,
+    pub collision: Collision,
+    pub rigid_body: RigidBody
+}
+
+impl PhysicsBodyBundle {
+    /// Create a new physics body bundle with its collision and rigid body, without velocity.
+    pub fn new(collision: Collision, rigid_body: RigidBody) -> Self {
+        return Self {
+            velocity: Velocity::new(Vector2::new(0.0, 0.0)),
+            collision,
+            rigid_body
+        };
+    }
+
+    /// Set the velocity on initialization.
+    pub fn velocity(self, velocity: Velocity) -> Self {
+        return Self {
+            velocity,
+            ..self
+        };
+    }
+}
@@ -0,0 +1,195 @@
+use cgmath::{ortho, Matrix4, Vector2, Vector3};
+use lotus_proc_macros::Component;
+use super::super::{color::color::Color, ecs::entity::Entity};
+
+/// Struct to represent the area of a target that a camera renders into.
+///
+/// The values are normalized between 0.0 and 1.0, starting from the top left corner.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        return Self::full();
+    }
+}
+
+impl Viewport {
+    /// Create a new viewport by its normalized position and size.
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        return Self {
+            x,
+            y,
+            width,
+            height
+        };
+    }
+
+    /// Create a new viewport covering the whole target.
+    pub fn full() -> Self {
+        return Self::new(0.0, 0.0, 1.0, 1.0);
+    }
+
+    /// Returns the position and size of the viewport in pixels of a target with a certain size.
+    pub fn to_pixels(&self, width: u32, height: u32) -> (f32, f32, f32, f32) {
+        let x: f32 = (self.x.clamp(0.0, 1.0) * width as f32).floor();
+        let y: f32 = (self.y.clamp(0.0, 1.0) * height as f32).floor();
+        let viewport_width: f32 = (self.width.clamp(0.0, 1.0) * width as f32).floor().min(width as f32 - x).max(1.0);
+        let viewport_height: f32 = (self.height.clamp(0.0, 1.0) * height as f32).floor().min(height as f32 - y).max(1.0);
+
+        return (x, y, viewport_width, viewport_height);
+    }
+}
+
+/// Enumerator to represent where a camera renders into.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum CameraTarget {
+    /// The default value, the window surface.
+    #[default]
+    Window,
+
+    /// An offscreen texture, usable as the source of a sprite by its name.
+    Texture { name: String, width: u32, height: u32 }
+}
+
+impl CameraTarget {
+    /// Create a new offscreen texture target, usable as a sprite with 'Sprite::new(name)'.
+    pub fn texture(name: String, width: u32, height: u32) -> Self {
+        return CameraTarget::Texture { name, width: width.max(1), height: height.max(1) };
+    }
+
+    /// Returns the name of the offscreen texture, if any.
+    pub fn texture_name(&self) -> Option<&str> {
+        return match self {
+            CameraTarget::Window => None,
+            CameraTarget::Texture { name, .. } => Some(name)
+        };
+    }
+}
+
+/// Struct to represent a camera entity, rendering the world on its own viewport and target.
+///
+/// The camera looks at the position of its transform, or at the position of the entity it follows.
+/// Cameras are drawn by their order, the ones with offscreen targets first, so their textures are ready for the window.
+///
+/// While there is any active camera entity, the global camera resource is not used for rendering.
+/// Camera entities are only used when the batching of the rendering state is enabled,
+/// and the texts are drawn once on top of the window, as they are positioned on the screen.
+#[derive(Clone, Debug, Component)]
+pub struct Camera {
+    pub viewport: Viewport,
+    pub zoom: f32,
+    pub clear_color: Option<Color>,
+    pub target: CameraTarget,
+    pub follow: Option<Entity>,
+    pub order: i32,
+    pub active: bool
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        return Self {
+            viewport: Viewport::full(),
+            zoom: 1.0,
+            clear_color: None,
+            target: CameraTarget::Window,
+            follow: None,
+            order: 0,
+            active: true
+        };
+    }
+}
+
+impl Camera {
+    /// Create a new camera rendering the whole window.
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Set the viewport on initialization.
+    pub fn viewport(self, viewport: Viewport) -> Self {
+        return Self {
+            viewport,
+            ..self
+        };
+    }
+
+    /// Set the zoom on initialization.
+    pub fn zoom(self, zoom: f32) -> Self {
+        return Self {
+            zoom,
+            ..self
+        };
+    }
+
+    /// Set the clear color of the viewport on initialization.
+    ///
+    /// Without a clear color, the camera draws over the background of the window.
+    pub fn clear_color(self, clear_color: Option<Color>) -> Self {
+        return Self {
+            clear_color,
+            ..self
+        };
+    }
+
+    /// Set the target on initialization.
+    pub fn target(self, target: CameraTarget) -> Self {
+        return Self {
+            target,
+            ..self
+        };
+    }
+
+    /// Set the entity to be followed on initialization.
+    pub fn follow(self, entity: Entity) -> Self {
+        return Self {
+            follow: Some(entity),
+            ..self
+        };
+    }
+
+    /// Set the drawing order on initialization.
+    pub fn order(self, order: i32) -> Self {
+        return Self {
+            order,
+            ..self
+        };
+    }
+
+    /// Set the active state on initialization.
+    pub fn active(self, active: bool) -> Self {
+        return Self {
+            active,
+            ..self
+        };
+    }
+
+    /// Returns if the camera renders into the window.
+    pub fn is_window_target(&self) -> bool {
+        return self.target == CameraTarget::Window;
+    }
+
+    /// Returns the projection matrix of the camera for a viewport size in pixels.
+    pub fn projection_matrix(&self, width: f32, height: f32) -> Matrix4<f32> {
+        let aspect_ratio: f32 = width / height;
+
+        return ortho(
+            -aspect_ratio * self.zoom,
+            aspect_ratio * self.zoom,
+            -self.zoom,
+            self.zoom,
+            -1.0,
+            1.0
+        );
+    }
+
+    /// Returns the view matrix of the camera looking at a position.
+    pub fn view_matrix(&self, position: Vector2<f32>) -> Matrix4<f32> {
+        return Matrix4::from_translation(Vector3::new(-position.x, -position.y, 0.0));
+    }
+}
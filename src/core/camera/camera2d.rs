@@ -3,6 +3,8 @@ use lotus_proc_macros::Resource;
 use super::super::{ecs::entity::Entity, physics::transform::Transform};
 
 /// Struct to represent the global 2D camera resource in the world.
+///
+/// It is used for rendering while there is no active camera entity.
 #[derive(Clone, Resource)]
 pub struct Camera2d {
     pub transform: Transform,
@@ -26,7 +28,7 @@ impl Camera2d {
     /// Set a new target to the camera following strategy.
     pub fn set_target(&mut self, entity: Entity) {
         if let Some(old_target) = self.target {
-            if old_target.0 != entity.0 {
+            if old_target != entity {
                 self.target = Some(entity);
             }
         } else {
@@ -1,2 +1,5 @@
 /// Camera 2D related features.
 pub mod camera2d;
+
+/// Camera entities with viewports and render targets related features.
+pub mod camera;
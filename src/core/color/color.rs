@@ -1,7 +1,8 @@
+use lotus_proc_macros::Reflect;
 use crate::ColorOption;
 
 /// The struct that holds a Color object in the engine.
-#[derive(Clone, Debug, Copy)]
+#[derive(Clone, Debug, Copy, Reflect)]
 pub struct Color {
     r: f32,
     g: f32,
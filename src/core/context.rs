@@ -1,9 +1,12 @@
 use super::{
     managers::window::manager::WindowConfiguration,
     ecs::{world::World, command::Commands},
-    managers::render::manager::RenderState,
+    event::channel::{Events, EventWriter},
+    schedule::{schedule::Schedule, state::{States, State, NextState}},
+    managers::render::{manager::RenderState, capture::FrameRecorder},
     listeners::{game_loop::listener::GameLoopListener, gamepad::listener::GamepadListener}
 };
+use std::path::PathBuf;
 
 /// Struct created to provide the main features of the engine for the end-user.
 pub struct Context {
@@ -22,6 +25,11 @@ pub struct Context {
     /// Use for mutating the world entities, components and resources.
     pub commands: Commands,
 
+    /// The schedule of systems executed on each frame.
+    ///
+    /// Use for registering, ordering and disabling systems, including the engine built-in ones.
+    pub schedule: Schedule,
+
     /// The window configuration data for reading purposes.
     pub window_configuration: WindowConfiguration,
 
@@ -40,14 +48,74 @@ pub struct Context {
 impl Context {
     /// Create a new context with parameters.
     pub fn new(render_state: RenderState, world: World, window_configuration: WindowConfiguration, delta: f32) -> Self {
+        let commands: Commands = world.commands();
+
         return Self {
             render_state,
             world,
-            commands: Commands::new(),
+            commands,
+            schedule: Schedule::new(),
             window_configuration,
             game_loop_listener: GameLoopListener::new(),
             gamepad_listener: GamepadListener::new(),
             delta
         };
     }
+
+    /// Execute the schedule of the context on the current frame.
+    ///
+    /// While it runs, the systems change a deferred schedule in its place, which is applied after the run.
+    pub fn run_schedule(&mut self) {
+        let deferred: Schedule = self.schedule.deferred();
+        let mut schedule: Schedule = std::mem::replace(&mut self.schedule, deferred);
+        schedule.run(self);
+        schedule.apply_deferred(std::mem::replace(&mut self.schedule, Schedule::empty()));
+        self.schedule = schedule;
+    }
+
+    /// Initialize a game state with its initial value.
+    ///
+    /// The State and NextState resources are added to the world and the state transitions are applied at the start of each frame.
+    pub fn init_state<S: States>(&mut self, initial: S) {
+        self.world.add_resource(Box::new(State::new(initial)));
+        self.world.add_resource(Box::new(NextState::<S>::new()));
+        self.schedule.add_state::<S>();
+    }
+
+    /// Register a typed event channel for user-defined events.
+    ///
+    /// The Events resource is added to the world and its buffers are swapped at the start of each frame.
+    pub fn add_event<T: Send + Sync + 'static>(&mut self) {
+        if self.world.get_resource::<Events<T>>().is_none() {
+            self.world.add_resource(Box::new(Events::<T>::new()));
+        }
+        self.schedule.add_event::<T>();
+    }
+
+    /// Returns a writer to a registered event channel.
+    pub fn event_writer<T: Send + Sync + 'static>(&self) -> Option<EventWriter<'_, T>> {
+        return EventWriter::new(&self.world);
+    }
+
+    /// Capture a screenshot of the next rendered frame, saved as a PNG file on a path relative to the current directory.
+    ///
+    /// The surface is copied after the rendering and the file is encoded on a background thread a few frames later.
+    pub fn capture_screenshot(&mut self, path: String) {
+        self.render_state.screenshot_requests.push(PathBuf::from(path));
+    }
+
+    /// Start recording the rendered frames as a PNG sequence, replacing the current recording.
+    pub fn start_recording(&mut self, frame_recorder: FrameRecorder) {
+        self.render_state.frame_recorder = Some(frame_recorder);
+    }
+
+    /// Stop the current recording and returns the number of recorded frames, even when the recording already finished.
+    pub fn stop_recording(&mut self) -> u32 {
+        return self.render_state.frame_recorder.take().map_or(0, |frame_recorder| frame_recorder.frames_recorded);
+    }
+
+    /// Returns if the rendered frames are being recorded, which stays true for a finished recording until it gets stopped.
+    pub fn is_recording(&self) -> bool {
+        return self.render_state.frame_recorder.is_some();
+    }
 }
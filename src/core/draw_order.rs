@@ -1,10 +1,10 @@
 use std::cmp::Ordering;
-use lotus_proc_macros::Component;
+use lotus_proc_macros::{Component, Reflect};
 use super::ecs::{world::World, entity::Entity};
 
 /// Struct to order the drawing process of entities.
 /// The smaller number will be rendered first.    
-#[derive(Clone, Component)]
+#[derive(Clone, Component, Reflect)]
 pub struct DrawOrder(pub u32);
 
 impl DrawOrder {
@@ -20,7 +20,7 @@ impl DrawOrder {
 
         match a_order.cmp(&b_order) {
             Ordering::Equal => {
-                a.0.cmp(&b.0)
+                a.cmp(b)
             },
             other => other
         }
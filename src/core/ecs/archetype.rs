@@ -1,31 +1,94 @@
 use std::{any::TypeId, collections::HashMap};
-use atomic_refcell::AtomicRefCell;
-use super::{entity::Entity, component::Component};
+use super::{entity::Entity, component::{Component, ComponentTicks}, column::{AnyColumn, Column}};
+
+/// Struct to represent the location of an entity inside of the world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityLocation {
+    pub archetype_id: u64,
+    pub row: usize
+}
 
 /// Struct to represent the different archetypes and/or clusters of data.
+///
+/// Each component type is stored on its own contiguous column and the rows are shared between columns.
 pub struct Archetype {
+    pub id: u64,
     pub entities: Vec<Entity>,
-    pub components: HashMap<TypeId, Vec<AtomicRefCell<Box<dyn Component>>>>
+    pub columns: HashMap<TypeId, Box<dyn AnyColumn>>
 }
 
 impl Archetype {
-    /// Create a new archetype.
-    pub fn new() -> Self {
+    /// Create a new archetype with its unique key.
+    pub fn new(id: u64) -> Self {
         return Self {
+            id,
             entities: Vec::new(),
-            components: HashMap::new()
+            columns: HashMap::new()
         };
     }
 
-    /// Add a new entity to a archetype by its components.
-    pub fn add_entity(&mut self, entity: Entity, components: Vec<AtomicRefCell<Box<dyn Component>>>) {
+    /// Add a new entity to a archetype by its components and returns its row.
+    ///
+    /// The components are flagged as added and changed on the tick passed.
+    pub fn add_entity(&mut self, entity: Entity, components: Vec<Box<dyn Component>>, change_tick: u32) -> usize {
+        return self.add_entity_with_ticks(
+            entity,
+            components.into_iter().map(|component| (component, ComponentTicks::new(change_tick))).collect()
+        );
+    }
+
+    /// Add a new entity to a archetype by its components and their ticks and returns its row.
+    pub fn add_entity_with_ticks(&mut self, entity: Entity, components: Vec<(Box<dyn Component>, ComponentTicks)>) -> usize {
         self.entities.push(entity);
 
-        for component in components {
-            let type_id: TypeId = component.borrow().type_id();
-            self.components.entry(type_id)
-                .or_insert_with(Vec::new)
-                .push(component);
+        for (component, ticks) in components {
+            let type_id: TypeId = component.as_any().type_id();
+            self.columns.entry(type_id)
+                .or_insert_with(|| component.create_column())
+                .push(component, ticks);
+        }
+        return self.entities.len() - 1;
+    }
+
+    /// Removes the entity of a specific row by swapping it with the last one.
+    ///
+    /// Returns the entity that was moved to the row, if any.
+    pub fn swap_remove(&mut self, row: usize) -> Option<Entity> {
+        for column in self.columns.values_mut() {
+            column.swap_remove(row);
+        }
+        self.entities.swap_remove(row);
+        return self.entities.get(row).copied();
+    }
+
+    /// Takes out the components of a specific row by swapping it with the last one.
+    ///
+    /// Returns the components with their ticks and the entity that was moved to the row, if any.
+    pub fn take_entity(&mut self, row: usize) -> (Vec<(Box<dyn Component>, ComponentTicks)>, Option<Entity>) {
+        let components: Vec<(Box<dyn Component>, ComponentTicks)> = self.columns.values_mut().map(|column| column.swap_take(row)).collect();
+        self.entities.swap_remove(row);
+        return (components, self.entities.get(row).copied());
+    }
+
+    /// Replace a component already stored on a specific row.
+    pub fn replace_component(&mut self, row: usize, component: Box<dyn Component>, change_tick: u32) {
+        if let Some(column) = self.columns.get_mut(&component.as_any().type_id()) {
+            column.replace(row, component, change_tick);
         }
     }
+
+    /// Returns if the archetype stores the component type.
+    pub fn has_component(&self, type_id: &TypeId) -> bool {
+        return self.columns.contains_key(type_id);
+    }
+
+    /// Returns the typed column of a specific component.
+    pub fn get_column<T: Component>(&self) -> Option<&Column<T>> {
+        return self.columns.get(&TypeId::of::<T>()).and_then(|column| column.as_any().downcast_ref::<Column<T>>());
+    }
+
+    /// Returns the ticks of a specific component stored on a row.
+    pub fn get_component_ticks(&self, type_id: &TypeId, row: usize) -> Option<&ComponentTicks> {
+        return self.columns.get(type_id).and_then(|column| column.get_ticks(row));
+    }
 }
@@ -0,0 +1,64 @@
+use super::component::Component;
+
+/// The Bundle trait to annotate groups of components that are spawned together.
+///
+/// It's implemented for every component, tuples of bundles and lists of boxed components.
+///
+/// Use the derive macro *Bundle* to create templates of entities, which can also contain other bundles.
+///
+/// If a bundle has more than one component of the same type, the last one is kept.
+pub trait Bundle {
+    /// Returns the components of the bundle.
+    fn into_components(self) -> Vec<Box<dyn Component>>;
+}
+
+impl<T: Component> Bundle for T {
+    fn into_components(self) -> Vec<Box<dyn Component>> {
+        return vec![Box::new(self)];
+    }
+}
+
+impl Bundle for Vec<Box<dyn Component>> {
+    fn into_components(self) -> Vec<Box<dyn Component>> {
+        return self;
+    }
+}
+
+impl<T: Component> Bundle for Vec<Box<T>> {
+    fn into_components(self) -> Vec<Box<dyn Component>> {
+        return self.into_iter().map(|component| component as Box<dyn Component>).collect();
+    }
+}
+
+impl Bundle for () {
+    fn into_components(self) -> Vec<Box<dyn Component>> {
+        return Vec::new();
+    }
+}
+
+macro_rules! impl_bundle_for_tuple {
+    ($($bundle:ident),+) => {
+        impl<$($bundle: Bundle),+> Bundle for ($($bundle,)+) {
+            #[allow(non_snake_case)]
+            fn into_components(self) -> Vec<Box<dyn Component>> {
+                let ($($bundle,)+) = self;
+                let mut components: Vec<Box<dyn Component>> = Vec::new();
+                $(components.extend($bundle.into_components());)+
+                return components;
+            }
+        }
+    };
+}
+
+impl_bundle_for_tuple!(A);
+impl_bundle_for_tuple!(A, B);
+impl_bundle_for_tuple!(A, B, C);
+impl_bundle_for_tuple!(A, B, C, D);
+impl_bundle_for_tuple!(A, B, C, D, E);
+impl_bundle_for_tuple!(A, B, C, D, E, F);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G, H);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_bundle_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
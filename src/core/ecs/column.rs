@@ -0,0 +1,128 @@
+use std::any::Any;
+use atomic_refcell::{AtomicRef, AtomicRefCell, AtomicRefMut};
+use super::component::{Component, ComponentTicks};
+
+/// Struct to represent a contiguous column of components of the same type inside of an archetype.
+pub struct Column<T: Component> {
+    pub components: Vec<AtomicRefCell<T>>,
+    pub ticks: Vec<ComponentTicks>
+}
+
+impl<T: Component> Column<T> {
+    /// Create a new empty column.
+    pub fn new() -> Self {
+        return Self {
+            components: Vec::new(),
+            ticks: Vec::new()
+        };
+    }
+
+    /// Returns the component stored on a specific row.
+    pub fn get(&self, row: usize) -> Option<&AtomicRefCell<T>> {
+        return self.components.get(row);
+    }
+}
+
+impl<T: Component> Default for Column<T> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+/// Trait to operate over the columns of an archetype without knowing the type of its components.
+pub trait AnyColumn: Any + Send + Sync {
+    /// Returns the column as any for downcasting purposes.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns the column as a mutable any for downcasting purposes.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Returns a new empty column of the same component type.
+    fn new_empty(&self) -> Box<dyn AnyColumn>;
+
+    /// Returns the number of components stored on the column.
+    fn len(&self) -> usize;
+
+    /// Returns if the column has no components stored.
+    fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Push a new component to the end of the column.
+    ///
+    /// Panics if the component type is different from the column type.
+    fn push(&mut self, component: Box<dyn Component>, ticks: ComponentTicks);
+
+    /// Replace the component stored on a specific row, flagging it as changed on the tick passed.
+    ///
+    /// Panics if the component type is different from the column type.
+    fn replace(&mut self, row: usize, component: Box<dyn Component>, change_tick: u32);
+
+    /// Removes the component of a specific row by swapping it with the last one and returns it.
+    fn swap_take(&mut self, row: usize) -> (Box<dyn Component>, ComponentTicks);
+
+    /// Removes and drops the component of a specific row by swapping it with the last one.
+    fn swap_remove(&mut self, row: usize);
+
+    /// Returns the component stored on a specific row.
+    fn get_component(&self, row: usize) -> Option<AtomicRef<'_, dyn Component>>;
+
+    /// Returns the component stored on a specific row as mutable.
+    fn get_component_mut(&self, row: usize) -> Option<AtomicRefMut<'_, dyn Component>>;
+
+    /// Returns the ticks of the component stored on a specific row.
+    fn get_ticks(&self, row: usize) -> Option<&ComponentTicks>;
+}
+
+impl<T: Component> AnyColumn for Column<T> {
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
+
+    fn new_empty(&self) -> Box<dyn AnyColumn> {
+        return Box::new(Column::<T>::new());
+    }
+
+    fn len(&self) -> usize {
+        return self.components.len();
+    }
+
+    fn push(&mut self, component: Box<dyn Component>, ticks: ComponentTicks) {
+        let component: Box<T> = component.into_any().downcast::<T>().expect("Component type should match the column type.");
+        self.components.push(AtomicRefCell::new(*component));
+        self.ticks.push(ticks);
+    }
+
+    fn replace(&mut self, row: usize, component: Box<dyn Component>, change_tick: u32) {
+        let component: Box<T> = component.into_any().downcast::<T>().expect("Component type should match the column type.");
+        *self.components[row].get_mut() = *component;
+        self.ticks[row].set_changed(change_tick);
+    }
+
+    fn swap_take(&mut self, row: usize) -> (Box<dyn Component>, ComponentTicks) {
+        let component: T = self.components.swap_remove(row).into_inner();
+        let ticks: ComponentTicks = self.ticks.swap_remove(row);
+        return (Box::new(component), ticks);
+    }
+
+    fn swap_remove(&mut self, row: usize) {
+        self.components.swap_remove(row);
+        self.ticks.swap_remove(row);
+    }
+
+    fn get_component(&self, row: usize) -> Option<AtomicRef<'_, dyn Component>> {
+        return self.components.get(row).map(|component| AtomicRef::map(component.borrow(), |c| c as &dyn Component));
+    }
+
+    fn get_component_mut(&self, row: usize) -> Option<AtomicRefMut<'_, dyn Component>> {
+        return self.components.get(row).map(|component| AtomicRefMut::map(component.borrow_mut(), |c| c as &mut dyn Component));
+    }
+
+    fn get_ticks(&self, row: usize) -> Option<&ComponentTicks> {
+        return self.ticks.get(row);
+    }
+}
@@ -1,48 +1,165 @@
+use std::{any::TypeId, mem::take};
 use super::{
     world::World,
-    entity::Entity,
+    entity::{Entity, Entities},
     component::Component,
+    bundle::Bundle,
     resource::Resource,
-    super::{super::Color, managers::render::manager::RenderState}
+    super::{super::Color, managers::render::manager::RenderState, scene::{Scene, SceneError}, save::{SaveGame, SaveData, SaveError}}
 };
 
+/// Closure executed with exclusive access to the world when the commands are flushed.
+pub type CommandClosure = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
 /// Enumerator that store the mutable commands allowed in the world.
 pub enum Command {
-    Spawn(Vec<Box<dyn Component>>),
+    Spawn(Entity, Vec<Box<dyn Component>>),
+    SpawnChild(Entity, Entity, Vec<Box<dyn Component>>),
+    SpawnScene(Vec<Entity>, Scene),
+    RestoreGame(Vec<Entity>, Scene, Vec<Box<dyn Resource>>),
     Despawn(Entity),
+    Insert(Entity, Box<dyn Component>),
+    Remove(Entity, TypeId),
     AddResource(Box<dyn Resource>),
     AddResources(Vec<Box<dyn Resource>>),
     ShowFps(u32, Color),
-    HideFps
+    HideFps,
+    Closure(CommandClosure)
 }
 
 /// Struct to represent the mutable commands to be made on the world.
+///
+/// The commands are deferred until the buffer is flushed, which happens at the end of each stage of the schedule.
+///
+/// The entities reserved by a buffer that is dropped without being flushed are freed, so their slots are recycled.
 pub struct Commands {
-    pub commands: Vec<Command>
+    pub commands: Vec<Command>,
+    entities: Option<Entities>,
+    reserved: Vec<Entity>
 }
 
 impl Commands {
-    /// Create a new command struct.
+    /// Create a new command struct detached from any world.
+    ///
+    /// Its entities are only allocated when the commands are flushed, so its spawns return Entity::PLACEHOLDER,
+    /// which is never alive. Use World::commands to know the spawned entities before the flush.
     pub fn new() -> Self {
         return Self {
-            commands: Vec::new()
+            commands: Vec::new(),
+            entities: None,
+            reserved: Vec::new()
+        };
+    }
+
+    /// Create a new command struct that reserves its entities on a shared allocator.
+    pub(crate) fn from_entities(entities: Entities) -> Self {
+        return Self {
+            commands: Vec::new(),
+            entities: Some(entities),
+            reserved: Vec::new()
         };
     }
 
+    /// Reserve an entity on the shared allocator until the buffer is flushed, or returns Entity::PLACEHOLDER if the buffer is detached.
+    fn reserve(&mut self) -> Entity {
+        let Some(entities) = &self.entities else {
+            return Entity::PLACEHOLDER;
+        };
+        let entity: Entity = entities.reserve();
+        self.reserved.push(entity);
+        return entity;
+    }
+
     /// Spawn a new entity on the world.
     ///
     /// The entity will be rendered as its type demands.
-    pub fn spawn(&mut self, components: Vec<Box<dyn Component>>) {
-        self.commands.push(Command::Spawn(components));
+    ///
+    /// Accepts a single component, a tuple of components or any bundle, like SpriteBundle.
+    ///
+    /// Returns the entity reserved for it, which is alive once the commands are flushed.
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        let entity: Entity = self.reserve();
+        self.commands.push(Command::Spawn(entity, bundle.into_components()));
+        return entity;
+    }
+
+    /// Spawn a new entity on the world as a child of another one.
+    ///
+    /// The child transform is relative to its parent, like a weapon following the hand of the player.
+    ///
+    /// Returns the entity reserved for it, which is alive once the commands are flushed.
+    pub fn spawn_child(&mut self, parent: Entity, bundle: impl Bundle) -> Entity {
+        let entity: Entity = self.reserve();
+        self.commands.push(Command::SpawnChild(entity, parent, bundle.into_components()));
+        return entity;
+    }
+
+    /// Spawn the entities of a scene file on the world, relative to the assets directory.
+    ///
+    /// The file is loaded right away, so a malformed scene or a missing asset is returned as an error.
+    ///
+    /// Returns the entities reserved for the scene entities, in the order of the file, which are alive once the commands are flushed.
+    pub fn spawn_scene(&mut self, path: &str) -> Result<Vec<Entity>, SceneError> {
+        return Ok(self.instantiate_scene(Scene::load(path)?));
+    }
+
+    /// Spawn the entities of a scene on the world.
+    ///
+    /// Returns the entities reserved for the scene entities, in the order of the scene, which are alive once the commands are flushed.
+    pub fn instantiate_scene(&mut self, scene: Scene) -> Vec<Entity> {
+        let entities: Vec<Entity> = scene.entities.iter().map(|_| self.reserve()).collect();
+        self.commands.push(Command::SpawnScene(entities.clone(), scene));
+        return entities;
+    }
+
+    /// Restore a save file on the world, relative to the assets directory, migrated to the current version of the save game.
+    ///
+    /// The file is loaded right away, so a malformed or unsupported save file is returned as an error.
+    ///
+    /// Returns the entities reserved for the saved entities, which are alive once the commands are flushed.
+    pub fn load_game(&mut self, save_game: &SaveGame, path: &str) -> Result<Vec<Entity>, SaveError> {
+        let save_data: SaveData = save_game.load(path)?;
+        return self.restore_game(save_game, save_data);
+    }
+
+    /// Restore a snapshot on the world, migrated to the current version of the save game.
+    ///
+    /// The persistent entities of the world are replaced by the saved ones, and the saved resources replace the current ones.
+    ///
+    /// Returns the entities reserved for the saved entities, which are alive once the commands are flushed.
+    pub fn restore_game(&mut self, save_game: &SaveGame, save_data: SaveData) -> Result<Vec<Entity>, SaveError> {
+        let mut save_data: SaveData = save_data;
+        save_game.migrate(&mut save_data)?;
+
+        let resources: Vec<Box<dyn Resource>> = save_game.construct_resources(&save_data)?;
+        let entities: Vec<Entity> = save_data.scene.entities.iter().map(|_| self.reserve()).collect();
+        self.commands.push(Command::RestoreGame(entities.clone(), save_data.scene, resources));
+        return Ok(entities);
     }
 
     /// Despawn a specific entity from the world.
     ///
     /// The entity is removed from the rendering flow and its related cached data is cleaned.
+    ///
+    /// Its children are despawned as well.
     pub fn despawn(&mut self, entity: Entity) {
         self.commands.push(Command::Despawn(entity));
     }
 
+    /// Insert a component on a specific entity.
+    ///
+    /// The entity is moved to the archetype that matches its new components, or the component is replaced if it already exists.
+    pub fn insert(&mut self, entity: Entity, component: Box<dyn Component>) {
+        self.commands.push(Command::Insert(entity, component));
+    }
+
+    /// Remove a component from a specific entity.
+    ///
+    /// The entity is moved to the archetype that matches its remaining components.
+    pub fn remove<T: Component>(&mut self, entity: Entity) {
+        self.commands.push(Command::Remove(entity, TypeId::of::<T>()));
+    }
+
     /// Add a new resource to the world.
     pub fn add_resource(&mut self, resource: Box<dyn Resource>) {
         self.commands.push(Command::AddResource(resource));
@@ -53,6 +170,29 @@ impl Commands {
         self.commands.push(Command::AddResources(resources));
     }
 
+    /// Add a closure to be executed with exclusive access to the world.
+    ///
+    /// Useful for mutations that are not covered by the other commands.
+    pub fn add(&mut self, closure: impl FnOnce(&mut World) + Send + Sync + 'static) {
+        self.commands.push(Command::Closure(Box::new(closure)));
+    }
+
+    /// Append the commands of another buffer to this one, keeping their order.
+    ///
+    /// The entities reserved by the other buffer on the same allocator are moved along with its commands.
+    pub fn append(&mut self, other: &mut Commands) {
+        self.commands.append(&mut other.commands);
+
+        if self.is_shared_with(other.entities.as_ref()) {
+            self.reserved.append(&mut other.reserved);
+        }
+    }
+
+    /// Returns if the buffer reserves its entities on the same allocator as the given one.
+    fn is_shared_with(&self, entities: Option<&Entities>) -> bool {
+        return self.entities.as_ref().zip(entities).is_some_and(|(own, other)| own.is_shared_with(other));
+    }
+
     /// Show the current FPS value.
     pub fn show_fps(&mut self, current_fps: u32, color: Color) {
         self.commands.push(Command::ShowFps(current_fps, color));
@@ -65,21 +205,96 @@ impl Commands {
 
     /// Take the commands memory reference.
     pub(crate) fn _take_commands(&mut self) -> Vec<Command> {
-        return std::mem::take(&mut self.commands);
+        return take(&mut self.commands);
     }
 
     /// Flush the commands inside the buffer.
+    ///
+    /// The commands issued by component hooks while flushing are flushed as well, after the current ones.
     pub fn flush_commands(&mut self, world: &mut World, render_state: &mut RenderState) {
-        for command in self.commands.drain(..) {
+        loop {
+            self.append(&mut world.hook_commands);
+
+            if self.commands.is_empty() {
+                return;
+            }
+            self.flush_buffered_commands(world, render_state);
+        }
+    }
+
+    /// Returns the world entities in which the entities of a scene are spawned.
+    fn reserve_targets(world: &World, entities: &[Entity], is_shared: bool) -> Vec<Entity> {
+        if is_shared {
+            return entities.to_vec();
+        }
+        return entities.iter().map(|_| world.entities.reserve()).collect();
+    }
+
+    /// Release the entities reserved for a scene if it failed to spawn.
+    fn release_targets(world: &World, targets: Vec<Entity>, result: Result<(), SceneError>) {
+        if let Err(error) = result {
+            log::error!("{}", error);
+
+            for target in targets {
+                world.entities.activate(target);
+                world.entities.free(target);
+            }
+        }
+    }
+
+    /// Flush the commands currently inside the buffer.
+    ///
+    /// If the buffer does not share the world allocator, its entities are allocated on the world while flushing.
+    fn flush_buffered_commands(&mut self, world: &mut World, render_state: &mut RenderState) {
+        let is_shared: bool = self.is_shared_with(Some(&world.entities));
+
+        if is_shared {
+            self.reserved.clear();
+        }
+
+        for command in take(&mut self.commands) {
             match command {
-                Command::Spawn(components) => {
-                    world.spawn(render_state, components);
+                Command::Spawn(entity, components) => {
+                    if is_shared {
+                        world.spawn_reserved(render_state, entity, components);
+                    } else {
+                        world.spawn(render_state, components);
+                    }
+                },
+                Command::SpawnChild(entity, parent, components) => {
+                    if world.is_entity_alive(parent) {
+                        let child: Entity = if is_shared { entity } else { world.entities.reserve() };
+                        world.spawn_child(render_state, child, &parent, components);
+                    } else if is_shared {
+                        world.entities.activate(entity);
+                        world.entities.free(entity);
+                    }
+                },
+                Command::SpawnScene(entities, scene) => {
+                    let targets: Vec<Entity> = Self::reserve_targets(world, &entities, is_shared);
+                    let result: Result<(), SceneError> = world.spawn_scene(render_state, &targets, &scene);
+                    Self::release_targets(world, targets, result);
+                },
+                Command::RestoreGame(entities, scene, resources) => {
+                    let targets: Vec<Entity> = Self::reserve_targets(world, &entities, is_shared);
+                    let result: Result<(), SceneError> = world.restore_game(render_state, &targets, &scene, resources);
+                    Self::release_targets(world, targets, result);
                 },
                 Command::Despawn(entity) => {
                     if world.is_entity_alive(entity) {
                         world.despawn(render_state, &entity);
                     }
                 },
+                Command::Insert(entity, component) => {
+                    if world.is_entity_alive(entity) {
+                        world.insert_component(render_state, &entity, component);
+                    }
+                },
+                Command::Remove(entity, type_id) => {
+                    if world.is_entity_alive(entity) {
+                        world.remove_component(render_state, &entity, type_id);
+                    }
+                },
                 Command::AddResource(resource) => {
                     world.add_resource(resource);
                 },
@@ -91,8 +306,22 @@ impl Commands {
                 },
                 Command::HideFps => {
                     world.hide_fps(render_state);
+                },
+                Command::Closure(closure) => {
+                    closure(world);
                 }
             }
         }
     }
 }
+
+impl Drop for Commands {
+    fn drop(&mut self) {
+        if let Some(entities) = &self.entities {
+            for entity in take(&mut self.reserved) {
+                entities.activate(entity);
+                entities.free(entity);
+            }
+        }
+    }
+}
@@ -1,43 +1,88 @@
-use std::{any::{Any, TypeId}, collections::HashSet, ops::{Deref, DerefMut}};
-use atomic_refcell::{AtomicRef, AtomicRefCell, AtomicRefMut};
-use uuid::Uuid;
+use std::{any::{Any, TypeId}, collections::{HashMap, HashSet}, ops::{Deref, DerefMut}, sync::{Mutex, atomic::{AtomicU32, Ordering}}};
+use atomic_refcell::{AtomicRef, AtomicRefMut};
+use super::{column::AnyColumn, entity::Entity};
 
 /// The Component trait to annotate which structs are.
 pub trait Component: Any + Send + Sync {
    fn as_any(&self) -> &dyn Any;
 
    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+   fn into_any(self: Box<Self>) -> Box<dyn Any>;
+
+   fn create_column(&self) -> Box<dyn AnyColumn>;
+}
+
+/// Struct to represent the world ticks in which a component was added and last changed.
+pub struct ComponentTicks {
+   pub added: u32,
+   pub changed: AtomicU32
+}
+
+impl ComponentTicks {
+   /// Create a new component ticks struct with the tick of its addition.
+   pub fn new(tick: u32) -> Self {
+      return Self {
+         added: tick,
+         changed: AtomicU32::new(tick)
+      };
+   }
+
+   /// Returns the tick in which the component was last changed.
+   pub fn get_changed(&self) -> u32 {
+      return self.changed.load(Ordering::Relaxed);
+   }
+
+   /// Set the tick in which the component was last changed.
+   pub fn set_changed(&self, tick: u32) {
+      self.changed.store(tick, Ordering::Relaxed);
+   }
+
+   /// Returns if the component was added after the last run tick, up to the current world tick.
+   pub fn is_added(&self, last_run: u32, change_tick: u32) -> bool {
+      return is_tick_newer(self.added, last_run, change_tick);
+   }
+
+   /// Returns if the component was changed after the last run tick, up to the current world tick.
+   pub fn is_changed(&self, last_run: u32, change_tick: u32) -> bool {
+      return is_tick_newer(self.get_changed(), last_run, change_tick);
+   }
+}
+
+/// Returns if a tick comes after the last run tick, comparing their distances to the current world tick so it survives the wrapping.
+fn is_tick_newer(tick: u32, last_run: u32, change_tick: u32) -> bool {
+   return change_tick.wrapping_sub(tick) < change_tick.wrapping_sub(last_run);
 }
 
 /// Struct to represent the actual borrowing state of the entities components.
 pub struct ComponentBorrowState {
-   pub immutable_borrows: HashSet<(TypeId, Uuid)>,
-   pub mutable_borrows: HashSet<(TypeId, Uuid)>
+   pub immutable_borrows: HashMap<(TypeId, Entity), usize>,
+   pub mutable_borrows: HashSet<(TypeId, Entity)>
 }
 
 impl ComponentBorrowState {
    pub fn new() -> Self {
       return Self {
-         immutable_borrows: HashSet::new(),
+         immutable_borrows: HashMap::new(),
          mutable_borrows: HashSet::new()
       };
    }
 
-   pub fn try_borrow_immutable(&mut self, type_id: TypeId, entity_id: Uuid) -> bool {
-      let key: (TypeId, Uuid) = (type_id, entity_id);
+   pub fn try_borrow_immutable(&mut self, type_id: TypeId, entity: Entity) -> bool {
+      let key: (TypeId, Entity) = (type_id, entity);
       
       if self.mutable_borrows.contains(&key) {
          return false;
       } else {
-         self.immutable_borrows.insert(key);
+         *self.immutable_borrows.entry(key).or_insert(0) += 1;
          return true;
       }
    }
 
-   pub fn try_borrow_mutable(&mut self, type_id: TypeId, entity_id: Uuid) -> bool {
-      let key: (TypeId, Uuid) = (type_id, entity_id);
+   pub fn try_borrow_mutable(&mut self, type_id: TypeId, entity: Entity) -> bool {
+      let key: (TypeId, Entity) = (type_id, entity);
 
-      if self.immutable_borrows.contains(&key) || self.mutable_borrows.contains(&key) {
+      if self.immutable_borrows.contains_key(&key) || self.mutable_borrows.contains(&key) {
          return false;
       } else {
          self.mutable_borrows.insert(key);
@@ -45,21 +90,29 @@ impl ComponentBorrowState {
       }
    }
 
-   pub fn release_immutable(&mut self, type_id: TypeId, entity_id: Uuid) {
-      self.immutable_borrows.remove(&(type_id, entity_id));
+   pub fn release_immutable(&mut self, type_id: TypeId, entity: Entity) {
+      let key: (TypeId, Entity) = (type_id, entity);
+
+      if let Some(count) = self.immutable_borrows.get_mut(&key) {
+         *count -= 1;
+
+         if *count == 0 {
+            self.immutable_borrows.remove(&key);
+         }
+      }
    }
 
-   pub fn release_mutable(&mut self, type_id: TypeId, entity_id: Uuid) {
-      self.mutable_borrows.remove(&(type_id, entity_id));
+   pub fn release_mutable(&mut self, type_id: TypeId, entity: Entity) {
+      self.mutable_borrows.remove(&(type_id, entity));
    }
 }
 
 /// Struct to represent the immutable reference of a component.
 pub struct ComponentRef<'a, T: Component + 'static> {
-   pub(crate) inner: AtomicRef<'a, Box<dyn Component>>,
+   pub(crate) inner: AtomicRef<'a, T>,
    pub(crate) type_id: TypeId,
-   pub entity_id: Uuid,
-   pub(crate) component_borrow_state: &'a AtomicRefCell<ComponentBorrowState>,
+   pub entity: Entity,
+   pub(crate) component_borrow_state: &'a Mutex<ComponentBorrowState>,
    pub(crate) phantom_data: std::marker::PhantomData<T>
 }
 
@@ -67,22 +120,26 @@ impl<'a, T: Component + 'static> Deref for ComponentRef<'a, T> {
    type Target = T;
 
    fn deref(&self) -> &T {
-      return self.inner.as_any().downcast_ref::<T>().unwrap();
+      return &self.inner;
    }
 }
 
 impl<'a, T: Component + 'static> Drop for ComponentRef<'a, T> {
    fn drop(&mut self) {
-      self.component_borrow_state.borrow_mut().release_immutable(self.type_id, self.entity_id);
+      self.component_borrow_state.lock().unwrap().release_immutable(self.type_id, self.entity);
    }
 }
 
 /// Struct to represent a mutable reference of a component.
+///
+/// The component is flagged as changed on the current world tick when mutably dereferenced.
 pub struct ComponentRefMut<'a, T: Component + 'static> {
-   pub(crate) inner: AtomicRefMut<'a, Box<dyn Component>>,
+   pub(crate) inner: AtomicRefMut<'a, T>,
    pub(crate) type_id: TypeId,
-   pub entity_id: Uuid,
-   pub(crate) component_borrow_state: &'a AtomicRefCell<ComponentBorrowState>,
+   pub entity: Entity,
+   pub(crate) component_borrow_state: &'a Mutex<ComponentBorrowState>,
+   pub(crate) ticks: &'a ComponentTicks,
+   pub(crate) change_tick: u32,
    pub(crate) phantom_data: std::marker::PhantomData<T>
 }
 
@@ -90,18 +147,19 @@ impl<'a, T: Component + 'static> Deref for ComponentRefMut<'a, T> {
    type Target = T;
 
    fn deref(&self) -> &T {
-      return self.inner.as_any().downcast_ref::<T>().unwrap();
+      return &self.inner;
    }
 }
 
 impl<'a, T: Component + 'static> DerefMut for ComponentRefMut<'a, T> {
    fn deref_mut(&mut self) -> &mut T {
-      return self.inner.as_any_mut().downcast_mut::<T>().unwrap();
+      self.ticks.set_changed(self.change_tick);
+      return &mut self.inner;
    }
 }
 
 impl<'a, T: Component + 'static> Drop for ComponentRefMut<'a, T> {
    fn drop(&mut self) {
-      self.component_borrow_state.borrow_mut().release_mutable(self.type_id, self.entity_id);
+      self.component_borrow_state.lock().unwrap().release_mutable(self.type_id, self.entity);
    }
 }
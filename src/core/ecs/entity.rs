@@ -1,14 +1,179 @@
-use uuid::Uuid;
+use std::sync::{Arc, Mutex};
 
 /// # Struct to represent entities.
 ///
-/// Each entity has a unique identification number generated randomly.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Entity(pub Uuid);
+/// Each entity is a compact generational index.
+///
+/// The index points to a slot that is recycled after a despawn and the generation tells apart the entities that used the same slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    pub index: u32,
+    pub generation: u32
+}
 
 impl Entity {
-    /// Returns a dummy Entity for specific cases.
-    pub(crate) fn dummy() -> Self {
-        return Self(Uuid::new_v4());
+    /// Entity that never is alive on the world, used for specific cases where an entity is not related.
+    pub const PLACEHOLDER: Entity = Entity { index: u32::MAX, generation: 0 };
+
+    /// Create a new entity by its index and generation.
+    pub fn new(index: u32, generation: u32) -> Self {
+        return Self {
+            index,
+            generation
+        };
+    }
+
+    /// Returns the entity packed as a single number, useful as a cheap and deterministic key.
+    pub fn to_bits(&self) -> u64 {
+        return ((self.generation as u64) << 32) | self.index as u64;
+    }
+
+    /// Returns the entity unpacked from a single number.
+    pub fn from_bits(bits: u64) -> Self {
+        return Self {
+            index: bits as u32,
+            generation: (bits >> 32) as u32
+        };
+    }
+}
+
+/// Struct to represent the allocator of entities of the world.
+///
+/// The slots freed are recycled with a new generation, so stale entities are never taken as alive.
+#[derive(Debug, Clone, Default)]
+pub struct EntityAllocator {
+    pub generations: Vec<u32>,
+    pub alive: Vec<bool>,
+    pub free_indexes: Vec<u32>
+}
+
+impl EntityAllocator {
+    /// Create a new entity allocator.
+    pub fn new() -> Self {
+        return Self {
+            generations: Vec::new(),
+            alive: Vec::new(),
+            free_indexes: Vec::new()
+        };
+    }
+
+    /// Allocate a new entity, recycling a freed slot if there is one.
+    pub fn allocate(&mut self) -> Entity {
+        let entity: Entity = self.reserve();
+        self.alive[entity.index as usize] = true;
+        return entity;
+    }
+
+    /// Reserve a new entity that only becomes alive when it is activated, recycling a freed slot if there is one.
+    pub fn reserve(&mut self) -> Entity {
+        if let Some(index) = self.free_indexes.pop() {
+            return Entity::new(index, self.generations[index as usize]);
+        }
+
+        let index: u32 = self.generations.len() as u32;
+        self.generations.push(0);
+        self.alive.push(false);
+        return Entity::new(index, 0);
+    }
+
+    /// Activate a reserved entity, making it alive.
+    ///
+    /// Returns if the entity was reserved and is now alive.
+    pub fn activate(&mut self, entity: Entity) -> bool {
+        let index: usize = entity.index as usize;
+
+        if index >= self.alive.len() || self.alive[index] || self.generations[index] != entity.generation {
+            return false;
+        }
+        self.alive[index] = true;
+        return true;
+    }
+
+    /// Free the slot of an entity, so it can be recycled.
+    ///
+    /// Returns if the entity was alive.
+    pub fn free(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        let index: usize = entity.index as usize;
+        self.alive[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free_indexes.push(entity.index);
+        return true;
+    }
+
+    /// Returns if an entity is alive, which means that its slot was not freed since its allocation.
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        let index: usize = entity.index as usize;
+        return index < self.alive.len() && self.alive[index] && self.generations[index] == entity.generation;
+    }
+
+    /// Returns the number of entities alive or reserved.
+    pub fn len(&self) -> usize {
+        return self.generations.len() - self.free_indexes.len();
+    }
+
+    /// Returns if there is no entity alive or reserved.
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+}
+
+/// Struct to represent the entity allocator shared by the world and its command buffers.
+///
+/// Command buffers reserve their entities through it, so the entities are known before the commands are flushed.
+#[derive(Debug, Clone, Default)]
+pub struct Entities {
+    allocator: Arc<Mutex<EntityAllocator>>
+}
+
+impl Entities {
+    /// Create a new shared entity allocator.
+    pub fn new() -> Self {
+        return Self {
+            allocator: Arc::new(Mutex::new(EntityAllocator::new()))
+        };
+    }
+
+    /// Allocate a new entity, recycling a freed slot if there is one.
+    pub fn allocate(&self) -> Entity {
+        return self.allocator.lock().unwrap().allocate();
+    }
+
+    /// Reserve a new entity that only becomes alive when it is activated.
+    pub fn reserve(&self) -> Entity {
+        return self.allocator.lock().unwrap().reserve();
+    }
+
+    /// Activate a reserved entity, making it alive.
+    pub fn activate(&self, entity: Entity) -> bool {
+        return self.allocator.lock().unwrap().activate(entity);
+    }
+
+    /// Free the slot of an entity, so it can be recycled.
+    pub fn free(&self, entity: Entity) -> bool {
+        return self.allocator.lock().unwrap().free(entity);
+    }
+
+    /// Returns if an entity is alive.
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        return self.allocator.lock().unwrap().is_alive(entity);
+    }
+
+    /// Returns the number of entities alive or reserved.
+    pub fn len(&self) -> usize {
+        return self.allocator.lock().unwrap().len();
+    }
+
+    /// Returns if there is no entity alive or reserved.
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Returns if two handles share the same allocator.
+    pub fn is_shared_with(&self, other: &Entities) -> bool {
+        return Arc::ptr_eq(&self.allocator, &other.allocator);
     }
 }
@@ -0,0 +1,119 @@
+use std::{any::TypeId, marker::PhantomData};
+use super::{world::World, archetype::Archetype, component::{Component, ComponentTicks}};
+
+/// Trait implemented by the types that can filter the entities of a typed query.
+///
+/// It's implemented for `With<T>`, `Without<T>`, `Or<(...)>`, `Added<T>`, `Changed<T>` and tuples of them.
+pub trait QueryFilter {
+    /// Returns if an archetype can have entities that match the filter.
+    fn matches_archetype(archetype: &Archetype) -> bool;
+
+    /// Returns if the entity stored on a specific row of a matching archetype passes the filter.
+    fn matches(world: &World, archetype: &Archetype, row: usize) -> bool;
+}
+
+/// Filter that matches entities that have the component.
+pub struct With<T: Component>(PhantomData<T>);
+
+/// Filter that matches entities that doesn't have the component.
+pub struct Without<T: Component>(PhantomData<T>);
+
+/// Filter that matches entities that pass at least one of the filters inside of the tuple.
+pub struct Or<T>(PhantomData<T>);
+
+/// Filter that matches entities whose component was added since the last run of the system that queries.
+///
+/// Outside of the systems, it matches the components added since the last call of World::increment_change_tick.
+pub struct Added<T: Component>(PhantomData<T>);
+
+/// Filter that matches entities whose component was changed since the last run of the system that queries.
+///
+/// Outside of the systems, it matches the components changed since the last call of World::increment_change_tick.
+///
+/// A component is changed when it's added or mutably dereferenced.
+pub struct Changed<T: Component>(PhantomData<T>);
+
+impl QueryFilter for () {
+    fn matches_archetype(_archetype: &Archetype) -> bool {
+        return true;
+    }
+
+    fn matches(_world: &World, _archetype: &Archetype, _row: usize) -> bool {
+        return true;
+    }
+}
+
+impl<T: Component + 'static> QueryFilter for With<T> {
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        return archetype.has_component(&TypeId::of::<T>());
+    }
+
+    fn matches(_world: &World, _archetype: &Archetype, _row: usize) -> bool {
+        return true;
+    }
+}
+
+impl<T: Component + 'static> QueryFilter for Without<T> {
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        return !archetype.has_component(&TypeId::of::<T>());
+    }
+
+    fn matches(_world: &World, _archetype: &Archetype, _row: usize) -> bool {
+        return true;
+    }
+}
+
+impl<T: Component + 'static> QueryFilter for Added<T> {
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        return archetype.has_component(&TypeId::of::<T>());
+    }
+
+    fn matches(world: &World, archetype: &Archetype, row: usize) -> bool {
+        return archetype.get_component_ticks(&TypeId::of::<T>(), row)
+            .is_some_and(|ticks: &ComponentTicks| ticks.is_added(world.get_last_run_tick(), world.change_tick));
+    }
+}
+
+impl<T: Component + 'static> QueryFilter for Changed<T> {
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        return archetype.has_component(&TypeId::of::<T>());
+    }
+
+    fn matches(world: &World, archetype: &Archetype, row: usize) -> bool {
+        return archetype.get_component_ticks(&TypeId::of::<T>(), row)
+            .is_some_and(|ticks: &ComponentTicks| ticks.is_changed(world.get_last_run_tick(), world.change_tick));
+    }
+}
+
+macro_rules! impl_query_filter_for_tuple {
+    ($($filter:ident),+) => {
+        impl<$($filter: QueryFilter),+> QueryFilter for ($($filter,)+) {
+            fn matches_archetype(archetype: &Archetype) -> bool {
+                return $($filter::matches_archetype(archetype))&&+;
+            }
+
+            fn matches(world: &World, archetype: &Archetype, row: usize) -> bool {
+                return $($filter::matches(world, archetype, row))&&+;
+            }
+        }
+
+        impl<$($filter: QueryFilter),+> QueryFilter for Or<($($filter,)+)> {
+            fn matches_archetype(archetype: &Archetype) -> bool {
+                return $($filter::matches_archetype(archetype))||+;
+            }
+
+            fn matches(world: &World, archetype: &Archetype, row: usize) -> bool {
+                return $(($filter::matches_archetype(archetype) && $filter::matches(world, archetype, row)))||+;
+            }
+        }
+    };
+}
+
+impl_query_filter_for_tuple!(A);
+impl_query_filter_for_tuple!(A, B);
+impl_query_filter_for_tuple!(A, B, C);
+impl_query_filter_for_tuple!(A, B, C, D);
+impl_query_filter_for_tuple!(A, B, C, D, E);
+impl_query_filter_for_tuple!(A, B, C, D, E, F);
+impl_query_filter_for_tuple!(A, B, C, D, E, F, G);
+impl_query_filter_for_tuple!(A, B, C, D, E, F, G, H);
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use super::{world::World, command::Commands, entity::Entity};
+
+/// Function called on a moment of the lifecycle of a component.
+///
+/// It receives the world, a command buffer flushed right after the current ones, and the entity that holds the component.
+pub type ComponentHook = Arc<dyn Fn(&mut World, &mut Commands, Entity) + Send + Sync>;
+
+/// Enumerator that store the moments of the lifecycle of a component in which hooks run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookKind {
+    /// The component was added to an entity, by spawning, inserting or replacing it.
+    Add,
+    /// The component is about to be removed from an entity, by removing or replacing it.
+    Remove,
+    /// The entity that holds the component is about to be despawned.
+    Despawn
+}
+
+/// Struct to represent the hooks registered for a component type.
+///
+/// Every on_add call is matched by an on_remove or on_despawn call, so resources tied to the component never leak.
+#[derive(Clone, Default)]
+pub struct ComponentHooks {
+    pub on_add: Vec<ComponentHook>,
+    pub on_remove: Vec<ComponentHook>,
+    pub on_despawn: Vec<ComponentHook>
+}
+
+impl ComponentHooks {
+    /// Create a new component hooks struct without any hook.
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Register a hook that runs after the component is added to an entity, or after it is replaced.
+    pub fn on_add(&mut self, hook: impl Fn(&mut World, &mut Commands, Entity) + Send + Sync + 'static) -> &mut Self {
+        self.on_add.push(Arc::new(hook));
+        return self;
+    }
+
+    /// Register a hook that runs before the component is removed from an entity, or before it is replaced.
+    pub fn on_remove(&mut self, hook: impl Fn(&mut World, &mut Commands, Entity) + Send + Sync + 'static) -> &mut Self {
+        self.on_remove.push(Arc::new(hook));
+        return self;
+    }
+
+    /// Register a hook that runs before the entity that holds the component is despawned.
+    pub fn on_despawn(&mut self, hook: impl Fn(&mut World, &mut Commands, Entity) + Send + Sync + 'static) -> &mut Self {
+        self.on_despawn.push(Arc::new(hook));
+        return self;
+    }
+
+    /// Returns the hooks registered for a specific moment.
+    pub fn get(&self, kind: HookKind) -> &[ComponentHook] {
+        return match kind {
+            HookKind::Add => &self.on_add,
+            HookKind::Remove => &self.on_remove,
+            HookKind::Despawn => &self.on_despawn
+        };
+    }
+}
@@ -4,17 +4,35 @@ pub mod world;
 /// The Archetypes that acts as clusters of data in our World.
 pub mod archetype;
 
+/// The Columns that store the components of an Archetype contiguously.
+pub mod column;
+
 /// Commands that affect the World.
 pub mod command;
 
+/// The Component lifecycle hooks that run when components are added, removed or despawned.
+pub mod hook;
+
 /// The Component and its features on the ECS architecture.
 pub mod component;
 
+/// The Bundle that groups components spawned together on the ECS architecture.
+pub mod bundle;
+
 /// The Resource and its features on the ECS architecture.
 pub mod resource;
 
+/// The Reflection of components and resources fields by their names on the ECS architecture.
+pub mod reflect;
+
+/// The Type registry that maps reflected type names to their constructors on the ECS architecture.
+pub mod registry;
+
 /// The Entity and its features on the ECS architecture.
 pub mod entity;
 
 /// The Query and its features on the ECS architecture.
 pub mod query;
+
+/// The Query filters and its features on the ECS architecture.
+pub mod filter;
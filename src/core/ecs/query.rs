@@ -1,6 +1,12 @@
-use std::any::TypeId;
+use std::{any::TypeId, marker::PhantomData};
 use atomic_refcell::AtomicRefMut;
-use super::{world::World, archetype::Archetype, component::Component, entity::Entity};
+use super::{
+    world::World,
+    archetype::Archetype,
+    filter::QueryFilter,
+    component::{Component, ComponentRef, ComponentRefMut},
+    entity::Entity
+};
 
 /// Struct to represent the querys made on the world.
 pub struct Query<'a> {
@@ -24,10 +30,10 @@ impl<'a> Query<'a> {
     }
 
     /// Returns entities by the exact components as mutables of a archetype.
-    pub fn entities_with_all_components_mut(&'a mut self) -> Option<Vec<(Entity, Vec<AtomicRefMut<'a, Box<dyn Component>>>)>> {
+    pub fn entities_with_all_components_mut(&'a mut self) -> Option<Vec<(Entity, Vec<AtomicRefMut<'a, dyn Component>>)>> {
         let archetype_unique_key: u64 = self.world.get_archetype_unique_key(&mut self.parameters);
         let archetype: &Archetype = self.world.archetypes.get(&archetype_unique_key)?;
-        let mut results: Vec<(Entity, Vec<AtomicRefMut<'_, Box<dyn Component>>>)> = Vec::new();
+        let mut results: Vec<(Entity, Vec<AtomicRefMut<'_, dyn Component>>)> = Vec::new();
 
         for entity in &archetype.entities {
             if let Some(components) = self.world.get_entity_components_mut(entity) {
@@ -42,7 +48,7 @@ impl<'a> Query<'a> {
         let mut results: Vec<Entity> = Vec::new();
 
         for (_, archetype) in &self.world.archetypes {
-            if self.parameters.iter().all(|param| archetype.components.contains_key(param)) {
+            if self.parameters.iter().all(|param| archetype.has_component(param)) {
                 for entity in &archetype.entities {
                     results.push(*entity);
                 }
@@ -56,7 +62,7 @@ impl<'a> Query<'a> {
         let mut results: Vec<Entity> = Vec::new();
 
         for (_, archetype) in &self.world.archetypes {
-            if self.parameters.iter().all(|param| !archetype.components.contains_key(param)) {
+            if self.parameters.iter().all(|param| !archetype.has_component(param)) {
                 for entity in &archetype.entities {
                     results.push(*entity);
                 }
@@ -66,10 +72,10 @@ impl<'a> Query<'a> {
     }
 
     /// Returns entities by the components as mutables of a archetype in a flexible way.
-    pub fn entities_with_components_mut_one(&'a mut self) -> Option<Vec<(Entity, Vec<AtomicRefMut<'a, Box<dyn Component>>>)>> {
+    pub fn entities_with_components_mut_one(&'a mut self) -> Option<Vec<(Entity, Vec<AtomicRefMut<'a, dyn Component>>)>> {
         for (_, archetype) in &self.world.archetypes {
-            if self.parameters.iter().all(|param| archetype.components.contains_key(param)) {
-                let mut results: Vec<(Entity, Vec<AtomicRefMut<'_, Box<dyn Component>>>)> = Vec::new();
+            if self.parameters.iter().all(|param| archetype.has_component(param)) {
+                let mut results: Vec<(Entity, Vec<AtomicRefMut<'_, dyn Component>>)> = Vec::new();
     
                 for entity in &archetype.entities {
                     if let Some(components) = self.world.get_entity_components_mut(entity) {
@@ -83,11 +89,11 @@ impl<'a> Query<'a> {
     }
 
     /// Returns all entites by components as mutables in a flexible way.
-    pub fn entities_with_components_mut_all(&'a mut self) -> Option<Vec<(Entity, Vec<AtomicRefMut<'a, Box<dyn Component>>>)>> {
-        let mut results: Vec<(Entity, Vec<AtomicRefMut<'_, Box<dyn Component>>>)> = Vec::new();
+    pub fn entities_with_components_mut_all(&'a mut self) -> Option<Vec<(Entity, Vec<AtomicRefMut<'a, dyn Component>>)>> {
+        let mut results: Vec<(Entity, Vec<AtomicRefMut<'_, dyn Component>>)> = Vec::new();
 
         for (_, archetype) in &self.world.archetypes {
-            if self.parameters.iter().all(|param| archetype.components.contains_key(param)) {
+            if self.parameters.iter().all(|param| archetype.has_component(param)) {
                 for entity in &archetype.entities {
                     if let Some(components) = self.world.get_entity_components_mut(entity) {
                         results.push((*entity, components));
@@ -98,3 +104,161 @@ impl<'a> Query<'a> {
         return Some(results);
     }
 }
+
+/// Trait implemented by the types that can be fetched by a typed query.
+///
+/// It's implemented for `&T`, `&mut T`, `Option<&T>`, `Option<&mut T>`, `Entity` and tuples of them.
+pub trait QueryParameter {
+    /// The item returned by the query for each entity.
+    type Item<'w>;
+
+    /// Push the components types that an archetype must have to be matched.
+    fn required_types(types: &mut Vec<TypeId>);
+
+    /// Returns the item of an entity stored on a specific row of an archetype.
+    ///
+    /// Returns None if any of the components is already borrowed in a conflicting way.
+    fn fetch<'w>(world: &'w World, archetype: &'w Archetype, entity: &Entity, row: usize) -> Option<Self::Item<'w>>;
+}
+
+impl<T: Component + 'static> QueryParameter for &T {
+    type Item<'w> = ComponentRef<'w, T>;
+
+    fn required_types(types: &mut Vec<TypeId>) {
+        types.push(TypeId::of::<T>());
+    }
+
+    fn fetch<'w>(world: &'w World, archetype: &'w Archetype, entity: &Entity, row: usize) -> Option<Self::Item<'w>> {
+        return world.borrow_component::<T>(archetype, entity, row);
+    }
+}
+
+impl<T: Component + 'static> QueryParameter for &mut T {
+    type Item<'w> = ComponentRefMut<'w, T>;
+
+    fn required_types(types: &mut Vec<TypeId>) {
+        types.push(TypeId::of::<T>());
+    }
+
+    fn fetch<'w>(world: &'w World, archetype: &'w Archetype, entity: &Entity, row: usize) -> Option<Self::Item<'w>> {
+        return world.borrow_component_mut::<T>(archetype, entity, row);
+    }
+}
+
+impl<T: Component + 'static> QueryParameter for Option<&T> {
+    type Item<'w> = Option<ComponentRef<'w, T>>;
+
+    fn required_types(_types: &mut Vec<TypeId>) {}
+
+    fn fetch<'w>(world: &'w World, archetype: &'w Archetype, entity: &Entity, row: usize) -> Option<Self::Item<'w>> {
+        if archetype.has_component(&TypeId::of::<T>()) {
+            return world.borrow_component::<T>(archetype, entity, row).map(Some);
+        }
+        return Some(None);
+    }
+}
+
+impl<T: Component + 'static> QueryParameter for Option<&mut T> {
+    type Item<'w> = Option<ComponentRefMut<'w, T>>;
+
+    fn required_types(_types: &mut Vec<TypeId>) {}
+
+    fn fetch<'w>(world: &'w World, archetype: &'w Archetype, entity: &Entity, row: usize) -> Option<Self::Item<'w>> {
+        if archetype.has_component(&TypeId::of::<T>()) {
+            return world.borrow_component_mut::<T>(archetype, entity, row).map(Some);
+        }
+        return Some(None);
+    }
+}
+
+impl QueryParameter for Entity {
+    type Item<'w> = Entity;
+
+    fn required_types(_types: &mut Vec<TypeId>) {}
+
+    fn fetch<'w>(_world: &'w World, _archetype: &'w Archetype, entity: &Entity, _row: usize) -> Option<Self::Item<'w>> {
+        return Some(*entity);
+    }
+}
+
+macro_rules! impl_query_parameter_for_tuple {
+    ($($parameter:ident),+) => {
+        impl<$($parameter: QueryParameter),+> QueryParameter for ($($parameter,)+) {
+            type Item<'w> = ($($parameter::Item<'w>,)+);
+
+            fn required_types(types: &mut Vec<TypeId>) {
+                $($parameter::required_types(types);)+
+            }
+
+            fn fetch<'w>(world: &'w World, archetype: &'w Archetype, entity: &Entity, row: usize) -> Option<Self::Item<'w>> {
+                return Some(($($parameter::fetch(world, archetype, entity, row)?,)+));
+            }
+        }
+    };
+}
+
+impl_query_parameter_for_tuple!(A);
+impl_query_parameter_for_tuple!(A, B);
+impl_query_parameter_for_tuple!(A, B, C);
+impl_query_parameter_for_tuple!(A, B, C, D);
+impl_query_parameter_for_tuple!(A, B, C, D, E);
+impl_query_parameter_for_tuple!(A, B, C, D, E, F);
+impl_query_parameter_for_tuple!(A, B, C, D, E, F, G);
+impl_query_parameter_for_tuple!(A, B, C, D, E, F, G, H);
+
+/// Struct to represent the iterator of a typed query made on the world.
+///
+/// Entities whose components are already borrowed in a conflicting way are skipped.
+pub struct QueryIter<'w, Q: QueryParameter, F: QueryFilter = ()> {
+    world: &'w World,
+    archetypes: Vec<&'w Archetype>,
+    archetype_index: usize,
+    row: usize,
+    phantom_data: PhantomData<(Q, F)>
+}
+
+impl<'w, Q: QueryParameter, F: QueryFilter> QueryIter<'w, Q, F> {
+    /// Create a new typed query iterator with the world as the parameter.
+    pub fn new(world: &'w World) -> Self {
+        let mut required_types: Vec<TypeId> = Vec::new();
+        Q::required_types(&mut required_types);
+
+        let archetypes: Vec<&'w Archetype> = world.archetypes.values()
+            .filter(|archetype| required_types.iter().all(|type_id| archetype.has_component(type_id)))
+            .filter(|archetype| F::matches_archetype(archetype))
+            .collect();
+
+        return Self {
+            world,
+            archetypes,
+            archetype_index: 0,
+            row: 0,
+            phantom_data: PhantomData
+        };
+    }
+}
+
+impl<'w, Q: QueryParameter, F: QueryFilter> Iterator for QueryIter<'w, Q, F> {
+    type Item = Q::Item<'w>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(archetype) = self.archetypes.get(self.archetype_index) {
+            if let Some(entity) = archetype.entities.get(self.row) {
+                let row: usize = self.row;
+                self.row += 1;
+
+                if !F::matches(self.world, archetype, row) {
+                    continue;
+                }
+
+                if let Some(item) = Q::fetch(self.world, archetype, entity, row) {
+                    return Some(item);
+                }
+            } else {
+                self.archetype_index += 1;
+                self.row = 0;
+            }
+        }
+        return None;
+    }
+}
@@ -0,0 +1,501 @@
+use std::{any::Any, time::Duration};
+use cgmath::Vector2;
+
+/// Enumerator to represent the value of a reflected object, detached from its type.
+///
+/// Structs are represented as maps of their fields, lists and tuples as lists and unit variants as strings.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReflectValue {
+    Unit,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    List(Vec<ReflectValue>),
+    Map(Vec<(String, ReflectValue)>)
+}
+
+impl ReflectValue {
+    /// Returns the value stored in a key of a map value.
+    pub fn get(&self, key: &str) -> Option<&ReflectValue> {
+        if let ReflectValue::Map(entries) = self {
+            return entries.iter().find(|(entry_key, _)| entry_key == key).map(|(_, value)| value);
+        }
+        return None;
+    }
+
+    /// Returns the value as a boolean, if it is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        if let ReflectValue::Bool(value) = self {
+            return Some(*value);
+        }
+        return None;
+    }
+
+    /// Returns the value as an integer, if it is one.
+    pub fn as_integer(&self) -> Option<i64> {
+        if let ReflectValue::Integer(value) = self {
+            return Some(*value);
+        }
+        return None;
+    }
+
+    /// Returns the value as a float, if it is a float or an integer.
+    pub fn as_float(&self) -> Option<f64> {
+        return match self {
+            ReflectValue::Float(value) => Some(*value),
+            ReflectValue::Integer(value) => Some(*value as f64),
+            _ => None
+        };
+    }
+
+    /// Returns the value as a string slice, if it is one.
+    pub fn as_str(&self) -> Option<&str> {
+        if let ReflectValue::String(value) = self {
+            return Some(value);
+        }
+        return None;
+    }
+
+    /// Returns the value as a list, if it is one.
+    pub fn as_list(&self) -> Option<&[ReflectValue]> {
+        if let ReflectValue::List(values) = self {
+            return Some(values);
+        }
+        return None;
+    }
+
+    /// Returns the value as a JSON value.
+    pub fn to_json(&self) -> serde_json::Value {
+        return match self {
+            ReflectValue::Unit => serde_json::Value::Null,
+            ReflectValue::Bool(value) => serde_json::Value::Bool(*value),
+            ReflectValue::Integer(value) => serde_json::Value::from(*value),
+            ReflectValue::Float(value) => serde_json::Value::from(*value),
+            ReflectValue::String(value) => serde_json::Value::String(value.clone()),
+            ReflectValue::List(values) => serde_json::Value::Array(values.iter().map(|value| value.to_json()).collect()),
+            ReflectValue::Map(entries) => serde_json::Value::Object(
+                entries.iter().map(|(key, value)| (key.clone(), value.to_json())).collect()
+            )
+        };
+    }
+
+    /// Create a new value from a JSON value.
+    ///
+    /// Numbers without a fractional part are read as integers, which are also accepted by floats.
+    pub fn from_json(json: &serde_json::Value) -> Self {
+        return match json {
+            serde_json::Value::Null => ReflectValue::Unit,
+            serde_json::Value::Bool(value) => ReflectValue::Bool(*value),
+            serde_json::Value::Number(number) => number.as_i64()
+                .map(ReflectValue::Integer)
+                .unwrap_or_else(|| ReflectValue::Float(number.as_f64().unwrap_or_default())),
+            serde_json::Value::String(value) => ReflectValue::String(value.clone()),
+            serde_json::Value::Array(values) => ReflectValue::List(values.iter().map(ReflectValue::from_json).collect()),
+            serde_json::Value::Object(entries) => ReflectValue::Map(
+                entries.iter().map(|(key, value)| (key.clone(), ReflectValue::from_json(value))).collect()
+            )
+        };
+    }
+
+    /// Returns the name of the kind of the value.
+    pub fn kind(&self) -> &'static str {
+        return match self {
+            ReflectValue::Unit => "unit",
+            ReflectValue::Bool(_) => "bool",
+            ReflectValue::Integer(_) => "integer",
+            ReflectValue::Float(_) => "float",
+            ReflectValue::String(_) => "string",
+            ReflectValue::List(_) => "list",
+            ReflectValue::Map(_) => "map"
+        };
+    }
+}
+
+/// Enumerator to represent the different errors that the reflection flow can throw.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReflectError {
+    FieldNotFound(String),
+    MissingField(String),
+    TypeMismatch { expected: &'static str, found: &'static str },
+    OutOfRange(String),
+    UnknownVariant(String),
+    UnknownType(String),
+    MissingComponent(String),
+    MissingResource(String),
+    AssetNotLoaded(String)
+}
+
+impl std::fmt::Display for ReflectError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReflectError::FieldNotFound(field) => write!(formatter, "Reflect Error: field '{}' not found.", field),
+            ReflectError::MissingField(field) => write!(formatter, "Reflect Error: field '{}' is missing from the value.", field),
+            ReflectError::TypeMismatch { expected, found } => write!(formatter, "Reflect Error: expected a {} value, found a {} value.", expected, found),
+            ReflectError::OutOfRange(type_name) => write!(formatter, "Reflect Error: value out of the range of '{}'.", type_name),
+            ReflectError::UnknownVariant(variant) => write!(formatter, "Reflect Error: unknown variant '{}'.", variant),
+            ReflectError::UnknownType(type_name) => write!(formatter, "Reflect Error: type '{}' is not registered.", type_name),
+            ReflectError::MissingComponent(type_name) => write!(formatter, "Reflect Error: entity doesn't have the component '{}'.", type_name),
+            ReflectError::MissingResource(type_name) => write!(formatter, "Reflect Error: world doesn't have the resource '{}'.", type_name),
+            ReflectError::AssetNotLoaded(path) => write!(formatter, "Reflect Error: asset '{}' could not be loaded.", path)
+        }
+    }
+}
+
+impl std::error::Error for ReflectError {}
+
+/// Struct to represent the name and the type name of a reflected field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldInfo {
+    pub name: String,
+    pub type_name: &'static str
+}
+
+impl FieldInfo {
+    /// Create a new field info struct.
+    pub fn new(name: impl Into<String>, type_name: &'static str) -> Self {
+        return Self {
+            name: name.into(),
+            type_name
+        };
+    }
+}
+
+/// The Reflect trait to expose the fields of an object by their names.
+///
+/// Use the derive macro *Reflect* to implement it, and register the component or resource on the world
+/// to access it by its type name.
+///
+/// Paths are field names separated by dots, like 'position.x' on a transform, or indexes on lists.
+pub trait Reflect: Any + Send + Sync {
+    /// Returns the name of the reflected type.
+    fn static_type_name() -> &'static str where Self: Sized;
+
+    /// Returns the name of the type of the object.
+    fn type_name(&self) -> &'static str;
+
+    /// Returns the names and type names of the reflected fields.
+    fn fields(&self) -> Vec<FieldInfo> {
+        return Vec::new();
+    }
+
+    /// Returns a reflected field by its name.
+    fn field(&self, _name: &str) -> Option<&dyn Reflect> {
+        return None;
+    }
+
+    /// Returns a reflected field as mutable by its name.
+    fn field_mut(&mut self, _name: &str) -> Option<&mut dyn Reflect> {
+        return None;
+    }
+
+    /// Returns the object as a reflected value.
+    ///
+    /// Values that can't be represented, like an integer beyond the range of an i64, are returned as an error.
+    fn to_value(&self) -> Result<ReflectValue, ReflectError>;
+
+    /// Apply a reflected value to the object.
+    ///
+    /// Maps may contain only some of the fields, the other ones are kept.
+    fn set_value(&mut self, value: &ReflectValue) -> Result<(), ReflectError>;
+
+    /// Create a new object from a reflected value.
+    fn from_value(value: &ReflectValue) -> Result<Self, ReflectError> where Self: Sized;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Returns a reflected field by its path.
+    fn path(&self, path: &str) -> Result<&dyn Reflect, ReflectError> {
+        let (name, rest) = path.split_once('.').unwrap_or((path, ""));
+        let field: &dyn Reflect = self.field(name).ok_or_else(|| ReflectError::FieldNotFound(name.to_string()))?;
+
+        if rest.is_empty() {
+            return Ok(field);
+        }
+        return field.path(rest);
+    }
+
+    /// Returns a reflected field as mutable by its path.
+    fn path_mut(&mut self, path: &str) -> Result<&mut dyn Reflect, ReflectError> {
+        let (name, rest) = path.split_once('.').unwrap_or((path, ""));
+        let field: &mut dyn Reflect = self.field_mut(name).ok_or_else(|| ReflectError::FieldNotFound(name.to_string()))?;
+
+        if rest.is_empty() {
+            return Ok(field);
+        }
+        return field.path_mut(rest);
+    }
+
+    /// Apply a reflected value to a field by its path.
+    ///
+    /// An empty path applies the value to the whole object.
+    /// Every field along the path applies the rest of it, so an object can react to the change of its own fields.
+    fn set_path(&mut self, path: &str, value: &ReflectValue) -> Result<(), ReflectError> {
+        if path.is_empty() {
+            return self.set_value(value);
+        }
+        let (name, rest) = path.split_once('.').unwrap_or((path, ""));
+        let field: &mut dyn Reflect = self.field_mut(name).ok_or_else(|| ReflectError::FieldNotFound(name.to_string()))?;
+        return field.set_path(rest, value);
+    }
+}
+
+impl dyn Reflect {
+    /// Returns the reflected value of a field by its path.
+    ///
+    /// An empty path returns the value of the whole object.
+    pub fn path_value(&self, path: &str) -> Result<ReflectValue, ReflectError> {
+        if path.is_empty() {
+            return self.to_value();
+        }
+        return self.path(path)?.to_value();
+    }
+
+    /// Returns if the reflected object is of a specific type.
+    pub fn is<T: Reflect>(&self) -> bool {
+        return self.as_any().is::<T>();
+    }
+
+    /// Returns the reflected object as a specific type.
+    pub fn downcast_ref<T: Reflect>(&self) -> Option<&T> {
+        return self.as_any().downcast_ref::<T>();
+    }
+
+    /// Returns the reflected object as a specific mutable type.
+    pub fn downcast_mut<T: Reflect>(&mut self) -> Option<&mut T> {
+        return self.as_any_mut().downcast_mut::<T>();
+    }
+}
+
+macro_rules! impl_reflect_for_value {
+    ($type:ty, $name:expr, |$self:ident| $to_value:expr, |$value:ident| $from_value:expr) => {
+        impl Reflect for $type {
+            fn static_type_name() -> &'static str {
+                return $name;
+            }
+
+            fn type_name(&self) -> &'static str {
+                return $name;
+            }
+
+            fn to_value(&self) -> Result<ReflectValue, ReflectError> {
+                let $self: &Self = self;
+                return $to_value;
+            }
+
+            fn set_value(&mut self, value: &ReflectValue) -> Result<(), ReflectError> {
+                *self = Self::from_value(value)?;
+                return Ok(());
+            }
+
+            fn from_value($value: &ReflectValue) -> Result<Self, ReflectError> {
+                return $from_value;
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                return self;
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                return self;
+            }
+        }
+    };
+}
+
+macro_rules! impl_reflect_for_integer {
+    ($($integer:ty),+) => {
+        $(
+            impl_reflect_for_value!($integer, stringify!($integer), |this| {
+                i64::try_from(*this).map(ReflectValue::Integer).map_err(|_| ReflectError::OutOfRange(stringify!($integer).to_string()))
+            }, |value| {
+                let integer: i64 = value.as_integer().ok_or(ReflectError::TypeMismatch { expected: "integer", found: value.kind() })?;
+                <$integer>::try_from(integer).map_err(|_| ReflectError::OutOfRange(stringify!($integer).to_string()))
+            });
+        )+
+    };
+}
+
+impl_reflect_for_integer!(i8, i16, i32, u8, u16, u32, u64, usize, isize);
+
+impl_reflect_for_value!(i64, "i64", |this| Ok(ReflectValue::Integer(*this)), |value| {
+    value.as_integer().ok_or(ReflectError::TypeMismatch { expected: "integer", found: value.kind() })
+});
+
+// The shortest representation of the f32 is kept, so 0.1 isn't stored as 0.10000000149011612.
+impl_reflect_for_value!(f32, "f32", |this| Ok(ReflectValue::Float(this.to_string().parse().unwrap_or(*this as f64))), |value| {
+    value.as_float().map(|float| float as f32).ok_or(ReflectError::TypeMismatch { expected: "float", found: value.kind() })
+});
+
+impl_reflect_for_value!(f64, "f64", |this| Ok(ReflectValue::Float(*this)), |value| {
+    value.as_float().ok_or(ReflectError::TypeMismatch { expected: "float", found: value.kind() })
+});
+
+impl_reflect_for_value!(bool, "bool", |this| Ok(ReflectValue::Bool(*this)), |value| {
+    value.as_bool().ok_or(ReflectError::TypeMismatch { expected: "bool", found: value.kind() })
+});
+
+impl_reflect_for_value!(String, "String", |this| Ok(ReflectValue::String(this.clone())), |value| {
+    value.as_str().map(|string| string.to_string()).ok_or(ReflectError::TypeMismatch { expected: "string", found: value.kind() })
+});
+
+// Durations are reflected as their seconds.
+impl_reflect_for_value!(Duration, "Duration", |this| Ok(ReflectValue::Float(this.as_secs_f64())), |value| {
+    value.as_float()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or(ReflectError::TypeMismatch { expected: "float", found: value.kind() })
+});
+
+impl<T: Reflect> Reflect for Vector2<T> {
+    fn static_type_name() -> &'static str {
+        return "Vector2";
+    }
+
+    fn type_name(&self) -> &'static str {
+        return "Vector2";
+    }
+
+    fn fields(&self) -> Vec<FieldInfo> {
+        return vec![FieldInfo::new("x", self.x.type_name()), FieldInfo::new("y", self.y.type_name())];
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        return match name {
+            "x" => Some(&self.x),
+            "y" => Some(&self.y),
+            _ => None
+        };
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        return match name {
+            "x" => Some(&mut self.x),
+            "y" => Some(&mut self.y),
+            _ => None
+        };
+    }
+
+    fn to_value(&self) -> Result<ReflectValue, ReflectError> {
+        return Ok(ReflectValue::Map(vec![("x".to_string(), self.x.to_value()?), ("y".to_string(), self.y.to_value()?)]));
+    }
+
+    fn set_value(&mut self, value: &ReflectValue) -> Result<(), ReflectError> {
+        let ReflectValue::Map(entries) = value else {
+            return Err(ReflectError::TypeMismatch { expected: "map", found: value.kind() });
+        };
+
+        for (key, entry) in entries {
+            self.field_mut(key).ok_or_else(|| ReflectError::FieldNotFound(key.clone()))?.set_value(entry)?;
+        }
+        return Ok(());
+    }
+
+    fn from_value(value: &ReflectValue) -> Result<Self, ReflectError> {
+        let x: &ReflectValue = value.get("x").ok_or_else(|| ReflectError::MissingField("x".to_string()))?;
+        let y: &ReflectValue = value.get("y").ok_or_else(|| ReflectError::MissingField("y".to_string()))?;
+        return Ok(Vector2::new(T::from_value(x)?, T::from_value(y)?));
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
+}
+
+impl<T: Reflect> Reflect for Vec<T> {
+    fn static_type_name() -> &'static str {
+        return "Vec";
+    }
+
+    fn type_name(&self) -> &'static str {
+        return "Vec";
+    }
+
+    fn fields(&self) -> Vec<FieldInfo> {
+        return self.iter().enumerate().map(|(index, item)| FieldInfo::new(index.to_string(), item.type_name())).collect();
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        return name.parse::<usize>().ok().and_then(|index| self.get(index)).map(|item| item as &dyn Reflect);
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        return name.parse::<usize>().ok().and_then(|index| self.get_mut(index)).map(|item| item as &mut dyn Reflect);
+    }
+
+    fn to_value(&self) -> Result<ReflectValue, ReflectError> {
+        return self.iter().map(|item| item.to_value()).collect::<Result<Vec<ReflectValue>, ReflectError>>().map(ReflectValue::List);
+    }
+
+    fn set_value(&mut self, value: &ReflectValue) -> Result<(), ReflectError> {
+        *self = Self::from_value(value)?;
+        return Ok(());
+    }
+
+    fn from_value(value: &ReflectValue) -> Result<Self, ReflectError> {
+        let items: &[ReflectValue] = value.as_list().ok_or(ReflectError::TypeMismatch { expected: "list", found: value.kind() })?;
+        return items.iter().map(T::from_value).collect();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
+}
+
+/// Options are reflected as unit when empty, and as their inner value otherwise.
+///
+/// The fields of an option are the fields of its inner value.
+impl<T: Reflect> Reflect for Option<T> {
+    fn static_type_name() -> &'static str {
+        return "Option";
+    }
+
+    fn type_name(&self) -> &'static str {
+        return "Option";
+    }
+
+    fn fields(&self) -> Vec<FieldInfo> {
+        return self.as_ref().map(|inner| inner.fields()).unwrap_or_default();
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        return self.as_ref().and_then(|inner| inner.field(name));
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        return self.as_mut().and_then(|inner| inner.field_mut(name));
+    }
+
+    fn to_value(&self) -> Result<ReflectValue, ReflectError> {
+        return self.as_ref().map(|inner| inner.to_value()).unwrap_or(Ok(ReflectValue::Unit));
+    }
+
+    fn set_value(&mut self, value: &ReflectValue) -> Result<(), ReflectError> {
+        *self = Self::from_value(value)?;
+        return Ok(());
+    }
+
+    fn from_value(value: &ReflectValue) -> Result<Self, ReflectError> {
+        if *value == ReflectValue::Unit {
+            return Ok(None);
+        }
+        return T::from_value(value).map(Some);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
+}
@@ -0,0 +1,230 @@
+use std::{any::TypeId, collections::HashMap};
+use super::{
+    super::{
+        draw_order::DrawOrder,
+        visibility::Visibility,
+        shape::shape::Shape,
+        texture::sprite::Sprite,
+        material::material::Material,
+        text::text::Text,
+        time::timer::Timer,
+        save::Persistent,
+        physics::{
+            transform::Transform,
+            velocity::Velocity,
+            acceleration::Acceleration,
+            gravity::Gravity,
+            collision::Collision,
+            rigid_body::RigidBody
+        }
+    },
+    component::Component,
+    entity::Entity,
+    reflect::{Reflect, ReflectError, ReflectValue},
+    resource::Resource,
+    world::World
+};
+
+/// Function that visits a reflected component or resource.
+pub type ReflectVisitor<'a> = dyn FnMut(&dyn Reflect) + 'a;
+
+/// Function that visits a reflected component or resource as mutable.
+pub type ReflectVisitorMut<'a> = dyn FnMut(&mut dyn Reflect) + 'a;
+
+/// Struct to represent a component type registered for reflection.
+#[derive(Clone, Copy)]
+pub struct ComponentRegistration {
+    pub type_name: &'static str,
+    pub type_id: TypeId,
+    from_value: fn(&ReflectValue) -> Result<Box<dyn Component>, ReflectError>,
+    reflect: fn(&World, &Entity, &mut ReflectVisitor) -> bool,
+    reflect_mut: fn(&World, &Entity, &mut ReflectVisitorMut) -> bool
+}
+
+impl ComponentRegistration {
+    /// Create a new component registration by its type.
+    pub fn new<T: Component + Reflect>() -> Self {
+        return Self {
+            type_name: T::static_type_name(),
+            type_id: TypeId::of::<T>(),
+            from_value: |value| T::from_value(value).map(|component| Box::new(component) as Box<dyn Component>),
+            reflect: |world, entity, visitor| {
+                return world.get_entity_component::<T>(entity).map(|component| visitor(&*component)).is_some();
+            },
+            reflect_mut: |world, entity, visitor| {
+                return world.get_entity_component_mut::<T>(entity).map(|mut component| visitor(&mut *component)).is_some();
+            }
+        };
+    }
+
+    /// Create a new component from a reflected value.
+    pub fn from_value(&self, value: &ReflectValue) -> Result<Box<dyn Component>, ReflectError> {
+        return (self.from_value)(value);
+    }
+
+    /// Visit the component of an entity as reflected.
+    ///
+    /// Returns false if the entity doesn't have the component.
+    pub fn reflect(&self, world: &World, entity: &Entity, visitor: &mut ReflectVisitor) -> bool {
+        return (self.reflect)(world, entity, visitor);
+    }
+
+    /// Visit the component of an entity as reflected and mutable, marking it as changed.
+    ///
+    /// Returns false if the entity doesn't have the component.
+    pub fn reflect_mut(&self, world: &World, entity: &Entity, visitor: &mut ReflectVisitorMut) -> bool {
+        return (self.reflect_mut)(world, entity, visitor);
+    }
+}
+
+/// Struct to represent a resource type registered for reflection.
+#[derive(Clone, Copy)]
+pub struct ResourceRegistration {
+    pub type_name: &'static str,
+    pub type_id: TypeId,
+    from_value: fn(&ReflectValue) -> Result<Box<dyn Resource>, ReflectError>,
+    reflect: fn(&World, &mut ReflectVisitor) -> bool,
+    reflect_mut: fn(&World, &mut ReflectVisitorMut) -> bool
+}
+
+impl ResourceRegistration {
+    /// Create a new resource registration by its type.
+    pub fn new<T: Resource + Reflect>() -> Self {
+        return Self {
+            type_name: T::static_type_name(),
+            type_id: TypeId::of::<T>(),
+            from_value: |value| T::from_value(value).map(|resource| Box::new(resource) as Box<dyn Resource>),
+            reflect: |world, visitor| {
+                return world.get_resource::<T>().map(|resource| visitor(&*resource)).is_some();
+            },
+            reflect_mut: |world, visitor| {
+                return world.get_resource_mut::<T>().map(|mut resource| visitor(&mut *resource)).is_some();
+            }
+        };
+    }
+
+    /// Create a new resource from a reflected value.
+    pub fn from_value(&self, value: &ReflectValue) -> Result<Box<dyn Resource>, ReflectError> {
+        return (self.from_value)(value);
+    }
+
+    /// Visit the resource of the world as reflected.
+    ///
+    /// Returns false if the world doesn't have the resource.
+    pub fn reflect(&self, world: &World, visitor: &mut ReflectVisitor) -> bool {
+        return (self.reflect)(world, visitor);
+    }
+
+    /// Visit the resource of the world as reflected and mutable.
+    ///
+    /// Returns false if the world doesn't have the resource.
+    pub fn reflect_mut(&self, world: &World, visitor: &mut ReflectVisitorMut) -> bool {
+        return (self.reflect_mut)(world, visitor);
+    }
+}
+
+/// Struct to represent the registry that maps the type names of reflected components and resources to their constructors.
+///
+/// The engine components and resources that can be reflected are registered by default.
+#[derive(Clone)]
+pub struct TypeRegistry {
+    components: HashMap<&'static str, ComponentRegistration>,
+    components_names: HashMap<TypeId, &'static str>,
+    resources: HashMap<&'static str, ResourceRegistration>
+}
+
+impl Default for TypeRegistry {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl TypeRegistry {
+    /// Create a new type registry with the engine types registered.
+    pub fn new() -> Self {
+        let mut type_registry: TypeRegistry = Self::empty();
+        type_registry
+            .register_component::<Transform>()
+            .register_component::<Velocity>()
+            .register_component::<Acceleration>()
+            .register_component::<Gravity>()
+            .register_component::<Collision>()
+            .register_component::<RigidBody>()
+            .register_component::<Shape>()
+            .register_component::<Sprite>()
+            .register_component::<DrawOrder>()
+            .register_component::<Visibility>()
+            .register_component::<Text>()
+            .register_component::<Persistent>()
+            .register_component::<Material>()
+            .register_resource::<Timer>();
+        return type_registry;
+    }
+
+    /// Create a new type registry without any type registered.
+    pub fn empty() -> Self {
+        return Self {
+            components: HashMap::new(),
+            components_names: HashMap::new(),
+            resources: HashMap::new()
+        };
+    }
+
+    /// Register a component type by its type name, replacing any type registered with the same name.
+    pub fn register_component<T: Component + Reflect>(&mut self) -> &mut Self {
+        let registration: ComponentRegistration = ComponentRegistration::new::<T>();
+        self.components_names.insert(registration.type_id, registration.type_name);
+        self.components.insert(registration.type_name, registration);
+        return self;
+    }
+
+    /// Register a resource type by its type name, replacing any type registered with the same name.
+    pub fn register_resource<T: Resource + Reflect>(&mut self) -> &mut Self {
+        let registration: ResourceRegistration = ResourceRegistration::new::<T>();
+        self.resources.insert(registration.type_name, registration);
+        return self;
+    }
+
+    /// Returns the registration of a component type by its type name.
+    pub fn get_component(&self, type_name: &str) -> Option<&ComponentRegistration> {
+        return self.components.get(type_name);
+    }
+
+    /// Returns the registration of a component type by its type identifier.
+    pub fn get_component_by_type_id(&self, type_id: &TypeId) -> Option<&ComponentRegistration> {
+        return self.components_names.get(type_id).and_then(|type_name| self.components.get(type_name));
+    }
+
+    /// Returns the registration of a resource type by its type name.
+    pub fn get_resource(&self, type_name: &str) -> Option<&ResourceRegistration> {
+        return self.resources.get(type_name);
+    }
+
+    /// Returns the type names of the registered components, sorted.
+    pub fn components_type_names(&self) -> Vec<&'static str> {
+        let mut type_names: Vec<&'static str> = self.components.keys().copied().collect();
+        type_names.sort();
+        return type_names;
+    }
+
+    /// Returns the type names of the registered resources, sorted.
+    pub fn resources_type_names(&self) -> Vec<&'static str> {
+        let mut type_names: Vec<&'static str> = self.resources.keys().copied().collect();
+        type_names.sort();
+        return type_names;
+    }
+
+    /// Create a new component by its type name and a reflected value.
+    pub fn construct_component(&self, type_name: &str, value: &ReflectValue) -> Result<Box<dyn Component>, ReflectError> {
+        return self.get_component(type_name)
+            .ok_or_else(|| ReflectError::UnknownType(type_name.to_string()))?
+            .from_value(value);
+    }
+
+    /// Create a new resource by its type name and a reflected value.
+    pub fn construct_resource(&self, type_name: &str, value: &ReflectValue) -> Result<Box<dyn Resource>, ReflectError> {
+        return self.get_resource(type_name)
+            .ok_or_else(|| ReflectError::UnknownType(type_name.to_string()))?
+            .from_value(value);
+    }
+}
@@ -1,5 +1,5 @@
-use std::{any::{Any, TypeId}, collections::HashSet, ops::{Deref, DerefMut}};
-use atomic_refcell::{AtomicRef, AtomicRefCell, AtomicRefMut};
+use std::{any::{Any, TypeId}, collections::{HashMap, HashSet}, ops::{Deref, DerefMut}, sync::Mutex};
+use atomic_refcell::{AtomicRef, AtomicRefMut};
 
 /// The Resource trait to annotate which structs are.
 pub trait Resource: Any + Send + Sync {
@@ -10,14 +10,14 @@ pub trait Resource: Any + Send + Sync {
 
 /// Struct to represent the actual borrowing state of the world resources.
 pub struct ResourceBorrowState {
-   pub immutable_borrows: HashSet<TypeId>,
+   pub immutable_borrows: HashMap<TypeId, usize>,
    pub mutable_borrows: HashSet<TypeId>
 }
 
 impl ResourceBorrowState {
    pub fn new() -> Self {
       return Self {
-         immutable_borrows: HashSet::new(),
+         immutable_borrows: HashMap::new(),
          mutable_borrows: HashSet::new()
       };
    }
@@ -26,13 +26,13 @@ impl ResourceBorrowState {
       if self.mutable_borrows.contains(&type_id) {
          return false;
       } else {
-         self.immutable_borrows.insert(type_id);
+         *self.immutable_borrows.entry(type_id).or_insert(0) += 1;
          return true;
       }
    }
 
    pub fn try_borrow_mutable(&mut self, type_id: TypeId) -> bool {
-      if self.immutable_borrows.contains(&type_id) || self.mutable_borrows.contains(&type_id) {
+      if self.immutable_borrows.contains_key(&type_id) || self.mutable_borrows.contains(&type_id) {
          return false;
       } else {
          self.mutable_borrows.insert(type_id);
@@ -41,7 +41,13 @@ impl ResourceBorrowState {
    }
 
    pub fn release_immutable(&mut self, type_id: TypeId) {
-      self.immutable_borrows.remove(&type_id);
+      if let Some(count) = self.immutable_borrows.get_mut(&type_id) {
+         *count -= 1;
+
+         if *count == 0 {
+            self.immutable_borrows.remove(&type_id);
+         }
+      }
    }
 
    pub fn release_mutable(&mut self, type_id: TypeId) {
@@ -53,7 +59,7 @@ impl ResourceBorrowState {
 pub struct ResourceRef<'a, T: Resource + 'static> {
    pub(crate) inner: AtomicRef<'a, Box<dyn Resource>>,
    pub(crate) type_id: TypeId,
-   pub(crate) resource_borrow_state: &'a AtomicRefCell<ResourceBorrowState>,
+   pub(crate) resource_borrow_state: &'a Mutex<ResourceBorrowState>,
    pub(crate) phantom_data: std::marker::PhantomData<T>
 }
 
@@ -67,7 +73,7 @@ impl<'a, T: Resource + 'static> Deref for ResourceRef<'a, T> {
 
 impl<'a, T: Resource + 'static> Drop for ResourceRef<'a, T> {
    fn drop(&mut self) {
-      self.resource_borrow_state.borrow_mut().release_immutable(self.type_id);
+      self.resource_borrow_state.lock().unwrap().release_immutable(self.type_id);
    }
 }
 
@@ -75,7 +81,7 @@ impl<'a, T: Resource + 'static> Drop for ResourceRef<'a, T> {
 pub struct ResourceRefMut<'a, T: Resource + 'static> {
    pub(crate) inner: AtomicRefMut<'a, Box<dyn Resource>>,
    pub(crate) type_id: TypeId,
-   pub(crate) resource_borrow_state: &'a AtomicRefCell<ResourceBorrowState>,
+   pub(crate) resource_borrow_state: &'a Mutex<ResourceBorrowState>,
    pub(crate) phantom_data: std::marker::PhantomData<T>
 }
 
@@ -95,6 +101,6 @@ impl<'a, T: Resource + 'static> DerefMut for ResourceRefMut<'a, T> {
 
 impl<'a, T: Resource + 'static> Drop for ResourceRefMut<'a, T> {
    fn drop(&mut self) {
-      self.resource_borrow_state.borrow_mut().release_mutable(self.type_id);
+      self.resource_borrow_state.lock().unwrap().release_mutable(self.type_id);
    }
 }
@@ -1,16 +1,13 @@
 use std::{
     any::TypeId,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     hash::{DefaultHasher, Hash, Hasher},
     marker::PhantomData,
-    mem::take,
-    sync::Arc
+    sync::{Arc, Mutex, MutexGuard}
 };
 use atomic_refcell::{AtomicRef, AtomicRefCell, AtomicRefMut};
 use cgmath::Vector2;
 use lotus_proc_macros::Component;
-use uuid::Uuid;
-use crate::core::event::synchronizer;
 
 use super::{
     super::{
@@ -23,15 +20,28 @@ use super::{
         bindings::mouse::mouse_input::MouseInput,
         bindings::gamepad::gamepad_input::GamepadInput,
         draw_order::DrawOrder,
+        texture::sprite::Sprite,
+        animation::animation::Animation,
+        material::material::Material,
+        post_process::post_process::PostProcess,
         visibility::Visibility,
-        text::{text::{Text, TextHolder, TextRenderer}, font::{Font, Fonts}},
+        text::{text::{Text, TextHolder}, font::{Font, Fonts}},
         managers::render::manager::RenderState,
-        physics::transform::{Transform, Position, Strategy}
+        hierarchy::{Parent, Children},
+        scene::{Scene, SceneError},
+        save::{SaveGame, SaveError, Persistent},
+        physics::transform::{Transform, GlobalTransform, Position, Strategy}
     },
-    archetype::Archetype,
-    query::Query,
-    entity::Entity,
-    component::{Component, ComponentRefMut, ComponentRef, ComponentBorrowState},
+    archetype::{Archetype, EntityLocation},
+    column::Column,
+    query::{Query, QueryParameter, QueryIter},
+    filter::{QueryFilter, With},
+    entity::{Entity, Entities},
+    command::Commands,
+    hook::{ComponentHook, ComponentHooks, HookKind},
+    reflect::{Reflect, ReflectValue, ReflectError},
+    registry::{TypeRegistry, ComponentRegistration, ResourceRegistration},
+    component::{Component, ComponentRefMut, ComponentRef, ComponentBorrowState, ComponentTicks},
     resource::{Resource, ResourceRefMut, ResourceRef, ResourceBorrowState}
 };
 
@@ -39,14 +49,29 @@ use super::{
 #[derive(Clone, Component)]
 pub struct Fps();
 
+/// Removes the text renderer of an entity when its text is removed or the entity is despawned.
+fn remove_text_renderer(world: &mut World, _commands: &mut Commands, entity: Entity) {
+    if let Some(mut text_holder) = world.get_resource_mut::<TextHolder>() {
+        text_holder.remove(&entity);
+    }
+}
+
 /// Struct to represent the World of the Entity-Component-System architecture.
 ///
 /// The World uses normalized coordinates at its core.
 pub struct World {
     pub archetypes: HashMap<u64, Archetype>,
+    pub entities: Entities,
+    pub entity_locations: HashMap<Entity, EntityLocation>,
     pub resources: HashMap<TypeId, Arc<AtomicRefCell<Box<dyn Resource>>>>,
-    pub resource_borrow_state: AtomicRefCell<ResourceBorrowState>,
-    pub component_borrow_state: AtomicRefCell<ComponentBorrowState>
+    pub resource_borrow_state: Mutex<ResourceBorrowState>,
+    pub component_borrow_state: Mutex<ComponentBorrowState>,
+    pub component_hooks: HashMap<TypeId, ComponentHooks>,
+    pub type_registry: TypeRegistry,
+    pub(crate) hook_commands: Commands,
+    pub(crate) change_tick: u32,
+    pub(crate) last_change_tick: u32,
+    pub(crate) system_last_run: Option<u32>
 }
 
 impl World {
@@ -59,13 +84,30 @@ impl World {
         resources.insert(TypeId::of::<GamepadInput>(), Arc::new(AtomicRefCell::new(Box::new(GamepadInput::default()))));
         resources.insert(TypeId::of::<Camera2d>(), Arc::new(AtomicRefCell::new(Box::new(Camera2d::default()))));
         resources.insert(TypeId::of::<TextHolder>(), Arc::new(AtomicRefCell::new(Box::new(TextHolder::default()))));
+        resources.insert(TypeId::of::<PostProcess>(), Arc::new(AtomicRefCell::new(Box::new(PostProcess::default()))));
 
-        return Self {
+        let entities: Entities = Entities::new();
+        let hook_commands: Commands = Commands::from_entities(entities.clone());
+
+        let mut world: World = Self {
             archetypes: HashMap::new(),
+            entities,
+            entity_locations: HashMap::new(),
             resources,
             resource_borrow_state: ResourceBorrowState::new().into(),
-            component_borrow_state: ComponentBorrowState::new().into()
+            component_borrow_state: ComponentBorrowState::new().into(),
+            component_hooks: HashMap::new(),
+            type_registry: TypeRegistry::new(),
+            hook_commands,
+            change_tick: 1,
+            last_change_tick: 0,
+            system_last_run: None
         };
+
+        world.component_hooks_mut::<Text>()
+            .on_remove(remove_text_renderer)
+            .on_despawn(remove_text_renderer);
+        return world;
     }
 
     /// Add a new resource to the world.
@@ -92,7 +134,7 @@ impl World {
 
         if let Some(fps_entity) = query.entities_with_components().unwrap().first() {
             if let Some(mut text_holder) = self.get_resource_mut::<TextHolder>() {
-                if let Some(text_renderer) = text_holder.text_renderers.get_mut(&fps_entity.0) {
+                if let Some(text_renderer) = text_holder.text_renderers.get_mut(fps_entity) {
                     text_renderer.content(current_fps.to_string(), render_state.queue.clone(), render_state.physical_size);
                 }
             }
@@ -119,78 +161,431 @@ impl World {
         }
     }
 
+    /// Create a new command buffer whose spawned entities are reserved on this world.
+    pub fn commands(&self) -> Commands {
+        return Commands::from_entities(self.entities.clone());
+    }
+
     /// Spawn a new entity on the world.
     ///
     /// The entity can be rendered on the fly, if its a shape or a sprite.
     pub(crate) fn spawn(&mut self, render_state: &mut RenderState, components: Vec<Box<dyn Component>>) -> Entity {
-        let entity: Entity = Entity(Uuid::new_v4());
+        let entity: Entity = self.entities.reserve();
+        self.spawn_reserved(render_state, entity, components);
+        return entity;
+    }
 
-        if components.iter().any(|component| component.as_any().is::<Text>()) {
-            let text: &Text = components.iter()
-                .find_map(|component| component.as_any().downcast_ref::<Text>()
-            ).unwrap();
+    /// Spawn an entity reserved by a command buffer on the world.
+    ///
+    /// If there is more than one component of the same type, the last one is kept.
+    ///
+    /// Returns if the entity was reserved and is now alive.
+    pub(crate) fn spawn_reserved(&mut self, render_state: &mut RenderState, entity: Entity, components: Vec<Box<dyn Component>>) -> bool {
+        if !self.entities.activate(entity) {
+            return false;
+        }
+
+        let mut seen_types_ids: HashSet<TypeId> = HashSet::new();
+        let mut components: Vec<Box<dyn Component>> = components;
+        components.reverse();
+        components.retain(|component| seen_types_ids.insert(component.as_any().type_id()));
+        components.reverse();
+
+        if let Some(text) = components.iter_mut().find_map(|component| component.as_any_mut().downcast_mut::<Text>()) {
             let mut text_holder: ResourceRefMut<'_, TextHolder> = self.get_resource_mut::<TextHolder>().unwrap();
 
-            let text_renderer: TextRenderer = TextRenderer::new(render_state, &text);
-            text_holder.text_renderers.insert(entity.0, text_renderer);
+            text.resolve_original_resolution(render_state);
+            text_holder.insert(render_state, entity, text);
         }
 
-        let mut components_refs: Vec<AtomicRefCell<Box<dyn Component>>> = Vec::with_capacity(components.len());
-        for component in components {
-            components_refs.push(AtomicRefCell::new(component));
+        if !components.iter().any(|component| component.as_any().is::<Transform>()) {
+            components.push(Box::new(Transform::default()));
         }
 
-        if !components_refs.iter().any(|component| component.borrow().as_any().is::<Transform>()) {
-            components_refs.push(AtomicRefCell::new(Box::new(Transform::default())));
+        if !components.iter().any(|component| component.as_any().is::<Visibility>()) {
+            components.push(Box::new(Visibility::default()));
         }
 
-        if !components_refs.iter().any(|component| component.borrow().as_any().is::<Visibility>()) {
-            components_refs.push(AtomicRefCell::new(Box::new(Visibility::default())));
+        if !components.iter().any(|component| component.as_any().is::<DrawOrder>()) {
+            components.push(Box::new(DrawOrder::default()));
         }
 
-        if !components_refs.iter().any(|component| component.borrow().as_any().is::<DrawOrder>()) {
-            components_refs.push(AtomicRefCell::new(Box::new(DrawOrder::default())));
+        let components_types_ids: Vec<TypeId> = components.iter().map(|component| component.as_any().type_id()).collect();
+        let change_tick: u32 = self.change_tick;
+        self.store_entity(entity, components.into_iter().map(|component| (component, ComponentTicks::new(change_tick))).collect());
+        render_state.add_entity_to_render(entity);
+        self.trigger_hooks(entity, &components_types_ids, HookKind::Add);
+
+        return true;
+    }
+
+    /// Spawn an entity reserved by a command buffer on the world as a child of another one.
+    ///
+    /// The child transform becomes relative to the parent, and both receive a global transform.
+    pub(crate) fn spawn_child(&mut self, render_state: &mut RenderState, child: Entity, parent: &Entity, components: Vec<Box<dyn Component>>) -> bool {
+        let mut components: Vec<Box<dyn Component>> = components;
+        components.retain(|component| !component.as_any().is::<Parent>());
+        components.push(Box::new(Parent(*parent)));
+
+        if !components.iter().any(|component| component.as_any().is::<GlobalTransform>()) {
+            components.push(Box::new(GlobalTransform::default()));
         }
 
-        let mut components_types_ids: Vec<TypeId> = components_refs.iter().map(|c| c.borrow().type_id()).collect();
-        let archetype_unique_key: u64 = self.get_archetype_unique_key(&mut components_types_ids);
-        let archetype: &mut Archetype = self.archetypes.entry(archetype_unique_key).or_insert_with(Archetype::new);
+        if !self.spawn_reserved(render_state, child, components) {
+            return false;
+        }
 
-        let moved_components: Vec<AtomicRefCell<Box<dyn Component>>> = take(&mut components_refs);
-        archetype.add_entity(entity, moved_components);
-        render_state.add_entity_to_render(entity);
+        let mut children: Vec<Entity> = self.get_entity_component::<Children>(parent).map(|children| children.0.clone()).unwrap_or_default();
+        children.push(child);
+        self.insert_component(render_state, parent, Box::new(Children(children)));
 
-        return entity;
+        if self.get_entity_component::<GlobalTransform>(parent).is_none() {
+            self.insert_component(render_state, parent, Box::new(GlobalTransform::default()));
+        }
+        return true;
     }
 
     /// Despawn a specific entity from the world.
     ///
     /// The entity is removed from the rendering flow and its related cached data is cleaned.
+    ///
+    /// Its children are despawned recursively and it is removed from the children of its parent.
+    ///
+    /// The on_despawn hooks of its components run before it is removed, with all of its components still available.
+    ///
+    /// The hierarchy is walked once per entity, so children that form a cycle are despawned only once.
     pub(crate) fn despawn(&mut self, render_state: &mut RenderState, entity: &Entity) {
+        let mut visited: HashSet<Entity> = HashSet::from([*entity]);
+        let mut pending: Vec<Entity> = vec![*entity];
+        let mut hierarchy: Vec<Entity> = Vec::new();
+
+        while let Some(current) = pending.pop() {
+            hierarchy.push(current);
+
+            if let Some(children) = self.get_entity_component::<Children>(&current) {
+                pending.extend(children.0.iter().copied().filter(|child| self.is_entity_alive(*child) && visited.insert(*child)));
+            }
+        }
+
+        for current in hierarchy.iter().rev() {
+            if self.is_entity_alive(*current) {
+                self.despawn_entity(render_state, current);
+            }
+        }
+    }
+
+    /// Despawn a single entity, without its children.
+    fn despawn_entity(&mut self, render_state: &mut RenderState, entity: &Entity) {
+        let parent: Option<Entity> = self.get_entity_component::<Parent>(entity).map(|parent| parent.0);
+
+        if let Some(mut children) = parent.and_then(|parent| self.get_entity_component_mut::<Children>(&parent)) {
+            children.0.retain(|child| child != entity);
+        }
+
+        let components_types_ids: Vec<TypeId> = self.get_entity_components_types_ids(entity);
+        self.trigger_hooks(*entity, &components_types_ids, HookKind::Despawn);
+
         render_state.remove_entity_to_render(entity);
+        render_state.release_entity_resources(entity);
+        self.entities.free(*entity);
+
+        if let Some(location) = self.entity_locations.remove(entity) {
+            if let Some(archetype) = self.archetypes.get_mut(&location.archetype_id) {
+                if let Some(moved_entity) = archetype.swap_remove(location.row) {
+                    self.entity_locations.insert(moved_entity, location);
+                }
+            }
+        }
+    }
+
+    /// Insert a component on a living entity.
+    ///
+    /// If the entity already has a component of the same type, it is replaced and flagged as changed.
+    /// Otherwise the entity is moved to the archetype that matches its new set of components.
+    ///
+    /// The on_remove hooks of a replaced component run before the replacement, and the on_add hooks after it.
+    pub(crate) fn insert_component(&mut self, render_state: &mut RenderState, entity: &Entity, mut component: Box<dyn Component>) {
+        let type_id: TypeId = component.as_any().type_id();
+
+        if self.get_entity_components_types_ids(entity).contains(&type_id) {
+            self.trigger_hooks(*entity, &[type_id], HookKind::Remove);
+        }
+
+        let Some(location) = self.entity_locations.get(entity).copied() else {
+            return;
+        };
+
+        if let Some(text) = component.as_any_mut().downcast_mut::<Text>() {
+            let mut text_holder: ResourceRefMut<'_, TextHolder> = self.get_resource_mut::<TextHolder>().unwrap();
+
+            text.resolve_original_resolution(render_state);
+            text_holder.insert(render_state, *entity, text);
+        }
+
+        let change_tick: u32 = self.change_tick;
+        let archetype: &mut Archetype = self.archetypes.get_mut(&location.archetype_id).unwrap();
+
+        if archetype.has_component(&type_id) {
+            archetype.replace_component(location.row, component, change_tick);
+        } else if let Some(mut components) = self.take_entity(entity) {
+            components.push((component, ComponentTicks::new(change_tick)));
+            self.store_entity(*entity, components);
+        }
+
         render_state.clean_entity_buffer_cache(entity);
         render_state.clean_entity_bind_group_cache(entity);
+        Self::release_entity_textures(render_state, entity, type_id);
+        self.trigger_hooks(*entity, &[type_id], HookKind::Add);
+    }
 
-        for archetype in self.archetypes.values_mut() {
-            if let Some(index) = archetype.entities.iter().position(|e| e.0 == entity.0) {
-                for components in archetype.components.values_mut() {
-                    if index < components.len() {
-                        components.remove(index);
-                    }
-                }
-                archetype.entities.remove(index);
-                break;
+    /// Remove a component from a living entity by its type.
+    ///
+    /// The entity is moved to the archetype that matches its remaining set of components.
+    ///
+    /// The on_remove hooks of the component run before it is removed.
+    pub(crate) fn remove_component(&mut self, render_state: &mut RenderState, entity: &Entity, type_id: TypeId) {
+        if !self.get_entity_components_types_ids(entity).contains(&type_id) {
+            return;
+        }
+
+        self.trigger_hooks(*entity, &[type_id], HookKind::Remove);
+
+        if let Some(mut components) = self.take_entity(entity) {
+            components.retain(|(component, _)| component.as_any().type_id() != type_id);
+            self.store_entity(*entity, components);
+        }
+
+        render_state.clean_entity_buffer_cache(entity);
+        render_state.clean_entity_bind_group_cache(entity);
+        Self::release_entity_textures(render_state, entity, type_id);
+    }
+
+    /// Release the textures owned by an entity if its textured component was replaced or removed.
+    ///
+    /// The textures it still renders are acquired again on the next frame.
+    fn release_entity_textures(render_state: &mut RenderState, entity: &Entity, type_id: TypeId) {
+        if type_id == TypeId::of::<Sprite>() || type_id == TypeId::of::<Animation>() || type_id == TypeId::of::<Material>() {
+            render_state.release_entity_textures(entity);
+        }
+    }
+
+    /// Returns the hooks registered for a component type, creating them if needed.
+    ///
+    /// Useful to register on_add, on_remove and on_despawn hooks that manage the resources tied to the component.
+    pub fn component_hooks_mut<T: Component + 'static>(&mut self) -> &mut ComponentHooks {
+        return self.component_hooks.entry(TypeId::of::<T>()).or_default();
+    }
+
+    /// Returns the types of the components of an entity.
+    pub fn get_entity_components_types_ids(&self, entity: &Entity) -> Vec<TypeId> {
+        return self.get_entity_archetype_and_row(entity)
+            .map(|(archetype, _)| archetype.columns.keys().copied().collect())
+            .unwrap_or_default();
+    }
+
+    /// Register a component type for reflection, so it can be inspected and created by its type name.
+    pub fn register_component<T: Component + Reflect>(&mut self) {
+        self.type_registry.register_component::<T>();
+    }
+
+    /// Register a resource type for reflection, so it can be inspected and created by its type name.
+    pub fn register_resource<T: Resource + Reflect>(&mut self) {
+        self.type_registry.register_resource::<T>();
+    }
+
+    /// Save the living entities of the world and their registered components as a scene file, relative to the assets directory.
+    pub fn save_scene(&self, path: &str) -> Result<(), SceneError> {
+        return Scene::from_world(self)?.save(path);
+    }
+
+    /// Spawn the entities of a scene on entities reserved by a command buffer, keeping their hierarchy.
+    ///
+    /// All the components are created before spawning, so a scene with an unknown component spawns nothing.
+    pub(crate) fn spawn_scene(&mut self, render_state: &mut RenderState, entities: &[Entity], scene: &Scene) -> Result<(), SceneError> {
+        scene.validate()?;
+        let components: Vec<Vec<Box<dyn Component>>> = scene.construct_components(self)?;
+
+        self.spawn_scene_components(render_state, entities, scene, components);
+        return Ok(());
+    }
+
+    /// Save the snapshot of the selected resources and persistent entities of the world as a save file.
+    pub fn save_game(&self, save_game: &SaveGame, path: &str) -> Result<(), SaveError> {
+        return save_game.save(self, path);
+    }
+
+    /// Restore a snapshot on entities reserved by a command buffer, replacing the persistent entities and the snapshot resources.
+    ///
+    /// All the components are created before anything is replaced, so a snapshot with an unknown component changes nothing.
+    pub(crate) fn restore_game(&mut self, render_state: &mut RenderState, entities: &[Entity], scene: &Scene, resources: Vec<Box<dyn Resource>>) -> Result<(), SceneError> {
+        scene.validate()?;
+        let components: Vec<Vec<Box<dyn Component>>> = scene.construct_components(self)?;
+
+        let persistent_entities: Vec<Entity> = self.query_filtered::<Entity, With<Persistent>>().collect();
+        for entity in persistent_entities {
+            if self.is_entity_alive(entity) {
+                self.despawn(render_state, &entity);
+            }
+        }
+
+        self.add_resources(resources);
+        self.spawn_scene_components(render_state, entities, scene, components);
+        return Ok(());
+    }
+
+    /// Spawn the entities of a scene with their already created components.
+    fn spawn_scene_components(&mut self, render_state: &mut RenderState, entities: &[Entity], scene: &Scene, components: Vec<Vec<Box<dyn Component>>>) {
+        for ((index, scene_entity), components) in scene.entities.iter().enumerate().zip(components) {
+            let entity: Entity = entities[index];
+
+            if let Some(parent) = scene_entity.parent {
+                self.spawn_child(render_state, entity, &entities[parent], components);
+            } else {
+                self.spawn_reserved(render_state, entity, components);
             }
         }
     }
 
-    /// Synchronize all pending events.
-    pub(crate) fn synchronize(&mut self, render_state: &mut RenderState, delta: f32) {
-        synchronizer::events(self, render_state);
-        synchronizer::camera(self, render_state);
-        synchronizer::animations(self, delta);
-        synchronizer::collisions(self);
-        synchronizer::gravity(self, render_state, delta);
+    /// Returns the reflected values of the registered components of an entity, sorted by their type names.
+    ///
+    /// A component that can't be reflected is returned as an error.
+    pub fn reflect_entity(&self, entity: &Entity) -> Result<Vec<(&'static str, ReflectValue)>, ReflectError> {
+        let mut components: Vec<(&'static str, ReflectValue)> = self.get_entity_components_types_ids(entity).iter()
+            .filter_map(|type_id| self.type_registry.get_component_by_type_id(type_id))
+            .filter_map(|registration| {
+                let mut value: Option<Result<ReflectValue, ReflectError>> = None;
+                registration.reflect(self, entity, &mut |component| value = Some(component.to_value()));
+                return value.map(|value| value.map(|value| (registration.type_name, value)));
+            })
+            .collect::<Result<Vec<(&'static str, ReflectValue)>, ReflectError>>()?;
+
+        components.sort_by(|a, b| a.0.cmp(b.0));
+        return Ok(components);
+    }
+
+    /// Returns the reflected value of a field of a component of an entity by its type name and path.
+    ///
+    /// An empty path returns the value of the whole component.
+    pub fn get_component_path(&self, entity: &Entity, type_name: &str, path: &str) -> Result<ReflectValue, ReflectError> {
+        let registration: &ComponentRegistration = self.type_registry.get_component(type_name)
+            .ok_or_else(|| ReflectError::UnknownType(type_name.to_string()))?;
+
+        let mut result: Result<ReflectValue, ReflectError> = Err(ReflectError::MissingComponent(type_name.to_string()));
+        registration.reflect(self, entity, &mut |component| result = component.path_value(path));
+        return result;
+    }
+
+    /// Apply a reflected value to a field of a component of an entity by its type name and path, marking it as changed.
+    ///
+    /// An empty path applies the value to the whole component.
+    pub fn set_component_path(&self, entity: &Entity, type_name: &str, path: &str, value: &ReflectValue) -> Result<(), ReflectError> {
+        let registration: &ComponentRegistration = self.type_registry.get_component(type_name)
+            .ok_or_else(|| ReflectError::UnknownType(type_name.to_string()))?;
+
+        let mut result: Result<(), ReflectError> = Err(ReflectError::MissingComponent(type_name.to_string()));
+        registration.reflect_mut(self, entity, &mut |component| result = component.set_path(path, value));
+        return result;
+    }
+
+    /// Returns the reflected value of a field of a resource by its type name and path.
+    ///
+    /// An empty path returns the value of the whole resource.
+    pub fn get_resource_path(&self, type_name: &str, path: &str) -> Result<ReflectValue, ReflectError> {
+        let registration: &ResourceRegistration = self.type_registry.get_resource(type_name)
+            .ok_or_else(|| ReflectError::UnknownType(type_name.to_string()))?;
+
+        let mut result: Result<ReflectValue, ReflectError> = Err(ReflectError::MissingResource(type_name.to_string()));
+        registration.reflect(self, &mut |resource| result = resource.path_value(path));
+        return result;
+    }
+
+    /// Apply a reflected value to a field of a resource by its type name and path.
+    ///
+    /// An empty path applies the value to the whole resource.
+    pub fn set_resource_path(&self, type_name: &str, path: &str, value: &ReflectValue) -> Result<(), ReflectError> {
+        let registration: &ResourceRegistration = self.type_registry.get_resource(type_name)
+            .ok_or_else(|| ReflectError::UnknownType(type_name.to_string()))?;
+
+        let mut result: Result<(), ReflectError> = Err(ReflectError::MissingResource(type_name.to_string()));
+        registration.reflect_mut(self, &mut |resource| result = resource.set_path(path, value));
+        return result;
+    }
+
+    /// Run the hooks registered for a moment of the lifecycle of the components of an entity.
+    ///
+    /// The commands issued by the hooks are flushed right after the current ones.
+    fn trigger_hooks(&mut self, entity: Entity, components_types_ids: &[TypeId], kind: HookKind) {
+        let hooks: Vec<ComponentHook> = components_types_ids.iter()
+            .filter_map(|type_id| self.component_hooks.get(type_id))
+            .flat_map(|component_hooks| component_hooks.get(kind).iter().cloned())
+            .collect();
+
+        if hooks.is_empty() {
+            return;
+        }
+
+        let mut commands: Commands = self.commands();
+        for hook in hooks {
+            hook(self, &mut commands, entity);
+        }
+        self.hook_commands.append(&mut commands);
+    }
+
+    /// Store an entity with its components on the archetype that matches them and index its location.
+    fn store_entity(&mut self, entity: Entity, components: Vec<(Box<dyn Component>, ComponentTicks)>) {
+        let mut components_types_ids: Vec<TypeId> = components.iter().map(|(c, _)| c.as_any().type_id()).collect();
+        let archetype_unique_key: u64 = self.get_archetype_unique_key(&mut components_types_ids);
+        let archetype: &mut Archetype = self.archetypes.entry(archetype_unique_key).or_insert_with(|| Archetype::new(archetype_unique_key));
+
+        let row: usize = archetype.add_entity_with_ticks(entity, components);
+        self.entity_locations.insert(entity, EntityLocation { archetype_id: archetype_unique_key, row });
+    }
+
+    /// Take an entity with its components out of its archetype, keeping the location of the moved entity.
+    fn take_entity(&mut self, entity: &Entity) -> Option<Vec<(Box<dyn Component>, ComponentTicks)>> {
+        let location: EntityLocation = self.entity_locations.remove(entity)?;
+        let archetype: &mut Archetype = self.archetypes.get_mut(&location.archetype_id)?;
+
+        let (components, moved_entity): (Vec<(Box<dyn Component>, ComponentTicks)>, Option<Entity>) = archetype.take_entity(location.row);
+        if let Some(moved_entity) = moved_entity {
+            self.entity_locations.insert(moved_entity, location);
+        }
+        return Some(components);
+    }
+
+    /// Advance the world tick used to flag added and changed components.
+    ///
+    /// Outside of the systems, the Added and Changed filters match the components flagged since the last call.
+    ///
+    /// Called by the game loop once at the start of each frame.
+    pub fn increment_change_tick(&mut self) {
+        self.last_change_tick = self.change_tick;
+        self.change_tick = self.change_tick.wrapping_add(1);
+    }
+
+    /// Returns the current world tick.
+    pub fn get_change_tick(&self) -> u32 {
+        return self.change_tick;
+    }
+
+    /// Returns the tick after which the Added and Changed filters match, the last run of the running system if there is one.
+    pub fn get_last_run_tick(&self) -> u32 {
+        return self.system_last_run.unwrap_or(self.last_change_tick);
+    }
+
+    /// Start the run of a system that last ran on a specific tick, returning the tick of this run.
+    ///
+    /// The returned tick is the next last run of the system.
+    pub(crate) fn begin_system_run(&mut self, last_run: u32) -> u32 {
+        self.change_tick = self.change_tick.wrapping_add(1);
+        self.system_last_run = Some(last_run);
+        return self.change_tick;
+    }
+
+    /// Finish the run of a system, so the changes made afterwards are newer than its run.
+    pub(crate) fn end_system_run(&mut self) {
+        self.system_last_run = None;
+        self.change_tick = self.change_tick.wrapping_add(1);
     }
 
     /// Returns the unique key of a archetype.
@@ -212,7 +607,7 @@ impl World {
     /// Returns an immutable reference to the specified resource.
     pub fn get_resource<T: Resource + 'static>(&self) -> Option<ResourceRef<'_, T>> {
         let type_id: TypeId = TypeId::of::<T>();
-        let mut resource_borrow_state: AtomicRefMut<'_, ResourceBorrowState> = self.resource_borrow_state.borrow_mut();
+        let mut resource_borrow_state: MutexGuard<'_, ResourceBorrowState> = self.resource_borrow_state.lock().unwrap();
 
         if resource_borrow_state.try_borrow_immutable(type_id) {
             if let Some(resource) = self.resources.get(&type_id) {
@@ -239,7 +634,7 @@ impl World {
     /// Returns a mutable reference to the specified resource.
     pub fn get_resource_mut<T: Resource + 'static>(&self) -> Option<ResourceRefMut<'_, T>> {
         let type_id: TypeId = TypeId::of::<T>();
-        let mut resource_borrow_state: AtomicRefMut<'_, ResourceBorrowState> = self.resource_borrow_state.borrow_mut();
+        let mut resource_borrow_state: MutexGuard<'_, ResourceBorrowState> = self.resource_borrow_state.lock().unwrap();
 
         if resource_borrow_state.try_borrow_mutable(type_id) {
             if let Some(resource) = self.resources.get(&type_id) {
@@ -262,108 +657,132 @@ impl World {
 
     /// Returns a specific component from an entity.
     pub fn get_entity_component<T: Component + 'static>(&self, entity: &Entity) -> Option<ComponentRef<'_, T>> {
-        for archetype in self.archetypes.values() {
-            if archetype.entities.contains(entity) {
-                let type_id: TypeId = TypeId::of::<T>();
-                let mut component_borrow_state: AtomicRefMut<'_, ComponentBorrowState> = self.component_borrow_state.borrow_mut();
-
-                if component_borrow_state.try_borrow_immutable(type_id, entity.0) {
-                    if let Some(components) = archetype.components.get(&type_id) {
-                        if let Some(index) = archetype.entities.iter().position(|e| e == entity) {
-                            return Some(ComponentRef {
-                                inner: components[index].borrow(),
-                                type_id,
-                                entity_id: entity.0,
-                                component_borrow_state: &self.component_borrow_state,
-                                phantom_data: std::marker::PhantomData
-                            });
-                        } else {
-                            component_borrow_state.release_immutable(type_id, entity.0);
-                            return None;
-                        }
-                    }
-                } else {
-                    return None;
-                }
-            }
-        }
-        return None;
+        let (archetype, row): (&Archetype, usize) = self.get_entity_archetype_and_row(entity)?;
+        return self.borrow_component::<T>(archetype, entity, row);
     }
 
     /// Return a specific component from an entity as mutable.
     pub fn get_entity_component_mut<T: Component + 'static>(&self, entity: &Entity) -> Option<ComponentRefMut<'_, T>> {
-        for archetype in self.archetypes.values() {
-            if archetype.entities.contains(entity) {
-                let type_id: TypeId = TypeId::of::<T>();
-                let mut component_borrow_state: AtomicRefMut<'_, ComponentBorrowState> = self.component_borrow_state.borrow_mut();
-
-                if component_borrow_state.try_borrow_mutable(type_id, entity.0) {
-                    if let Some(components) = archetype.components.get(&type_id) {
-                        if let Some(index) = archetype.entities.iter().position(|e| e == entity) {
-                            return Some(ComponentRefMut {
-                                inner: components[index].borrow_mut(),
-                                type_id,
-                                entity_id: entity.0,
-                                component_borrow_state: &self.component_borrow_state,
-                                phantom_data: std::marker::PhantomData
-                            });
-                        } else {
-                            component_borrow_state.release_mutable(type_id, entity.0);
-                            return None;
-                        }
-                    }
-                } else {
-                    return None;
-                }
-            }
+        let (archetype, row): (&Archetype, usize) = self.get_entity_archetype_and_row(entity)?;
+        return self.borrow_component_mut::<T>(archetype, entity, row);
+    }
+
+    /// Returns a typed query iterator over all the entities that match the parameters.
+    ///
+    /// The parameters can be `&T`, `&mut T`, `Option<&T>`, `Option<&mut T>`, `Entity` or tuples of them.
+    ///
+    /// ```
+    /// use lotus_engine::*;
+    ///
+    /// fn move_objects(context: &mut Context) {
+    ///     for (mut transform, velocity) in context.world.query::<(&mut Transform, &Velocity)>() {
+    ///         transform.position.x += velocity.x * context.delta;
+    ///     }
+    /// }
+    /// ```
+    pub fn query<Q: QueryParameter>(&self) -> QueryIter<'_, Q> {
+        return QueryIter::new(self);
+    }
+
+    /// Returns a typed query iterator over all the entities that match the parameters and pass the filters.
+    ///
+    /// The filters can be `With<T>`, `Without<T>`, `Or<(...)>`, `Added<T>`, `Changed<T>` or tuples of them.
+    ///
+    /// ```
+    /// use lotus_engine::*;
+    ///
+    /// fn moved_colliders(context: &mut Context) -> Vec<Entity> {
+    ///     return context.world.query_filtered::<Entity, (With<Collision>, Without<Text>, Changed<Transform>)>().collect();
+    /// }
+    /// ```
+    pub fn query_filtered<Q: QueryParameter, F: QueryFilter>(&self) -> QueryIter<'_, Q, F> {
+        return QueryIter::new(self);
+    }
+
+    /// Returns the typed query parameters of a specific entity.
+    ///
+    /// Returns None if the entity doesn't match the parameters or if they are already borrowed.
+    pub fn query_entity<Q: QueryParameter>(&self, entity: &Entity) -> Option<Q::Item<'_>> {
+        let (archetype, row): (&Archetype, usize) = self.get_entity_archetype_and_row(entity)?;
+        let mut required_types: Vec<TypeId> = Vec::new();
+        Q::required_types(&mut required_types);
+
+        if required_types.iter().all(|type_id| archetype.has_component(type_id)) {
+            return Q::fetch(self, archetype, entity, row);
         }
         return None;
     }
 
-    /// Return all the components from a specific entity.
-    pub fn get_entity_components<'a>(&'a self, entity: &'a Entity) -> Option<Vec<AtomicRef<'a, Box<dyn Component>>>> {
-        if let Some((_, archetype)) = self.archetypes.iter().find(|(_, arch)| arch.entities.contains(entity)) {
-            if let Some(index) = archetype.entities.iter().position(|e| e == entity) {
-                let mut entity_components: Vec<AtomicRef<'a, Box<dyn Component>>> = Vec::new();
-
-                for component_vec in archetype.components.values() {
-                    if let Some(component) = component_vec.get(index) {
-                        let borrowed: AtomicRef<'_, Box<dyn Component>> = component.borrow();
-                        entity_components.push(borrowed);
-                    }
-                }
-                return Some(entity_components);
-            }
+    /// Returns the archetype that stores an entity and its row inside of it.
+    pub(crate) fn get_entity_archetype_and_row(&self, entity: &Entity) -> Option<(&Archetype, usize)> {
+        let location: &EntityLocation = self.entity_locations.get(entity)?;
+        return self.archetypes.get(&location.archetype_id).map(|archetype| (archetype, location.row));
+    }
+
+    /// Returns the location of an entity inside of the world.
+    pub fn get_entity_location(&self, entity: &Entity) -> Option<EntityLocation> {
+        return self.entity_locations.get(entity).copied();
+    }
+
+    /// Returns a specific component stored on a row of an archetype.
+    pub(crate) fn borrow_component<'w, T: Component + 'static>(&'w self, archetype: &'w Archetype, entity: &Entity, row: usize) -> Option<ComponentRef<'w, T>> {
+        let type_id: TypeId = TypeId::of::<T>();
+        let component: &AtomicRefCell<T> = archetype.get_column::<T>()?.get(row)?;
+        let mut component_borrow_state: MutexGuard<'_, ComponentBorrowState> = self.component_borrow_state.lock().unwrap();
+
+        if component_borrow_state.try_borrow_immutable(type_id, *entity) {
+            return Some(ComponentRef {
+                inner: component.borrow(),
+                type_id,
+                entity: *entity,
+                component_borrow_state: &self.component_borrow_state,
+                phantom_data: PhantomData
+            });
         }
         return None;
     }
 
-    /// Return all the components from a specific entity as mutables.
-    pub fn get_entity_components_mut<'a>(&'a self, entity: &'a Entity) -> Option<Vec<AtomicRefMut<'a, Box<dyn Component>>>> {
-        if let Some((_, archetype)) = self.archetypes.iter().find(|(_, arch)| arch.entities.contains(entity)) {
-            if let Some(index) = archetype.entities.iter().position(|e| e == entity) {
-                let mut entity_components: Vec<AtomicRefMut<'a, Box<dyn Component>>> = Vec::new();
-
-                for component_vec in archetype.components.values() {
-                    if let Some(component) = component_vec.get(index) {
-                        let borrowed: AtomicRefMut<'_, Box<dyn Component>> = component.borrow_mut();
-                        entity_components.push(borrowed);
-                    }
-                }
-                return Some(entity_components);
-            }
+    /// Returns a specific component stored on a row of an archetype as mutable.
+    pub(crate) fn borrow_component_mut<'w, T: Component + 'static>(&'w self, archetype: &'w Archetype, entity: &Entity, row: usize) -> Option<ComponentRefMut<'w, T>> {
+        let type_id: TypeId = TypeId::of::<T>();
+        let column: &Column<T> = archetype.get_column::<T>()?;
+        let component: &AtomicRefCell<T> = column.get(row)?;
+        let ticks: &ComponentTicks = column.ticks.get(row)?;
+        let mut component_borrow_state: MutexGuard<'_, ComponentBorrowState> = self.component_borrow_state.lock().unwrap();
+
+        if component_borrow_state.try_borrow_mutable(type_id, *entity) {
+            return Some(ComponentRefMut {
+                inner: component.borrow_mut(),
+                type_id,
+                entity: *entity,
+                component_borrow_state: &self.component_borrow_state,
+                ticks,
+                change_tick: self.change_tick,
+                phantom_data: PhantomData
+            });
         }
         return None;
     }
 
+    /// Return all the components from a specific entity.
+    pub fn get_entity_components<'a>(&'a self, entity: &'a Entity) -> Option<Vec<AtomicRef<'a, dyn Component>>> {
+        let (archetype, row): (&Archetype, usize) = self.get_entity_archetype_and_row(entity)?;
+        return Some(archetype.columns.values().filter_map(|column| column.get_component(row)).collect());
+    }
+
+    /// Return all the components from a specific entity as mutables.
+    pub fn get_entity_components_mut<'a>(&'a self, entity: &'a Entity) -> Option<Vec<AtomicRefMut<'a, dyn Component>>> {
+        let (archetype, row): (&Archetype, usize) = self.get_entity_archetype_and_row(entity)?;
+        return Some(archetype.columns.values().filter_map(|column| column.get_component_mut(row)).collect());
+    }
+
     /// Returns if an entity still is in the world.
     pub fn is_entity_alive(&self, entity: Entity) -> bool {
-        return self.archetypes.values().any(|archetype| archetype.entities.iter().any(|e| e.0 == entity.0));
+        return self.entities.is_alive(entity);
     }
 
     /// Returns if an entity is visible.
     pub fn is_entity_visible(&self, entity: Entity) -> bool {
-        let visibility: ComponentRef<'_, Visibility> = self.get_entity_component::<Visibility>(&entity).unwrap();
-        return visibility.0;
+        return self.get_entity_component::<Visibility>(&entity).is_none_or(|visibility| visibility.0);
     }
 }
@@ -0,0 +1,157 @@
+use std::{any::Any, mem::take, marker::PhantomData};
+use super::super::ecs::{resource::{Resource, ResourceRefMut}, world::World};
+
+/// Struct to represent an event stored with its sequential identifier.
+struct EventInstance<T> {
+    id: usize,
+    event: T
+}
+
+/// Struct to represent the resource that stores a channel of user-defined events.
+///
+/// The events are double-buffered: the ones sent on a frame can be read on that frame and on the next one,
+/// being cleared after that.
+///
+/// Register a channel with Context::add_event, so the buffers are swapped at the start of each frame.
+pub struct Events<T: Send + Sync + 'static> {
+    previous: Vec<EventInstance<T>>,
+    current: Vec<EventInstance<T>>,
+    event_count: usize
+}
+
+impl<T: Send + Sync + 'static> Events<T> {
+    /// Create a new event channel without any event.
+    pub fn new() -> Self {
+        return Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            event_count: 0
+        };
+    }
+
+    /// Send a new event to the channel.
+    pub fn send(&mut self, event: T) {
+        self.current.push(EventInstance { id: self.event_count, event });
+        self.event_count += 1;
+    }
+
+    /// Swap the buffers, dropping the events sent two frames ago.
+    ///
+    /// Called by the schedule at the start of each frame for the registered channels.
+    pub fn update(&mut self) {
+        self.previous = take(&mut self.current);
+    }
+
+    /// Remove all the events of the channel.
+    pub fn clear(&mut self) {
+        self.previous.clear();
+        self.current.clear();
+    }
+
+    /// Returns the number of events stored on the channel.
+    pub fn len(&self) -> usize {
+        return self.previous.len() + self.current.len();
+    }
+
+    /// Returns if the channel has no events stored.
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Returns the events stored on the channel, from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        return self.previous.iter().chain(self.current.iter()).map(|instance| &instance.event);
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for Events<T> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<T: Send + Sync + 'static> Resource for Events<T> {
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
+}
+
+/// Struct to represent the writing access to an event channel.
+pub struct EventWriter<'a, T: Send + Sync + 'static> {
+    events: ResourceRefMut<'a, Events<T>>
+}
+
+impl<'a, T: Send + Sync + 'static> EventWriter<'a, T> {
+    /// Create a new event writer by the channel stored on the world, if it was registered.
+    pub fn new(world: &'a World) -> Option<Self> {
+        return world.get_resource_mut::<Events<T>>().map(|events| Self { events });
+    }
+
+    /// Send a new event to the channel.
+    pub fn send(&mut self, event: T) {
+        self.events.send(event);
+    }
+
+    /// Send a list of events to the channel.
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        for event in events {
+            self.events.send(event);
+        }
+    }
+}
+
+/// Struct to represent the reading access to an event channel.
+///
+/// Each reader tracks its own cursor, so every reader sees every event once.
+///
+/// Readers are usually moved into the closure of the system that reads the events.
+pub struct EventReader<T: Send + Sync + 'static> {
+    cursor: usize,
+    _marker: PhantomData<fn() -> T>
+}
+
+impl<T: Send + Sync + 'static> EventReader<T> {
+    /// Create a new event reader that will read all the events still stored on the channel.
+    pub fn new() -> Self {
+        return Self {
+            cursor: 0,
+            _marker: PhantomData
+        };
+    }
+
+    /// Returns the events that were not read yet by this reader, moving its cursor to the end of the channel.
+    pub fn read<'a>(&mut self, events: &'a Events<T>) -> impl Iterator<Item = &'a T> {
+        let cursor: usize = self.cursor;
+        self.cursor = events.event_count;
+
+        return events.previous.iter()
+            .chain(events.current.iter())
+            .filter(move |instance| instance.id >= cursor)
+            .map(|instance| &instance.event);
+    }
+
+    /// Returns the number of events that were not read yet by this reader.
+    pub fn len(&self, events: &Events<T>) -> usize {
+        return events.previous.iter().chain(events.current.iter()).filter(|instance| instance.id >= self.cursor).count();
+    }
+
+    /// Returns if all the events of the channel were read by this reader.
+    pub fn is_empty(&self, events: &Events<T>) -> bool {
+        return self.len(events) == 0;
+    }
+
+    /// Move the cursor of this reader to the end of the channel, skipping the events not read yet.
+    pub fn clear(&mut self, events: &Events<T>) {
+        self.cursor = events.event_count;
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for EventReader<T> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
@@ -43,10 +43,10 @@ impl Event {
         };
     }
 
-    /// Create a new event struct with a dummy entity.
+    /// Create a new event struct that is not related to any entity.
     pub(crate) fn new_with_dummy<T: Any + Send + Sync>(event_type: EventType, value: T) -> Self {
         return Self {
-            entity: Entity::dummy(),
+            entity: Entity::PLACEHOLDER,
             event_type: event_type,
             value: Box::new(value)
         };
@@ -3,3 +3,6 @@ pub mod dispatcher;
 
 /// Event synchronization related features.
 pub mod synchronizer;
+
+/// Typed event channels related features.
+pub mod channel;
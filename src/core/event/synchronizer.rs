@@ -1,16 +1,16 @@
-use std::any::TypeId;
 use atomic_refcell::{AtomicRef, AtomicRefMut};
 use cgmath::{Matrix4, Vector2, Vector3};
 use gilrs::{Axis, Button, GamepadId};
 use super::{
     dispatcher::{EventDispatcher, EventType, SubEventType},
     super::{
-        ecs::{resource::ResourceRefMut, component::{ComponentRefMut, Component}, world:: World, query::Query},
-        physics::{transform::{Transform, Position}, collision::Collision, velocity::Velocity, gravity::Gravity, rigid_body::{RigidBody, BodyType}},
+        ecs::{resource::ResourceRefMut, component::ComponentRefMut, column::Column, world:: World},
+        physics::{transform::{Transform, GlobalTransform, Position}, collision::Collision, velocity::Velocity, gravity::Gravity, rigid_body::{RigidBody, BodyType}},
         managers::render::{manager::RenderState, cache},
         text::{text::TextHolder, font::Font},
         color::color::Color,
         camera::camera2d::Camera2d,
+        particle::particle_emitter::ParticleEmitter,
         animation::{animation::Animation, looping_state::LoopingState, animation_state::AnimationState},
         bindings::gamepad::{gamepad_input::GamepadInput, gamepad_instance::GamepadInstance, gamepad_button::GamepadButton}
     }
@@ -41,17 +41,12 @@ pub(crate) fn events(world: &mut World, render_state: &RenderState) {
             EventType::Text(sub_event_type) => {
                 let mut text_holder: ResourceRefMut<'_, TextHolder> = world.get_resource_mut::<TextHolder>().unwrap();
 
-                if let Some(text_renderer) = text_holder.text_renderers.get_mut(&event.entity.0) {
+                if sub_event_type == &SubEventType::UpdateTextFont {
+                    if let Some(font) = event.get::<Font>() {
+                        text_holder.update_font(render_state, event.entity, font.clone());
+                    }
+                } else if let Some(text_renderer) = text_holder.text_renderers.get_mut(&event.entity) {
                     match sub_event_type {
-                        SubEventType::UpdateTextFont => {
-                            if let Some(font) = event.get::<Font>() {
-                                text_renderer.font(
-                                    font.clone(),
-                                    render_state.queue.clone(),
-                                    render_state.physical_size.clone()
-                                );
-                            }
-                        },
                         SubEventType::UpdateTextPosition => {
                             if let Some(position) = event.get::<Position>() {
                                 text_renderer.position(
@@ -159,73 +154,72 @@ pub(crate) fn camera(world: &mut World, render_state: &mut RenderState) {
 
 /// Synchronizes the animation of entities sprite sheets.
 pub(crate) fn animations(world: &mut World, delta: f32) {
-    let mut query: Query<'_> = Query::new(&world).with::<Animation>();
-
-    for entity in query.entities_with_components().unwrap() {
-        if let Some(mut animation) = world.get_entity_component_mut::<Animation>(&entity) {
-            let mut to_remove_from_stack: Vec<String> = Vec::new();
+    for mut animation in world.query::<&mut Animation>() {
+        let mut to_remove_from_stack: Vec<String> = Vec::new();
 
-            for (title, sprite_sheet) in animation.sprite_sheets.iter_mut() {
-                if sprite_sheet.animation_state != AnimationState::Playing {
-                    continue;
-                }
-                sprite_sheet.timer.tick(delta);
+        for (title, sprite_sheet) in animation.sprite_sheets.iter_mut() {
+            if sprite_sheet.animation_state != AnimationState::Playing {
+                continue;
+            }
+            sprite_sheet.timer.tick(delta);
 
-                if sprite_sheet.timer.is_finished() {
-                    sprite_sheet.current_index = (sprite_sheet.current_index + 1) % sprite_sheet.indices.len() as u32;
+            if sprite_sheet.timer.is_finished() {
+                sprite_sheet.current_index = (sprite_sheet.current_index + 1) % sprite_sheet.indices.len() as u32;
 
-                    if &sprite_sheet.current_index == sprite_sheet.indices.last().unwrap() && sprite_sheet.looping_state != LoopingState::Repeat {
-                        sprite_sheet.animation_state = AnimationState::Finished;
-                        to_remove_from_stack.push(title.clone());
-                    }
+                if &sprite_sheet.current_index == sprite_sheet.indices.last().unwrap() && sprite_sheet.looping_state != LoopingState::Repeat {
+                    sprite_sheet.animation_state = AnimationState::Finished;
+                    to_remove_from_stack.push(title.clone());
                 }
             }
+        }
 
-            for title in to_remove_from_stack {
-                animation.playing_stack.retain(|t| *t != title);
-            }
+        for title in to_remove_from_stack {
+            animation.playing_stack.retain(|t| *t != title);
         }
     }
 }
 
 /// Synchronizes the gravity with entities that are considered dynamic bodies.
 pub(crate) fn gravity(world: &mut World, render_state: &mut RenderState, delta: f32) {
-    let mut query: Query<'_> = Query::new(world).with::<Gravity>()
-        .with::<Transform>()
-        .with::<Velocity>()
-        .with::<RigidBody>();
-
-    for entity in query.entities_with_components().unwrap() {
-        if let (Some(gravity), Some(mut transform), Some(mut velocity), Some(rigid_body)) = (
-            world.get_entity_component::<Gravity>(&entity),
-            world.get_entity_component_mut::<Transform>(&entity),
-            world.get_entity_component_mut::<Velocity>(&entity),
-            world.get_entity_component::<RigidBody>(&entity)
-        ) {
-            if rigid_body.body_type == BodyType::Dynamic && rigid_body.rest == false {
-                velocity.y -= gravity.value * rigid_body.friction * delta;
-                let new_y: f32 = transform.position.y + velocity.y * delta;
-                transform.set_position_y(render_state, new_y);
-            }
+    for (gravity, mut transform, mut velocity, rigid_body) in world.query::<(&Gravity, &mut Transform, &mut Velocity, &RigidBody)>() {
+        if rigid_body.body_type == BodyType::Dynamic && rigid_body.rest == false {
+            velocity.y -= gravity.value * rigid_body.friction * delta;
+            let new_y: f32 = transform.position.y + velocity.y * delta;
+            transform.set_position_y(render_state, new_y);
         }
     }
 }
 
+/// Synchronizes the particle emitters, simulating their particles from the world position of their entities.
+pub(crate) fn particles(world: &mut World, render_state: &RenderState, delta: f32) {
+    for (mut particle_emitter, transform, global_transform) in world.query::<(&mut ParticleEmitter, Option<&Transform>, Option<&GlobalTransform>)>() {
+        let origin: Vector2<f32> = transform.as_deref()
+            .map(|transform| render_state.get_world_position(transform, global_transform.as_deref()))
+            .unwrap_or(Vector2::new(0.0, 0.0));
+        particle_emitter.update(delta, origin);
+    }
+}
+
 /// Synchronizes the transformation matrices with the collision objects.
+///
+/// Entities of a hierarchy use their global transform, so children collide where they are rendered.
 pub(crate) fn collisions(world: &mut World) {
-    for archetype in world.archetypes.values_mut() {
+    for archetype in world.archetypes.values() {
         if let (Some(transforms), Some(collisions)) = (
-            archetype.components.get(&TypeId::of::<Transform>()),
-            archetype.components.get(&TypeId::of::<Collision>())
+            archetype.get_column::<Transform>(),
+            archetype.get_column::<Collision>()
         ) {
-            for (transform, collision) in transforms.iter().zip(collisions) {
-                let transform_ref: AtomicRef<'_, Box<dyn Component>> = transform.borrow();
-                let mut collision_ref: AtomicRefMut<'_, Box<dyn Component>> = collision.borrow_mut();
-
-                if let (Some(transform), Some(collision)) = (
-                    transform_ref.as_any().downcast_ref::<Transform>(),
-                    collision_ref.as_any_mut().downcast_mut::<Collision>()
-                ) {
+            let global_transforms: Option<&Column<GlobalTransform>> = archetype.get_column::<GlobalTransform>();
+
+            for (row, (transform, collision)) in transforms.components.iter().zip(&collisions.components).enumerate() {
+                let transform: AtomicRef<'_, Transform> = transform.borrow();
+                let mut collision: AtomicRefMut<'_, Collision> = collision.borrow_mut();
+
+                if let Some(global_transforms) = global_transforms {
+                    let global_transform: AtomicRef<'_, GlobalTransform> = global_transforms.components[row].borrow();
+                    collision.collider.position = global_transform.get_position();
+                    collision.collider.scale = global_transform.get_scale();
+                } else {
                     collision.collider.position = transform.position.to_vec();
                     collision.collider.scale = transform.scale;
                 }
@@ -44,13 +44,11 @@ impl GameLoop {
         self.previous_time_of_last_run = now;
 
         context.delta = self.get_delta_as_seconds();
+        context.render_state.elapsed_time += context.delta;
+        context.world.increment_change_tick();
         context.commands.flush_commands(&mut context.world, &mut context.render_state);
-        context.world.synchronize(&mut context.render_state, context.delta);
 
-        if context.gamepad_listener.enabled {
-            context.gamepad_listener.manage(&mut context.world);
-        }
-        (self.update)(context);
+        context.run_schedule();
 
         self.render(&mut context.render_state, &mut context.world, event_loop);
         self.calculate_current_fps(context);
@@ -0,0 +1,80 @@
+use std::collections::HashSet;
+use cgmath::{Matrix4, SquareMatrix};
+use lotus_proc_macros::Component;
+use super::{ecs::{entity::Entity, world::World, component::ComponentRefMut, filter::{With, Without}}, physics::transform::{Transform, GlobalTransform}};
+
+/// Struct to represent the parent of an entity in the hierarchy.
+///
+/// The transform of an entity with a parent is relative to the global transform of its parent.
+#[derive(Clone, Debug, Component)]
+pub struct Parent(pub Entity);
+
+impl Parent {
+    /// Returns the parent entity.
+    pub fn get(&self) -> Entity {
+        return self.0;
+    }
+}
+
+/// Struct to represent the children of an entity in the hierarchy.
+///
+/// Despawning an entity despawns all of its children recursively.
+#[derive(Clone, Debug, Default, Component)]
+pub struct Children(pub Vec<Entity>);
+
+impl Children {
+    /// Returns the children entities.
+    pub fn get(&self) -> &[Entity] {
+        return &self.0;
+    }
+
+    /// Returns if a specific entity is one of the children.
+    pub fn contains(&self, entity: &Entity) -> bool {
+        return self.0.contains(entity);
+    }
+}
+
+/// Propagates the transforms from the root entities down to their children.
+///
+/// The global transform of a root entity is its own transform, and the global transform of a child
+/// is the global transform of its parent multiplied by its own transform.
+///
+/// Every entity is propagated once, so a hierarchy with a cycle on its children stops at the repeated entity.
+pub(crate) fn propagate_transforms(world: &World) {
+    let roots: Vec<Entity> = world.query_filtered::<Entity, (With<GlobalTransform>, Without<Parent>)>().collect();
+    let mut visited: HashSet<Entity> = HashSet::new();
+
+    for root in roots {
+        propagate_transform(world, &root, Matrix4::identity(), &mut visited);
+    }
+}
+
+/// Propagates the transform of an entity with the matrix of its parent, and then to its children.
+fn propagate_transform(world: &World, entity: &Entity, parent_matrix: Matrix4<f32>, visited: &mut HashSet<Entity>) {
+    if !visited.insert(*entity) {
+        log::warn!("Entity {:?} was reached twice on the hierarchy, its children form a cycle.", entity);
+        return;
+    }
+
+    let Some(local_matrix) = world.get_entity_component::<Transform>(entity).map(|transform| transform.to_matrix()) else {
+        return;
+    };
+    let matrix: Matrix4<f32> = parent_matrix * local_matrix;
+
+    if let Some(global_transform) = world.get_entity_component::<GlobalTransform>(entity) {
+        let is_outdated: bool = global_transform.matrix != matrix || global_transform.parent_matrix != parent_matrix;
+        drop(global_transform);
+
+        if is_outdated {
+            let mut global_transform: ComponentRefMut<'_, GlobalTransform> = world.get_entity_component_mut::<GlobalTransform>(entity).unwrap();
+            global_transform.matrix = matrix;
+            global_transform.parent_matrix = parent_matrix;
+        }
+    }
+
+    let children: Vec<Entity> = world.get_entity_component::<Children>(entity).map(|children| children.0.clone()).unwrap_or_default();
+
+    for child in &children {
+        propagate_transform(world, child, matrix, visited);
+    }
+}
@@ -0,0 +1,262 @@
+use cgmath::Vector2;
+use lotus_proc_macros::{Component, Resource};
+use super::super::{
+    color::color::Color,
+    physics::collision::Collider,
+    shape::{geometry_type::GeometryType, orientation::Orientation},
+    super::ColorOption
+};
+use crate::utils::constants::lighting::{MAX_POINT_LIGHTS, MAX_OCCLUDER_EDGES, OCCLUDER_CIRCLE_SEGMENTS};
+
+/// Struct to represent the ambient light resource, the light that reaches every pixel of the frame.
+///
+/// The lighting pass is active while the world has this resource or any active point light.
+#[derive(Clone, Debug, Resource)]
+pub struct AmbientLight {
+    pub color: Color,
+    pub intensity: f32
+}
+
+impl Default for AmbientLight {
+    fn default() -> Self {
+        return Self {
+            color: Color::by_option(ColorOption::White),
+            intensity: 0.2
+        };
+    }
+}
+
+impl AmbientLight {
+    /// Create a new ambient light with parameters.
+    pub fn new(color: Color, intensity: f32) -> Self {
+        return Self {
+            color,
+            intensity
+        };
+    }
+}
+
+/// Struct to represent a point light component, lighting the pixels around the position of its entity transform.
+///
+/// The radius and the height are in world units, the height being how far the light is above the scene.
+/// A higher light reaches the pixels around it more evenly, while a lower one makes the normal maps stand out.
+///
+/// The lights are placed on the screen through the global camera, even while rendering through camera entities.
+#[derive(Clone, Debug, Component)]
+pub struct PointLight2d {
+    pub color: Color,
+    pub intensity: f32,
+    pub radius: f32,
+    pub falloff: f32,
+    pub height: f32,
+    pub cast_shadows: bool,
+    pub active: bool
+}
+
+impl Default for PointLight2d {
+    fn default() -> Self {
+        return Self {
+            color: Color::by_option(ColorOption::White),
+            intensity: 1.0,
+            radius: 0.5,
+            falloff: 1.0,
+            height: 0.1,
+            cast_shadows: false,
+            active: true
+        };
+    }
+}
+
+impl PointLight2d {
+    /// Create a new white point light.
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Set the color on initialization.
+    pub fn color(self, color: Color) -> Self {
+        return Self {
+            color,
+            ..self
+        };
+    }
+
+    /// Set the intensity on initialization.
+    pub fn intensity(self, intensity: f32) -> Self {
+        return Self {
+            intensity,
+            ..self
+        };
+    }
+
+    /// Set the radius on initialization.
+    pub fn radius(self, radius: f32) -> Self {
+        return Self {
+            radius,
+            ..self
+        };
+    }
+
+    /// Set the falloff exponent on initialization, where 1.0 is a linear falloff until the radius.
+    pub fn falloff(self, falloff: f32) -> Self {
+        return Self {
+            falloff,
+            ..self
+        };
+    }
+
+    /// Set the height on initialization.
+    pub fn height(self, height: f32) -> Self {
+        return Self {
+            height,
+            ..self
+        };
+    }
+
+    /// Set if the light casts hard shadows from the occluders on initialization.
+    pub fn cast_shadows(self, cast_shadows: bool) -> Self {
+        return Self {
+            cast_shadows,
+            ..self
+        };
+    }
+
+    /// Set if the light is active on initialization.
+    pub fn active(self, active: bool) -> Self {
+        return Self {
+            active,
+            ..self
+        };
+    }
+}
+
+/// Struct to represent an occluder component, blocking the light of the point lights that cast shadows.
+///
+/// The shape of the occluder is the collider of the entity, so it needs a collision component as well.
+/// The rotation of the entity is ignored, as it is for the collisions.
+#[derive(Clone, Debug, Component)]
+pub struct Occluder {
+    pub active: bool
+}
+
+impl Default for Occluder {
+    fn default() -> Self {
+        return Self {
+            active: true
+        };
+    }
+}
+
+impl Occluder {
+    /// Create a new active occluder.
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Set if the occluder is active on initialization.
+    pub fn active(self, active: bool) -> Self {
+        return Self {
+            active
+        };
+    }
+
+    /// Returns the edges of the outline of a collider, in world units.
+    ///
+    /// Circles are simplified to a fixed number of segments.
+    pub fn edges(collider: &Collider) -> Vec<(Vector2<f32>, Vector2<f32>)> {
+        let outline: Vec<Vector2<f32>> = match &collider.geometry_type {
+            GeometryType::Circle(circle) => (0..OCCLUDER_CIRCLE_SEGMENTS)
+                .map(|index| {
+                    let theta: f32 = 2.0 * std::f32::consts::PI * (index as f32) / (OCCLUDER_CIRCLE_SEGMENTS as f32);
+                    Vector2::new(circle.radius * theta.cos(), circle.radius * theta.sin())
+                })
+                .collect(),
+            geometry_type => geometry_type.to_vertex_array(Orientation::Horizontal, ColorOption::White.to_rgba()).iter()
+                .map(|vertex| Vector2::new(vertex.position[0], vertex.position[1]))
+                .collect()
+        };
+        let points: Vec<Vector2<f32>> = outline.iter()
+            .map(|point| collider.position + Vector2::new(point.x * collider.scale.x, point.y * collider.scale.y))
+            .collect();
+
+        return (0..points.len())
+            .map(|index| (points[index], points[(index + 1) % points.len()]))
+            .collect();
+    }
+}
+
+/// Struct to represent a point light on the screen that will be sent to the lighting shader.
+///
+/// The position, radius and height are in pixels and the color is already multiplied by the intensity.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PointLightUniform {
+    pub position: [f32; 2],
+    pub radius: f32,
+    pub falloff: f32,
+    pub color: [f32; 4],
+    pub height: f32,
+    pub cast_shadows: u32,
+    pub _padding: [f32; 2]
+}
+
+/// Struct to represent the lights and the occluder edges of the frame that will be sent to the lighting shader.
+///
+/// The lights and edges beyond the maximum amounts supported by the shader are ignored.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LightingUniforms {
+    pub ambient: [f32; 4],
+    pub resolution: [f32; 2],
+    pub light_count: u32,
+    pub edge_count: u32,
+    pub lights: [PointLightUniform; MAX_POINT_LIGHTS],
+    pub edges: [[f32; 4]; MAX_OCCLUDER_EDGES]
+}
+
+impl LightingUniforms {
+    /// Create new lighting uniforms without lights and edges.
+    pub fn new(ambient_light: &AmbientLight, resolution: [f32; 2]) -> Self {
+        let color: [f32; 4] = ambient_light.color.to_array();
+
+        return Self {
+            ambient: [color[0] * ambient_light.intensity, color[1] * ambient_light.intensity, color[2] * ambient_light.intensity, 1.0],
+            resolution,
+            ..bytemuck::Zeroable::zeroed()
+        };
+    }
+
+    /// Add a point light on its screen position, with its radius and height in pixels.
+    ///
+    /// Returns if the light was added.
+    pub fn push_light(&mut self, point_light: &PointLight2d, position: [f32; 2], radius: f32, height: f32) -> bool {
+        if self.light_count as usize >= MAX_POINT_LIGHTS {
+            return false;
+        }
+        let color: [f32; 4] = point_light.color.to_array();
+
+        self.lights[self.light_count as usize] = PointLightUniform {
+            position,
+            radius,
+            falloff: point_light.falloff,
+            color: [color[0] * point_light.intensity, color[1] * point_light.intensity, color[2] * point_light.intensity, 1.0],
+            height,
+            cast_shadows: point_light.cast_shadows as u32,
+            _padding: [0.0; 2]
+        };
+        self.light_count += 1;
+        return true;
+    }
+
+    /// Add an occluder edge on its screen positions.
+    ///
+    /// Returns if the edge was added.
+    pub fn push_edge(&mut self, start: [f32; 2], end: [f32; 2]) -> bool {
+        if self.edge_count as usize >= MAX_OCCLUDER_EDGES {
+            return false;
+        }
+        self.edges[self.edge_count as usize] = [start[0], start[1], end[0], end[1]];
+        self.edge_count += 1;
+        return true;
+    }
+}
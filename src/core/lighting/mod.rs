@@ -0,0 +1,2 @@
+/// Point lights, ambient light and occluders related features.
+pub mod light;
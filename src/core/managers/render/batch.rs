@@ -0,0 +1,181 @@
+use std::ops::Range;
+use wgpu::*;
+use super::{manager::Vertex, super::super::{
+    super::ColorOption,
+    ecs::entity::Entity,
+    shape::{geometry_type::GeometryType, orientation::Orientation}
+}};
+
+/// Struct to represent the per instance data of a batch that will be sent to the shader.
+///
+/// The UV rectangle is the offset and size of the area of the texture to be sampled.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct BatchInstance {
+    pub transform: [[f32; 4]; 4],
+    pub uv_rect: [f32; 4],
+    pub tint: [f32; 4],
+    pub rendering_type: u32
+}
+
+impl BatchInstance {
+    const INSTANCE_ATTRIBUTES: [VertexAttribute; 7] = vertex_attr_array![
+        3 => Float32x4,
+        4 => Float32x4,
+        5 => Float32x4,
+        6 => Float32x4,
+        7 => Float32x4,
+        8 => Float32x4,
+        9 => Uint32
+    ];
+
+    /// Create a new batch instance.
+    pub fn new(transform: [[f32; 4]; 4], uv_rect: [f32; 4], tint: [f32; 4], rendering_type: u32) -> Self {
+        return Self {
+            transform,
+            uv_rect,
+            tint,
+            rendering_type
+        };
+    }
+
+    pub(crate) fn descriptor() -> VertexBufferLayout<'static> {
+        return VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as BufferAddress,
+            step_mode: VertexStepMode::Instance,
+            attributes: &Self::INSTANCE_ATTRIBUTES
+        };
+    }
+}
+
+/// Enumerator to represent the geometry shared by every instance of a batch.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BatchGeometry {
+    Quad,
+    Shape(GeometryType)
+}
+
+impl BatchGeometry {
+    /// Returns the key of the geometry, used for caching its buffers.
+    pub fn key(&self) -> String {
+        return match self {
+            BatchGeometry::Quad => "quad".to_string(),
+            BatchGeometry::Shape(geometry_type) => format!("{:?}", geometry_type)
+        };
+    }
+
+    /// Returns the vertices of the geometry, colored in white to be tinted by each instance.
+    pub fn to_vertex_array(&self) -> Vec<Vertex> {
+        return self.geometry_type().to_vertex_array(Orientation::Horizontal, ColorOption::White.to_rgba());
+    }
+
+    /// Returns the indices of the geometry.
+    pub fn to_index_array(&self) -> Vec<u16> {
+        return self.geometry_type().to_index_array();
+    }
+
+    fn geometry_type(&self) -> &GeometryType {
+        return match self {
+            BatchGeometry::Quad => &GeometryType::Square,
+            BatchGeometry::Shape(geometry_type) => geometry_type
+        };
+    }
+}
+
+/// Struct to represent the material of the entity of a batch, by its shader and extra textures.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchMaterial {
+    pub entity: Entity,
+    pub shader: String,
+    pub textures: Vec<String>
+}
+
+/// Struct to represent a range of instances sharing the same texture and geometry, drawn at once.
+///
+/// A batch with a material has a single instance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Batch {
+    pub texture: String,
+    pub geometry: BatchGeometry,
+    pub instances: Range<u32>,
+    pub material: Option<BatchMaterial>
+}
+
+/// Enumerator to represent a draw call of a frame.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BatchDraw {
+    Instanced(Batch),
+    Text(Entity)
+}
+
+/// Struct to represent the batches of a frame, built in drawing order.
+///
+/// Consecutive instances with the same texture and geometry are merged into the same batch.
+/// A text breaks the current batch, as it is drawn by its own brush.
+#[derive(Clone, Debug, Default)]
+pub struct BatchBuilder {
+    pub instances: Vec<BatchInstance>,
+    pub draws: Vec<BatchDraw>
+}
+
+impl BatchBuilder {
+    /// Create a new empty batch builder.
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Add an instance, merging it on the last batch if it shares its texture and geometry.
+    pub fn push(&mut self, texture: &str, geometry: BatchGeometry, instance: BatchInstance) {
+        let index: u32 = self.instances.len() as u32;
+        self.instances.push(instance);
+
+        if let Some(BatchDraw::Instanced(batch)) = self.draws.last_mut() {
+            if batch.material.is_none() && batch.texture == texture && batch.geometry == geometry {
+                batch.instances.end = index + 1;
+                return;
+            }
+        }
+        self.draws.push(BatchDraw::Instanced(Batch {
+            texture: texture.to_string(),
+            geometry,
+            instances: index..index + 1,
+            material: None
+        }));
+    }
+
+    /// Add an instance drawn with a material, on a batch of its own.
+    pub fn push_material(&mut self, texture: &str, geometry: BatchGeometry, material: BatchMaterial, instance: BatchInstance) {
+        let index: u32 = self.instances.len() as u32;
+        self.instances.push(instance);
+
+        self.draws.push(BatchDraw::Instanced(Batch {
+            texture: texture.to_string(),
+            geometry,
+            instances: index..index + 1,
+            material: Some(material)
+        }));
+    }
+
+    /// Add an instance out of the drawing order, returning its own batch to be drawn separately.
+    pub fn push_detached(&mut self, texture: &str, geometry: BatchGeometry, instance: BatchInstance) -> Batch {
+        let index: u32 = self.instances.len() as u32;
+        self.instances.push(instance);
+
+        return Batch {
+            texture: texture.to_string(),
+            geometry,
+            instances: index..index + 1,
+            material: None
+        };
+    }
+
+    /// Add a text to be drawn after the current batches.
+    pub fn push_text(&mut self, entity: Entity) {
+        self.draws.push(BatchDraw::Text(entity));
+    }
+
+    /// Returns the number of instanced draw calls.
+    pub fn batches_count(&self) -> usize {
+        return self.draws.iter().filter(|draw| matches!(draw, BatchDraw::Instanced(_))).count();
+    }
+}
@@ -1,17 +1,31 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
+use cgmath::Matrix4;
 use wgpu::{Buffer, BindGroup, BindingResource, BindGroupDescriptor, BindGroupEntry};
+use super::buffer;
 use super::{
     utils,
     super::super::{
         render::manager::RenderState,
-        super::{texture::{texture::Texture, sprite_sheet::SpriteSheet}, ecs::entity::Entity}
+        super::{texture::{texture::Texture, sprite_sheet::SpriteSheet}, camera::camera2d::Camera2d, ecs::entity::Entity}
     }
 };
-use crate::utils::constants::cache::{RENDERING_TYPE_BIND_GROUP, TEXTURE_BIND_GROUP, TRANSFORM_BIND_GROUP};
+use crate::utils::constants::cache::{
+    FIXED_ID,
+    PROJECTION,
+    VIEW,
+    RENDERING_TYPE_BIND_GROUP,
+    TEXTURE_BIND_GROUP,
+    TRANSFORM_BIND_GROUP,
+    BATCH_TEXTURE_BIND_GROUP,
+    CAMERA_BIND_GROUP,
+    MATERIAL_BIND_GROUP,
+    POST_PROCESS_BIND_GROUP,
+    LIGHTING_BIND_GROUP
+};
 
 /// Struct for caching Bind Groups.
 pub struct BindGroupCache {
-    pub cache: HashMap<(String, String), BindGroup>
+    pub cache: HashMap<(u64, String), BindGroup>
 }
 
 impl BindGroupCache {
@@ -23,19 +37,19 @@ impl BindGroupCache {
     }
 
     /// Find the cached Bind Group by the key.
-    pub fn find(&self, key: (String, String)) -> Option<BindGroup> {
+    pub fn find(&self, key: (u64, String)) -> Option<BindGroup> {
         return self.cache.get(&key).cloned();
     }
 
     /// Clean the cached Bind Group data related to a certain entity if its found.
-    pub fn clean(&mut self, entity_id: String) {
+    pub fn clean(&mut self, entity_id: u64) {
         self.cache.retain(|(entity_id_from_map, _), _| entity_id_from_map != &entity_id);
     }
 }
 
 pub(crate) fn get_rendering_type_bind_group(render_state: &mut RenderState, entity: Option<&Entity>, rendering_type_buffer: Buffer) -> BindGroup {
-    let uuid: String = utils::extract_id_from_entity(entity);
-    let key: (String, String) = (uuid, RENDERING_TYPE_BIND_GROUP.to_string());
+    let id: u64 = utils::extract_id_from_entity(entity);
+    let key: (u64, String) = (id, RENDERING_TYPE_BIND_GROUP.to_string());
 
     if let Some(rendering_type_bind_group) = render_state.bind_group_cache.find(key.clone()) {
         return rendering_type_bind_group.clone();
@@ -61,9 +75,9 @@ pub(crate) fn get_texture_bind_group(
     texture: &Texture,
     sprite_sheet: Option<&SpriteSheet>
 ) -> BindGroup {
-    let uuid: String = utils::extract_id_from_entity(entity);
-    let key: (String, String) = (
-        uuid,
+    let id: u64 = utils::extract_id_from_entity(entity);
+    let key: (u64, String) = (
+        id,
         if sprite_sheet.is_none() { TEXTURE_BIND_GROUP.to_string() } else { sprite_sheet.unwrap().path.to_string() }
     );
 
@@ -96,8 +110,8 @@ pub(crate) fn get_transform_bind_group(
     projection_buffer: Buffer,
     view_buffer: Buffer
 ) -> BindGroup {
-    let uuid: String = utils::extract_id_from_entity(entity);
-    let key: (String, String) = (uuid, TRANSFORM_BIND_GROUP.to_string());
+    let id: u64 = utils::extract_id_from_entity(entity);
+    let key: (u64, String) = (id, TRANSFORM_BIND_GROUP.to_string());
 
     if let Some(transform_bind_group) = render_state.bind_group_cache.find(key.clone()) {
         return transform_bind_group.clone();
@@ -124,3 +138,170 @@ pub(crate) fn get_transform_bind_group(
         return transform_bind_group;
     }
 }
+
+pub(crate) fn get_batch_texture_bind_group(render_state: &mut RenderState, texture_key: &str, texture: &Texture) -> BindGroup {
+    let key: (u64, String) = (FIXED_ID, format!("{}_{}", BATCH_TEXTURE_BIND_GROUP, texture_key));
+
+    if let Some(texture_bind_group) = render_state.bind_group_cache.find(key.clone()) {
+        return texture_bind_group;
+    } else {
+        let texture_bind_group: BindGroup = render_state.device.as_ref().unwrap().create_bind_group(&BindGroupDescriptor {
+            label: Some("Batch Texture Bind Group"),
+            layout: render_state.texture_bind_group_layout.as_ref().unwrap(),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&texture.texture_view)
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&texture.sampler)
+                }
+            ]
+        });
+        render_state.bind_group_cache.cache.insert(key, texture_bind_group.clone());
+        return texture_bind_group;
+    }
+}
+
+pub(crate) fn get_camera_bind_group(render_state: &mut RenderState, camera2d: &Camera2d) -> BindGroup {
+    let projection: Matrix4<f32> = render_state.get_projection_matrix(camera2d);
+    return get_camera_entity_bind_group(render_state, None, projection, camera2d.view_matrix);
+}
+
+pub(crate) fn get_camera_entity_bind_group(
+    render_state: &mut RenderState,
+    entity: Option<&Entity>,
+    projection: Matrix4<f32>,
+    view: Matrix4<f32>
+) -> BindGroup {
+    let key: (u64, String) = (utils::extract_id_from_entity(entity), CAMERA_BIND_GROUP.to_string());
+    let projection_buffer: Buffer = buffer::get_camera_buffer(render_state, PROJECTION, entity, projection);
+    let view_buffer: Buffer = buffer::get_camera_buffer(render_state, VIEW, entity, view);
+
+    if let Some(camera_bind_group) = render_state.bind_group_cache.find(key.clone()) {
+        return camera_bind_group;
+    } else {
+        let camera_bind_group: BindGroup = render_state.device.as_ref().unwrap().create_bind_group(&BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: render_state.camera_bind_group_layout.as_ref().unwrap(),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: projection_buffer.as_entire_binding()
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: view_buffer.as_entire_binding()
+                }
+            ]
+        });
+        render_state.bind_group_cache.cache.insert(key, camera_bind_group.clone());
+        return camera_bind_group;
+    }
+}
+
+pub(crate) fn material_bind_group_key(entity: &Entity, textures: &[String]) -> (u64, String) {
+    return (entity.to_bits(), format!("{}_{}", MATERIAL_BIND_GROUP, textures.join(";")));
+}
+
+pub(crate) fn get_material_bind_group(
+    render_state: &mut RenderState,
+    entity: &Entity,
+    texture_keys: &[String],
+    material_buffer: Buffer,
+    textures: &[Arc<Texture>]
+) -> BindGroup {
+    let key: (u64, String) = material_bind_group_key(entity, texture_keys);
+
+    if let Some(material_bind_group) = render_state.bind_group_cache.find(key.clone()) {
+        return material_bind_group;
+    } else {
+        let mut entries: Vec<BindGroupEntry> = vec![
+            BindGroupEntry {
+                binding: 0,
+                resource: material_buffer.as_entire_binding()
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: BindingResource::Sampler(&textures[0].sampler)
+            }
+        ];
+        entries.extend(textures.iter().enumerate().map(|(index, texture)| BindGroupEntry {
+            binding: 2 + index as u32,
+            resource: BindingResource::TextureView(&texture.texture_view)
+        }));
+
+        let material_bind_group: BindGroup = render_state.device.as_ref().unwrap().create_bind_group(&BindGroupDescriptor {
+            label: Some("Material Bind Group"),
+            layout: render_state.material_bind_group_layout.as_ref().unwrap(),
+            entries: &entries
+        });
+        render_state.bind_group_cache.cache.insert(key, material_bind_group.clone());
+        return material_bind_group;
+    }
+}
+
+pub(crate) fn get_post_process_bind_group(
+    render_state: &mut RenderState,
+    index: usize,
+    texture_key: &str,
+    post_process_buffer: Buffer,
+    texture: &Texture
+) -> BindGroup {
+    let key: (u64, String) = (FIXED_ID, format!("{}_{}_{}", POST_PROCESS_BIND_GROUP, index, texture_key));
+
+    if let Some(post_process_bind_group) = render_state.bind_group_cache.find(key.clone()) {
+        return post_process_bind_group;
+    } else {
+        let post_process_bind_group: BindGroup = render_state.device.as_ref().unwrap().create_bind_group(&BindGroupDescriptor {
+            label: Some("Post Process Bind Group"),
+            layout: render_state.post_process_bind_group_layout.as_ref().unwrap(),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: post_process_buffer.as_entire_binding()
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&texture.texture_view)
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&texture.sampler)
+                }
+            ]
+        });
+        render_state.bind_group_cache.cache.insert(key, post_process_bind_group.clone());
+        return post_process_bind_group;
+    }
+}
+
+pub(crate) fn get_lighting_bind_group(render_state: &mut RenderState, lighting_buffer: Buffer, normals: &Texture) -> BindGroup {
+    let key: (u64, String) = (FIXED_ID, LIGHTING_BIND_GROUP.to_string());
+
+    if let Some(lighting_bind_group) = render_state.bind_group_cache.find(key.clone()) {
+        return lighting_bind_group;
+    } else {
+        let lighting_bind_group: BindGroup = render_state.device.as_ref().unwrap().create_bind_group(&BindGroupDescriptor {
+            label: Some("Lighting Bind Group"),
+            layout: render_state.post_process_bind_group_layout.as_ref().unwrap(),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: lighting_buffer.as_entire_binding()
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&normals.texture_view)
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&normals.sampler)
+                }
+            ]
+        });
+        render_state.bind_group_cache.cache.insert(key, lighting_bind_group.clone());
+        return lighting_bind_group;
+    }
+}
@@ -4,15 +4,15 @@ use wgpu::{Buffer, BufferUsages, util::{BufferInitDescriptor, DeviceExt}};
 use super::{
     utils,
     super::super::{
-        render::manager::{RenderState, Vertex},
-        super::{camera::camera2d::Camera2d, ecs::entity::Entity}
+        render::{manager::{RenderState, Vertex}, batch::BatchGeometry},
+        super::{camera::camera2d::Camera2d, material::material::MaterialUniforms, post_process::post_process::PostProcessUniforms, lighting::light::LightingUniforms, ecs::entity::Entity}
     }
 };
-use crate::utils::constants::cache::{VERTEX, INDEX, PROJECTION, VIEW, TRANSFORM_BUFFER};
+use crate::utils::constants::cache::{FIXED_ID, VERTEX, INDEX, PROJECTION, VIEW, TRANSFORM_BUFFER, BATCH_VERTEX, BATCH_INDEX, MATERIAL_BUFFER, POST_PROCESS_BUFFER, LIGHTING_BUFFER};
 
 /// Struct for caching Buffers.
 pub struct BufferCache {
-    pub cache: HashMap<(String, String), Buffer>
+    pub cache: HashMap<(u64, String), Buffer>
 }
 
 impl BufferCache {
@@ -24,19 +24,19 @@ impl BufferCache {
     }
 
     /// Find the cached Buffer by the key.
-    pub fn find(&self, key: (String, String)) -> Option<Buffer> {
+    pub fn find(&self, key: (u64, String)) -> Option<Buffer> {
         return self.cache.get(&key).cloned();
     }
 
     /// Clean the cached Buffer data related to a certain entity if its found.
-    pub fn clean(&mut self, entity_id: String) {
+    pub fn clean(&mut self, entity_id: u64) {
         self.cache.retain(|(entity_id_from_map, _), _| entity_id_from_map != &entity_id);
     }
 }
 
 pub(crate) fn get_conditional_buffer(render_state: &mut RenderState, title: &str, entity: Option<&Entity>, value: u32) -> Buffer {
-    let uuid: String = utils::extract_id_from_entity(entity);
-    let key: (String, String) = (uuid, title.to_string().clone());
+    let id: u64 = utils::extract_id_from_entity(entity);
+    let key: (u64, String) = (id, title.to_string().clone());
 
     if let Some(buffer) = render_state.buffer_cache.find(key.clone()) {
         render_state.queue.as_ref().unwrap().write_buffer(
@@ -57,9 +57,9 @@ pub(crate) fn get_conditional_buffer(render_state: &mut RenderState, title: &str
 }
 
 pub(crate) fn get_vertex_and_index_buffers(render_state: &mut RenderState, entity: Option<&Entity>, vertex_array: &[Vertex], index_array: &[u16]) -> (Buffer, Buffer) {
-    let uuid: String = utils::extract_id_from_entity(entity);
-    let vertex_key: (String, String) = (uuid.clone(), VERTEX.to_string());
-    let index_key: (String, String) = (uuid.clone(), INDEX.to_string());
+    let id: u64 = utils::extract_id_from_entity(entity);
+    let vertex_key: (u64, String) = (id, VERTEX.to_string());
+    let index_key: (u64, String) = (id, INDEX.to_string());
 
     if let (Some(vertex_buffer), Some(index_buffer)) = (
         render_state.buffer_cache.find(vertex_key.clone()),
@@ -88,9 +88,36 @@ pub(crate) fn get_vertex_and_index_buffers(render_state: &mut RenderState, entit
     }
 }
 
+pub(crate) fn get_batch_vertex_and_index_buffers(render_state: &mut RenderState, geometry: &BatchGeometry) -> (Buffer, Buffer, u32) {
+    let index_array: Vec<u16> = geometry.to_index_array();
+    let vertex_key: (u64, String) = (FIXED_ID, format!("{}_{}", BATCH_VERTEX, geometry.key()));
+    let index_key: (u64, String) = (FIXED_ID, format!("{}_{}", BATCH_INDEX, geometry.key()));
+
+    if let (Some(vertex_buffer), Some(index_buffer)) = (
+        render_state.buffer_cache.find(vertex_key.clone()),
+        render_state.buffer_cache.find(index_key.clone())
+    ) {
+        return (vertex_buffer, index_buffer, index_array.len() as u32);
+    } else {
+        let vertex_buffer: Buffer = render_state.device.as_ref().unwrap().create_buffer_init(&BufferInitDescriptor {
+            label: Some("Batch Vertex Buffer"),
+            contents: bytemuck::cast_slice(&geometry.to_vertex_array()),
+            usage: BufferUsages::VERTEX
+        });
+        let index_buffer: Buffer = render_state.device.as_ref().unwrap().create_buffer_init(&BufferInitDescriptor {
+            label: Some("Batch Index Buffer"),
+            contents: bytemuck::cast_slice(&index_array),
+            usage: BufferUsages::INDEX
+        });
+        render_state.buffer_cache.cache.insert(vertex_key, vertex_buffer.clone());
+        render_state.buffer_cache.cache.insert(index_key, index_buffer.clone());
+        return (vertex_buffer, index_buffer, index_array.len() as u32);
+    }
+}
+
 pub(crate) fn get_transform_buffer(render_state: &mut RenderState, entity: Option<&Entity>, transform_matrix_unwrapped: [[f32; 4]; 4]) -> Buffer {
-    let uuid: String = utils::extract_id_from_entity(entity);
-    let key: (String, String) = (uuid, TRANSFORM_BUFFER.to_string());
+    let id: u64 = utils::extract_id_from_entity(entity);
+    let key: (u64, String) = (id, TRANSFORM_BUFFER.to_string());
 
     if let Some(transform_buffer) = render_state.buffer_cache.find(key.clone()) {
         render_state.queue.as_ref().unwrap().write_buffer(
@@ -112,9 +139,13 @@ pub(crate) fn get_transform_buffer(render_state: &mut RenderState, entity: Optio
 
 pub(crate) fn get_projection_or_view_buffer(render_state: &mut RenderState, is_projection: bool, entity: Option<&Entity>, camera2d: &Camera2d) -> Buffer {
     let title: &str = if is_projection { PROJECTION } else { VIEW };
-    let uuid: String = utils::extract_id_from_entity(entity);
-    let key: (String, String) = (uuid, title.to_string().clone());
     let matrix: Matrix4<f32> = if is_projection { render_state.get_projection_matrix(camera2d) } else { camera2d.view_matrix };
+    return get_camera_buffer(render_state, title, entity, matrix);
+}
+
+pub(crate) fn get_camera_buffer(render_state: &mut RenderState, title: &str, entity: Option<&Entity>, matrix: Matrix4<f32>) -> Buffer {
+    let id: u64 = utils::extract_id_from_entity(entity);
+    let key: (u64, String) = (id, title.to_string().clone());
     let matrix_unwrapped: [[f32; 4]; 4] = *matrix.as_ref();
 
     if let Some(buffer) = render_state.buffer_cache.find(key.clone()) {
@@ -134,3 +165,66 @@ pub(crate) fn get_projection_or_view_buffer(render_state: &mut RenderState, is_p
         return buffer;
     }
 }
+
+pub(crate) fn get_material_buffer(render_state: &mut RenderState, entity: &Entity, material_uniforms: MaterialUniforms) -> Buffer {
+    let key: (u64, String) = (entity.to_bits(), MATERIAL_BUFFER.to_string());
+
+    if let Some(material_buffer) = render_state.buffer_cache.find(key.clone()) {
+        render_state.queue.as_ref().unwrap().write_buffer(
+            &material_buffer,
+            0,
+            bytemuck::cast_slice(&[material_uniforms])
+        );
+        return material_buffer;
+    } else {
+        let material_buffer: Buffer = render_state.device.as_ref().unwrap().create_buffer_init(&BufferInitDescriptor {
+            label: Some("Material Buffer"),
+            contents: bytemuck::cast_slice(&[material_uniforms]),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST
+        });
+        render_state.buffer_cache.cache.insert(key, material_buffer.clone());
+        return material_buffer;
+    }
+}
+
+pub(crate) fn get_post_process_buffer(render_state: &mut RenderState, index: usize, post_process_uniforms: PostProcessUniforms) -> Buffer {
+    let key: (u64, String) = (FIXED_ID, format!("{}_{}", POST_PROCESS_BUFFER, index));
+
+    if let Some(post_process_buffer) = render_state.buffer_cache.find(key.clone()) {
+        render_state.queue.as_ref().unwrap().write_buffer(
+            &post_process_buffer,
+            0,
+            bytemuck::cast_slice(&[post_process_uniforms])
+        );
+        return post_process_buffer;
+    } else {
+        let post_process_buffer: Buffer = render_state.device.as_ref().unwrap().create_buffer_init(&BufferInitDescriptor {
+            label: Some("Post Process Buffer"),
+            contents: bytemuck::cast_slice(&[post_process_uniforms]),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST
+        });
+        render_state.buffer_cache.cache.insert(key, post_process_buffer.clone());
+        return post_process_buffer;
+    }
+}
+
+pub(crate) fn get_lighting_buffer(render_state: &mut RenderState, lighting_uniforms: LightingUniforms) -> Buffer {
+    let key: (u64, String) = (FIXED_ID, LIGHTING_BUFFER.to_string());
+
+    if let Some(lighting_buffer) = render_state.buffer_cache.find(key.clone()) {
+        render_state.queue.as_ref().unwrap().write_buffer(
+            &lighting_buffer,
+            0,
+            bytemuck::cast_slice(&[lighting_uniforms])
+        );
+        return lighting_buffer;
+    } else {
+        let lighting_buffer: Buffer = render_state.device.as_ref().unwrap().create_buffer_init(&BufferInitDescriptor {
+            label: Some("Lighting Buffer"),
+            contents: bytemuck::cast_slice(&[lighting_uniforms]),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST
+        });
+        render_state.buffer_cache.cache.insert(key, lighting_buffer.clone());
+        return lighting_buffer;
+    }
+}
@@ -1,6 +1,6 @@
 use super::super::super::super::{ecs::entity::Entity};
-use crate::utils::constants::cache::FIXED_UUID;
+use crate::utils::constants::cache::FIXED_ID;
 
-pub(crate) fn extract_id_from_entity(entity: Option<&Entity>) -> String {
-    return entity.map(|e| e.0.to_string()).unwrap_or_else(|| FIXED_UUID.to_string());
+pub(crate) fn extract_id_from_entity(entity: Option<&Entity>) -> u64 {
+    return entity.map(|e| e.to_bits()).unwrap_or(FIXED_ID);
 }
@@ -0,0 +1,199 @@
+use std::{collections::VecDeque, path::{Path, PathBuf}, sync::mpsc, thread::JoinHandle};
+use image::{ImageFormat, RgbaImage};
+use wgpu::{Buffer, BufferAsyncError, Device, MapMode, PollType, Queue, Texture, TextureFormat};
+use super::headless;
+use crate::utils::constants::capture::MAX_QUEUED_CAPTURES;
+
+/// Struct to represent a recorder of frame sequences, saving the rendered frames as numbered PNG files on a directory.
+///
+/// The sequence can be turned into a GIF or a video by external tools, like *ffmpeg -i frame_%05d.png gameplay.gif*.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameRecorder {
+    pub directory: PathBuf,
+    pub frame_interval: u32,
+    pub max_frames: Option<u32>,
+    pub frames_recorded: u32,
+    pub frames_rendered: u32
+}
+
+impl FrameRecorder {
+    /// Create a new recorder of every frame, with the directory of the sequence relative to the current directory.
+    pub fn new(directory: String) -> Self {
+        return Self {
+            directory: PathBuf::from(directory),
+            frame_interval: 1,
+            max_frames: None,
+            frames_recorded: 0,
+            frames_rendered: 0
+        };
+    }
+
+    /// Set the interval between the recorded frames on initialization, like 2 for recording every other frame.
+    pub fn frame_interval(self, frame_interval: u32) -> Self {
+        return Self {
+            frame_interval: frame_interval.max(1),
+            ..self
+        };
+    }
+
+    /// Set the maximum number of recorded frames on initialization.
+    pub fn max_frames(self, max_frames: u32) -> Self {
+        return Self {
+            max_frames: Some(max_frames),
+            ..self
+        };
+    }
+
+    /// Returns if the recorder reached its maximum number of frames.
+    pub fn is_finished(&self) -> bool {
+        return self.max_frames.is_some_and(|max_frames| self.frames_recorded >= max_frames);
+    }
+
+    /// Count a rendered frame, returning the path where it should be saved if it is recorded.
+    pub fn next_frame(&mut self) -> Option<PathBuf> {
+        if self.is_finished() {
+            return None;
+        }
+        let is_recorded: bool = self.frames_rendered.is_multiple_of(self.frame_interval);
+        self.frames_rendered += 1;
+
+        if !is_recorded {
+            return None;
+        }
+        let path: PathBuf = self.directory.join(format!("frame_{:05}.png", self.frames_recorded));
+        self.frames_recorded += 1;
+        return Some(path);
+    }
+}
+
+/// The sender of the frames to the encoder worker, with the handle of the worker thread.
+type CaptureEncoder = (mpsc::SyncSender<(RgbaImage, Vec<PathBuf>)>, JoinHandle<()>);
+
+/// Struct to represent a frame copied into a readback buffer, waiting for the buffer to be mapped.
+struct PendingCapture {
+    readback_buffer: Buffer,
+    width: u32,
+    height: u32,
+    format: TextureFormat,
+    paths: Vec<PathBuf>,
+    receiver: mpsc::Receiver<Result<(), BufferAsyncError>>
+}
+
+/// Struct to represent the capturing process of the rendered frames, without stalling the rendering.
+///
+/// The frames are copied into pooled readback buffers and collected on a later frame, once the buffers are mapped.
+/// The collected frames are encoded as PNG files by a single worker thread, fed by a bounded channel.
+/// The rendering only waits for the worker when it falls behind by more than *MAX_QUEUED_CAPTURES* frames.
+#[derive(Default)]
+pub(crate) struct FrameCapturer {
+    readback_buffers: Vec<Buffer>,
+    pending_captures: VecDeque<PendingCapture>,
+    encoder: Option<CaptureEncoder>
+}
+
+impl FrameCapturer {
+    /// Copy a texture into a readback buffer and start mapping it, to be collected on a later frame.
+    pub(crate) fn capture(&mut self, device: &Device, queue: &Queue, texture: &Texture, paths: Vec<PathBuf>) {
+        let size: u64 = headless::readback_buffer_size(texture.width(), texture.height());
+        self.readback_buffers.retain(|readback_buffer| readback_buffer.size() == size);
+
+        let readback_buffer: Buffer = self.readback_buffers.pop()
+            .unwrap_or_else(|| headless::create_readback_buffer(device, texture.width(), texture.height()));
+        headless::copy_texture_to_buffer(device, queue, texture, &readback_buffer);
+
+        let (sender, receiver): (mpsc::Sender<_>, mpsc::Receiver<_>) = mpsc::channel::<Result<(), BufferAsyncError>>();
+        readback_buffer.slice(..).map_async(MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        self.pending_captures.push_back(PendingCapture {
+            readback_buffer,
+            width: texture.width(),
+            height: texture.height(),
+            format: texture.format(),
+            paths,
+            receiver
+        });
+    }
+
+    /// Collect the captures whose readback buffers are mapped, sending their frames to the encoder.
+    ///
+    /// When waiting, blocks until every pending capture is collected.
+    pub(crate) fn collect(&mut self, device: &Device, wait: bool) {
+        if self.pending_captures.is_empty() {
+            return;
+        }
+        let poll_type: PollType = if wait { PollType::wait_indefinitely() } else { PollType::Poll };
+
+        if let Err(error) = device.poll(poll_type) {
+            log::error!("Frame captures could not be polled: {}", error);
+            return;
+        }
+
+        while let Some(pending_capture) = self.pending_captures.front() {
+            let result: Result<(), BufferAsyncError> = match pending_capture.receiver.try_recv() {
+                Ok(result) => result,
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => Err(BufferAsyncError)
+            };
+            let pending_capture: PendingCapture = self.pending_captures.pop_front().unwrap();
+
+            match result {
+                Ok(()) => {
+                    let image: RgbaImage = headless::read_mapped_buffer(
+                        &pending_capture.readback_buffer,
+                        pending_capture.width,
+                        pending_capture.height,
+                        pending_capture.format
+                    );
+                    self.readback_buffers.push(pending_capture.readback_buffer);
+                    self.encode(image, pending_capture.paths);
+                },
+                Err(error) => log::error!("Frame could not be captured: {}", error)
+            }
+        }
+    }
+
+    /// Send a frame to the encoder worker, starting it on the first capture.
+    pub(crate) fn encode(&mut self, image: RgbaImage, paths: Vec<PathBuf>) {
+        let (sender, _) = self.encoder.get_or_insert_with(|| {
+            let (sender, receiver): (mpsc::SyncSender<_>, mpsc::Receiver<_>) = mpsc::sync_channel::<(RgbaImage, Vec<PathBuf>)>(MAX_QUEUED_CAPTURES);
+            let worker: JoinHandle<()> = std::thread::spawn(move || {
+                for (image, paths) in receiver {
+                    for path in paths {
+                        save_capture(&image, &path);
+                    }
+                }
+            });
+            (sender, worker)
+        });
+
+        if sender.send((image, paths)).is_err() {
+            log::error!("Frame could not be captured, the encoder has stopped.");
+        }
+    }
+}
+
+impl Drop for FrameCapturer {
+    /// Wait for the encoder worker to save the frames sent to it.
+    fn drop(&mut self) {
+        if let Some((sender, worker)) = self.encoder.take() {
+            drop(sender);
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Save a captured frame as a PNG file, creating its directory when needed.
+fn save_capture(image: &RgbaImage, path: &Path) {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        if let Err(error) = std::fs::create_dir_all(parent) {
+            log::error!("Capture directory '{}' could not be created: {}", parent.display(), error);
+            return;
+        }
+    }
+
+    if let Err(error) = image.save_with_format(path, ImageFormat::Png) {
+        log::error!("Capture '{}' could not be saved: {}", path.display(), error);
+    }
+}
@@ -1,48 +1,73 @@
+use std::collections::HashMap;
 use wgpu::*;
-use atomic_refcell::AtomicRefMut;
 use crate::RenderState;
+use cgmath::{Matrix4, Vector2};
+use super::{batch::{Batch, BatchBuilder, BatchDraw}, cache::bind_group::{get_camera_bind_group, get_camera_entity_bind_group}};
 use super::super::super::{
     super::ColorOption,
     color::color,
     event::dispatcher::EventDispatcher,
     shape::shape::Shape,
-    physics::transform::Transform,
+    physics::transform::{Transform, GlobalTransform},
     draw_order::DrawOrder,
     texture::sprite::Sprite,
     animation::animation::Animation,
+    post_process::post_process::PostEffect,
     text::text::TextHolder,
-    camera::camera2d::Camera2d,
-    ecs::{entity::Entity, world::World, component::Component, resource::{ResourceRef, ResourceRefMut}}
+    camera::{camera2d::Camera2d, camera::{Camera, CameraTarget}},
+    ecs::{entity::Entity, world::World, resource::{ResourceRef, ResourceRefMut}}
 };
 
 /// Execute processes related to the sucess of getting the surface texture (frame).
 pub(crate) fn on_success(render_state: &mut RenderState, world: &mut World, surface_texture: SurfaceTexture) {
     let texture_view: TextureView = surface_texture.texture.create_view(&TextureViewDescriptor::default());
+    render_frame(render_state, world, &texture_view);
+    render_state.capture_frame(&surface_texture.texture);
+    surface_texture.present();
+}
+
+/// Render the frame of the world into a texture view and submit it to the queue.
+///
+/// With active camera entities, the frame is drawn once per camera, on its own viewport and target.
+///
+/// With lighting or post-processing effects, the scene is rendered on an offscreen target,
+/// lit and then processed to the frame afterwards.
+pub(crate) fn render_frame(render_state: &mut RenderState, world: &mut World, texture_view: &TextureView) {
     let mut command_encoder: CommandEncoder = render_state.device.as_ref().unwrap().create_command_encoder(&CommandEncoderDescriptor {
         label: Some("Render Encoder")
     });
     render_state.text(world);
 
+    let normal_maps: Option<HashMap<String, String>> = render_state.prepare_lighting(world);
+    let post_process_effects: Vec<PostEffect> = render_state.prepare_post_process(world);
+    let scene_view: TextureView = if post_process_effects.is_empty() && normal_maps.is_none() {
+        texture_view.clone()
+    } else {
+        render_state.render_targets[0].view().clone()
+    };
+
     {
         let camera2d: ResourceRef<'_, Camera2d> = world.get_resource::<Camera2d>().unwrap();
         let mut event_dispatcher: ResourceRefMut<'_, EventDispatcher> = world.get_resource_mut::<EventDispatcher>().unwrap();
         let text_holder: ResourceRef<'_, TextHolder> = world.get_resource::<TextHolder>().unwrap();
-        let mut render_pass: RenderPass<'_> = command_encoder.begin_render_pass(&RenderPassDescriptor {
-            label: Some("Render Pass"),
-            color_attachments: &[Some(RenderPassColorAttachment {
-                depth_slice: None,
-                view: &texture_view,
-                resolve_target: None,
-                ops: Operations {
-                    load: LoadOp::Clear(color::Color::to_wgpu(render_state.color.unwrap_or_else(|| color::Color::by_option(ColorOption::White)))),
-                    store: StoreOp::Store
-                },
-            })],
-            depth_stencil_attachment: None,
-            occlusion_query_set: None,
-            timestamp_writes: None,
-            multiview_mask: None
-        });
+        let entities_to_render_sorted: Vec<Entity> = get_entities_to_render_sorted(render_state, world);
+        let cameras: Vec<(Entity, Camera)> = if render_state.batching { get_cameras_sorted(world) } else { Vec::new() };
+        let camera_frame: Option<CameraFrame> = if cameras.is_empty() {
+            None
+        } else {
+            Some(prepare_cameras(render_state, world, &mut command_encoder, &mut event_dispatcher, &text_holder, &entities_to_render_sorted, &cameras))
+        };
+        let batch_builder: Option<BatchBuilder> = if camera_frame.is_none() && render_state.batching {
+            Some(prepare_batches(render_state, world, &mut event_dispatcher, &text_holder, &entities_to_render_sorted))
+        } else {
+            None
+        };
+
+        if let Some(normal_maps) = &normal_maps {
+            render_normals(render_state, &mut command_encoder, &camera2d, batch_builder.as_ref(), normal_maps);
+        }
+        let background_color: color::Color = render_state.color.unwrap_or_else(|| color::Color::by_option(ColorOption::White));
+        let mut render_pass: RenderPass<'_> = begin_render_pass(&mut command_encoder, &scene_view, LoadOp::Clear(background_color.to_wgpu()), "Render Pass");
         render_pass.set_viewport(
             0.0,
             0.0,
@@ -62,86 +87,329 @@ pub(crate) fn on_success(render_state: &mut RenderState, world: &mut World, surf
                 None,
                 None,
                 None,
+                None,
                 &camera2d,
                 true
             );
             render_state.render(&mut render_pass);
         }
 
-        for entity in get_entities_to_render_sorted(render_state, world).clone() {
-            if world.is_entity_alive(entity) {
-                let is_entity_visible: bool = world.is_entity_visible(entity);
-                let components: Vec<AtomicRefMut<'_, Box<dyn Component>>> = world.get_entity_components_mut(&entity).unwrap();
-                let transform: Option<&Transform> = components.iter().find_map(
-                    |component| component.as_any().downcast_ref::<Transform>()
-                );
-                let animation: Option<&Animation> = components.iter().find_map(
-                    |component| component.as_any().downcast_ref::<Animation>()
-                );
+        if let Some(camera_frame) = camera_frame {
+            render_window_cameras(render_state, &mut render_pass, &text_holder, &camera_frame);
+        } else if let Some(batch_builder) = &batch_builder {
+            render_batches(render_state, &mut render_pass, &camera2d, &text_holder, batch_builder);
+        } else {
+            render_entities(render_state, world, &mut render_pass, &mut event_dispatcher, &camera2d, &text_holder, &entities_to_render_sorted);
+        }
+    }
 
-                if let Some(animation) = animation {
-                    if !animation.playing_stack.is_empty() {
-                        render_pass.set_pipeline(render_state.render_pipeline_2d.as_ref().unwrap());
-                        render_state.setup(
-                            &mut event_dispatcher,
-                            Some(&entity),
-                            None,
-                            None,
-                            transform,
-                            Some(animation),
-                            &camera2d,
-                            false
-                        );
-
-                        if is_entity_visible {
-                            render_state.render(&mut render_pass);
-                        }
-                        continue;
-                    }
+    if normal_maps.is_some() {
+        let lit_view: TextureView = if post_process_effects.is_empty() {
+            texture_view.clone()
+        } else {
+            render_state.render_targets[1].view().clone()
+        };
+        render_state.apply_lighting(&mut command_encoder, &lit_view);
+    }
+
+    if !post_process_effects.is_empty() {
+        let first_target: usize = if normal_maps.is_some() { 1 } else { 0 };
+        render_state.apply_post_process(&mut command_encoder, &post_process_effects, first_target, texture_view);
+    }
+    render_state.queue.as_ref().unwrap().submit(std::iter::once(command_encoder.finish()));
+}
+
+/// Struct to represent the batches of a frame and the views of the camera entities that draw them.
+struct CameraFrame {
+    batch_builder: BatchBuilder,
+    views: Vec<CameraView>
+}
+
+/// Struct to represent a camera entity ready to draw the batches of a frame.
+struct CameraView {
+    camera: Camera,
+    viewport: (f32, f32, f32, f32),
+    bind_group: BindGroup,
+    clear: Option<Batch>
+}
+
+/// Begin a render pass on a single color attachment.
+pub(crate) fn begin_render_pass<'a>(command_encoder: &'a mut CommandEncoder, view: &TextureView, load: LoadOp<wgpu::Color>, label: &str) -> RenderPass<'a> {
+    return command_encoder.begin_render_pass(&RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            depth_slice: None,
+            view,
+            resolve_target: None,
+            ops: Operations {
+                load,
+                store: StoreOp::Store
+            },
+        })],
+        depth_stencil_attachment: None,
+        occlusion_query_set: None,
+        timestamp_writes: None,
+        multiview_mask: None
+    });
+}
+
+/// Prepare the batches of the frame and the views of the camera entities, rendering the ones with offscreen targets.
+///
+/// The batches are built once and drawn by every camera, each one with its own projection and view.
+fn prepare_cameras(
+    render_state: &mut RenderState,
+    world: &World,
+    command_encoder: &mut CommandEncoder,
+    event_dispatcher: &mut EventDispatcher,
+    text_holder: &TextHolder,
+    entities: &[Entity],
+    cameras: &[(Entity, Camera)]
+) -> CameraFrame {
+    for (_, camera) in cameras {
+        if let CameraTarget::Texture { name, width, height } = &camera.target {
+            render_state.prepare_camera_target(name, *width, *height);
+        }
+    }
+    let mut batch_builder: BatchBuilder = render_state.batches(world, event_dispatcher, text_holder, entities);
+    let mut views: Vec<CameraView> = Vec::with_capacity(cameras.len());
+
+    for (entity, camera) in cameras {
+        let (width, height): (u32, u32) = match &camera.target {
+            CameraTarget::Window => (render_state.physical_size.unwrap().width, render_state.physical_size.unwrap().height),
+            CameraTarget::Texture { width, height, .. } => (*width, *height)
+        };
+        let viewport: (f32, f32, f32, f32) = camera.viewport.to_pixels(width, height);
+        let projection: Matrix4<f32> = camera.projection_matrix(viewport.2, viewport.3);
+        let view: Matrix4<f32> = camera.view_matrix(get_camera_position(world, entity, camera));
+        let bind_group: BindGroup = get_camera_entity_bind_group(render_state, Some(entity), projection, view);
+        let clear: Option<Batch> = camera.clear_color
+            .filter(|_| camera.is_window_target())
+            .map(|clear_color| render_state.camera_clear_batch(&mut batch_builder, clear_color.to_array(), projection, view));
+
+        views.push(CameraView {
+            camera: camera.clone(),
+            viewport,
+            bind_group,
+            clear
+        });
+    }
+    render_state.write_instances(&batch_builder.instances);
+
+    for camera_view in views.iter().filter(|camera_view| !camera_view.camera.is_window_target()) {
+        let name: &str = camera_view.camera.target.texture_name().unwrap();
+        let target_view: TextureView = render_state.camera_targets[name].view().clone();
+        let clear_color: color::Color = camera_view.camera.clear_color
+            .or(render_state.color)
+            .unwrap_or_else(|| color::Color::by_option(ColorOption::White));
+        let mut render_pass: RenderPass<'_> = begin_render_pass(command_encoder, &target_view, LoadOp::Clear(clear_color.to_wgpu()), "Camera Render Pass");
+
+        render_camera(render_state, &mut render_pass, &batch_builder, camera_view);
+    }
+    return CameraFrame {
+        batch_builder,
+        views
+    };
+}
+
+/// Render the batches of the frame through a camera, on its viewport.
+///
+/// The batches sampling the offscreen target of the camera itself are skipped.
+fn render_camera(render_state: &mut RenderState, render_pass: &mut RenderPass<'_>, batch_builder: &BatchBuilder, camera_view: &CameraView) {
+    let (x, y, width, height): (f32, f32, f32, f32) = camera_view.viewport;
+    let own_texture: Option<&str> = camera_view.camera.target.texture_name();
+    render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
+
+    if let Some(clear) = &camera_view.clear {
+        render_state.render_batch(render_pass, clear, &camera_view.bind_group);
+    }
+
+    for draw in &batch_builder.draws {
+        if let BatchDraw::Instanced(batch) = draw {
+            if own_texture != Some(batch.texture.as_str()) {
+                render_state.render_batch(render_pass, batch, &camera_view.bind_group);
+            }
+        }
+    }
+}
+
+/// Render the camera entities that target the window by their order, with the texts on top of all of them.
+fn render_window_cameras(render_state: &mut RenderState, render_pass: &mut RenderPass<'_>, text_holder: &TextHolder, camera_frame: &CameraFrame) {
+    for camera_view in camera_frame.views.iter().filter(|camera_view| camera_view.camera.is_window_target()) {
+        render_camera(render_state, render_pass, &camera_frame.batch_builder, camera_view);
+    }
+    render_pass.set_viewport(
+        0.0,
+        0.0,
+        render_state.physical_size.as_ref().unwrap().width as f32,
+        render_state.physical_size.as_ref().unwrap().height as f32,
+        0.0,
+        1.0
+    );
+
+    for draw in &camera_frame.batch_builder.draws {
+        if let BatchDraw::Text(entity) = draw {
+            if let Some(text_renderer) = text_holder.text_renderers.get(entity) {
+                text_renderer.text_brush.draw(render_pass);
+            }
+        }
+    }
+}
+
+/// Build the batches of the frame from the entities to render, writing their instances on the instance buffer.
+fn prepare_batches(
+    render_state: &mut RenderState,
+    world: &World,
+    event_dispatcher: &mut EventDispatcher,
+    text_holder: &TextHolder,
+    entities: &[Entity]
+) -> BatchBuilder {
+    let batch_builder: BatchBuilder = render_state.batches(world, event_dispatcher, text_holder, entities);
+    render_state.write_instances(&batch_builder.instances);
+    return batch_builder;
+}
+
+/// Render the normals of the frame used by the lighting, drawing the normal maps of the batched sprites over flat normals.
+///
+/// Without batches, like while rendering through camera entities, the whole frame keeps flat normals.
+fn render_normals(
+    render_state: &mut RenderState,
+    command_encoder: &mut CommandEncoder,
+    camera2d: &Camera2d,
+    batch_builder: Option<&BatchBuilder>,
+    normal_maps: &HashMap<String, String>
+) {
+    let normal_view: TextureView = render_state.normal_target.as_ref().unwrap().view().clone();
+    let flat_normal: wgpu::Color = wgpu::Color { r: 0.5, g: 0.5, b: 1.0, a: 1.0 };
+    let mut render_pass: RenderPass<'_> = begin_render_pass(command_encoder, &normal_view, LoadOp::Clear(flat_normal), "Normal Render Pass");
+
+    let Some(batch_builder) = batch_builder.filter(|_| !normal_maps.is_empty()) else {
+        return;
+    };
+    let camera_bind_group: BindGroup = get_camera_bind_group(render_state, camera2d);
+
+    for draw in &batch_builder.draws {
+        if let BatchDraw::Instanced(batch) = draw {
+            if let Some(normal_map) = normal_maps.get(&batch.texture) {
+                render_state.render_normal_batch(&mut render_pass, batch, normal_map, &camera_bind_group);
+            }
+        }
+    }
+}
+
+/// Render the batches of the frame, drawing the entities that share a texture and a geometry with instancing.
+fn render_batches(
+    render_state: &mut RenderState,
+    render_pass: &mut RenderPass<'_>,
+    camera2d: &Camera2d,
+    text_holder: &TextHolder,
+    batch_builder: &BatchBuilder
+) {
+    if batch_builder.draws.is_empty() {
+        return;
+    }
+    let camera_bind_group: BindGroup = get_camera_bind_group(render_state, camera2d);
+
+    for draw in &batch_builder.draws {
+        match draw {
+            BatchDraw::Instanced(batch) => render_state.render_batch(render_pass, batch, &camera_bind_group),
+            BatchDraw::Text(entity) => {
+                if let Some(text_renderer) = text_holder.text_renderers.get(entity) {
+                    text_renderer.text_brush.draw(render_pass);
                 }
+            }
+        }
+    }
+}
 
-                if let Some(sprite) = components.iter().find_map(|component| component.as_any().downcast_ref::<Sprite>()) {
-                    render_pass.set_pipeline(render_state.render_pipeline_2d.as_ref().unwrap());
-                    render_state.setup(
-                        &mut event_dispatcher,
-                        Some(&entity),
-                        Some(sprite),
-                        None,
-                        transform,
-                        animation,
-                        &camera2d,
-                        false
-                    );
+/// Render the entities of the frame one by one, each one with its own bindings.
+fn render_entities(
+    render_state: &mut RenderState,
+    world: &World,
+    render_pass: &mut RenderPass<'_>,
+    event_dispatcher: &mut EventDispatcher,
+    camera2d: &Camera2d,
+    text_holder: &TextHolder,
+    entities: &[Entity]
+) {
+    for entity in entities.iter().copied() {
+        if world.is_entity_alive(entity) {
+            let is_entity_visible: bool = world.is_entity_visible(entity);
+            let Some((transform, global_transform, animation, sprite, shape)) = world.query_entity::<(
+                Option<&Transform>,
+                Option<&GlobalTransform>,
+                Option<&Animation>,
+                Option<&Sprite>,
+                Option<&Shape>
+            )>(&entity) else {
+                continue;
+            };
+            let transform: Option<&Transform> = transform.as_deref();
+            let global_transform: Option<&GlobalTransform> = global_transform.as_deref();
+            let animation: Option<&Animation> = animation.as_deref();
 
-                    if is_entity_visible {
-                        render_state.render(&mut render_pass);
-                    }
-                } else if let Some(shape) = components.iter().find_map(|component| component.as_any().downcast_ref::<Shape>()) {
+            if let Some(animation) = animation {
+                if !animation.playing_stack.is_empty() {
                     render_pass.set_pipeline(render_state.render_pipeline_2d.as_ref().unwrap());
                     render_state.setup(
-                        &mut event_dispatcher,
+                        event_dispatcher,
                         Some(&entity),
                         None,
-                        Some(shape),
-                        transform,
                         None,
-                        &camera2d,
+                        transform,
+                        global_transform,
+                        Some(animation),
+                        camera2d,
                         false
                     );
 
                     if is_entity_visible {
-                        render_state.render(&mut render_pass);
-                    }
-                } else if let Some(text_renderer) = text_holder.text_renderers.get(&entity.0) {
-                    if is_entity_visible {
-                        text_renderer.text_brush.draw(&mut render_pass);
+                        render_state.render(render_pass);
                     }
+                    continue;
+                }
+            }
+
+            if let Some(sprite) = sprite.as_deref() {
+                render_pass.set_pipeline(render_state.render_pipeline_2d.as_ref().unwrap());
+                render_state.setup(
+                    event_dispatcher,
+                    Some(&entity),
+                    Some(sprite),
+                    None,
+                    transform,
+                    global_transform,
+                    animation,
+                    camera2d,
+                    false
+                );
+
+                if is_entity_visible {
+                    render_state.render(render_pass);
+                }
+            } else if let Some(shape) = shape.as_deref() {
+                render_pass.set_pipeline(render_state.render_pipeline_2d.as_ref().unwrap());
+                render_state.setup(
+                    event_dispatcher,
+                    Some(&entity),
+                    None,
+                    Some(shape),
+                    transform,
+                    global_transform,
+                    None,
+                    camera2d,
+                    false
+                );
+
+                if is_entity_visible {
+                    render_state.render(render_pass);
+                }
+            } else if let Some(text_renderer) = text_holder.text_renderers.get(&entity) {
+                if is_entity_visible {
+                    text_renderer.text_brush.draw(render_pass);
                 }
             }
         }
     }
-    render_state.queue.as_ref().unwrap().submit(std::iter::once(command_encoder.finish()));
-    surface_texture.present();
 }
 
 /// Execute processes related to getting the surface texture (frame) being suboptimal.
@@ -157,6 +425,26 @@ pub(crate) fn on_suboptimal(render_state: &mut RenderState, world: &mut World) {
     );
 }
 
+/// Returns the active camera entities, the ones with offscreen targets first and then by their order.
+fn get_cameras_sorted(world: &World) -> Vec<(Entity, Camera)> {
+    let mut cameras: Vec<(Entity, Camera)> = world.query::<(Entity, &Camera)>()
+        .filter(|(_, camera)| camera.active)
+        .map(|(entity, camera)| (entity, camera.clone()))
+        .collect();
+
+    cameras.sort_by_key(|(_, camera)| (camera.is_window_target(), camera.order));
+    return cameras;
+}
+
+/// Returns the position a camera looks at, the one of the entity it follows or its own.
+fn get_camera_position(world: &World, entity: &Entity, camera: &Camera) -> Vector2<f32> {
+    let looked_entity: Entity = camera.follow.filter(|followed| world.is_entity_alive(*followed)).unwrap_or(*entity);
+
+    return world.get_entity_component::<Transform>(&looked_entity)
+        .map(|transform| Vector2::new(transform.position.x, transform.position.y))
+        .unwrap_or(Vector2::new(0.0, 0.0));
+}
+
 fn get_entities_to_render_sorted(render_state: &mut RenderState, world: &World) -> Vec<Entity> {
     let mut entities_to_render_sorted: Vec<Entity> = render_state.entities_to_render.clone();
 
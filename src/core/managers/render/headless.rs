@@ -0,0 +1,241 @@
+use std::{path::PathBuf, sync::mpsc};
+use image::RgbaImage;
+use wgpu::*;
+use crate::utils::constants::headless::{UPDATE_GOLDEN_VARIABLE, ACTUAL_IMAGE_SUFFIX};
+
+/// Enumerator to represent the different errors that the headless rendering flow can throw.
+#[derive(Debug)]
+pub enum HeadlessError {
+    Adapter(RequestAdapterError),
+    Device(RequestDeviceError),
+    Poll(PollError),
+    Readback(BufferAsyncError),
+    Io(std::io::Error),
+    Image(image::ImageError),
+    Mismatch { golden: PathBuf, different_pixels: usize, total_pixels: usize },
+    MissingGolden(PathBuf)
+}
+
+impl std::fmt::Display for HeadlessError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeadlessError::Adapter(error) => write!(formatter, "Adapter Headless Error: {}.", error),
+            HeadlessError::Device(error) => write!(formatter, "Device Headless Error: {}.", error),
+            HeadlessError::Poll(error) => write!(formatter, "Poll Headless Error: {}.", error),
+            HeadlessError::Readback(error) => write!(formatter, "Readback Headless Error: {}.", error),
+            HeadlessError::Io(error) => write!(formatter, "IO Headless Error: {}.", error),
+            HeadlessError::Image(error) => write!(formatter, "Image Headless Error: {}.", error),
+            HeadlessError::Mismatch { golden, different_pixels, total_pixels } => write!(
+                formatter,
+                "Golden Image Headless Error: {} of {} pixels differ from '{}'.",
+                different_pixels,
+                total_pixels,
+                golden.display()
+            ),
+            HeadlessError::MissingGolden(golden) => write!(
+                formatter,
+                "Golden Image Headless Error: '{}' was not found, set {} to create it.",
+                golden.display(),
+                UPDATE_GOLDEN_VARIABLE
+            )
+        }
+    }
+}
+
+impl std::error::Error for HeadlessError {}
+
+impl From<RequestAdapterError> for HeadlessError {
+    fn from(error: RequestAdapterError) -> Self {
+        return HeadlessError::Adapter(error);
+    }
+}
+
+impl From<RequestDeviceError> for HeadlessError {
+    fn from(error: RequestDeviceError) -> Self {
+        return HeadlessError::Device(error);
+    }
+}
+
+impl From<PollError> for HeadlessError {
+    fn from(error: PollError) -> Self {
+        return HeadlessError::Poll(error);
+    }
+}
+
+impl From<BufferAsyncError> for HeadlessError {
+    fn from(error: BufferAsyncError) -> Self {
+        return HeadlessError::Readback(error);
+    }
+}
+
+impl From<std::io::Error> for HeadlessError {
+    fn from(error: std::io::Error) -> Self {
+        return HeadlessError::Io(error);
+    }
+}
+
+impl From<image::ImageError> for HeadlessError {
+    fn from(error: image::ImageError) -> Self {
+        return HeadlessError::Image(error);
+    }
+}
+
+/// Struct to represent a golden image, the expected rendering of a frame stored as a PNG file.
+///
+/// The golden images are only written while the *LOTUS_UPDATE_GOLDEN* environment variable is set,
+/// so a missing golden image fails the comparison instead of being created.
+///
+/// When a frame doesn't match, it is saved next to the golden image with the *.actual.png* suffix for inspection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GoldenImage {
+    pub path: PathBuf,
+    pub tolerance: u8
+}
+
+impl GoldenImage {
+    /// Create a new golden image by its file path.
+    pub fn new(path: String) -> Self {
+        return Self {
+            path: PathBuf::from(path),
+            tolerance: 2
+        };
+    }
+
+    /// Set the maximum difference allowed on each channel of a pixel on initialization.
+    pub fn tolerance(self, tolerance: u8) -> Self {
+        return Self {
+            tolerance,
+            ..self
+        };
+    }
+
+    /// Compare a frame against the golden image.
+    ///
+    /// While updating the golden images, the frame is written as the golden image instead.
+    pub fn compare(&self, image: &RgbaImage) -> Result<(), HeadlessError> {
+        if std::env::var_os(UPDATE_GOLDEN_VARIABLE).is_some() {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            image.save(&self.path)?;
+            return Ok(());
+        }
+
+        if !self.path.exists() {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            image.save(self.actual_path())?;
+            return Err(HeadlessError::MissingGolden(self.path.clone()));
+        }
+        let golden: RgbaImage = image::open(&self.path)?.into_rgba8();
+        let different_pixels: usize = count_different_pixels(image, &golden, self.tolerance);
+
+        if different_pixels > 0 {
+            image.save(self.actual_path())?;
+
+            return Err(HeadlessError::Mismatch {
+                golden: self.path.clone(),
+                different_pixels,
+                total_pixels: (image.width() * image.height()) as usize
+            });
+        }
+        return Ok(());
+    }
+
+    /// Returns the path where a frame that doesn't match the golden image is saved.
+    pub fn actual_path(&self) -> PathBuf {
+        let stem: String = self.path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        return self.path.with_file_name(format!("{}{}", stem, ACTUAL_IMAGE_SUFFIX));
+    }
+}
+
+/// Returns the number of pixels that differ between two images by more than the tolerance on any channel.
+///
+/// Images with different sizes differ on every pixel.
+pub fn count_different_pixels(image: &RgbaImage, other: &RgbaImage, tolerance: u8) -> usize {
+    if image.dimensions() != other.dimensions() {
+        return (image.width() * image.height()).max(other.width() * other.height()) as usize;
+    }
+    return image.pixels().zip(other.pixels())
+        .filter(|(pixel, other_pixel)| pixel.0.iter().zip(other_pixel.0.iter()).any(|(a, b)| a.abs_diff(*b) > tolerance))
+        .count();
+}
+
+/// Returns the pixels of a texture as an RGBA image, waiting for the copy to finish.
+///
+/// The texture must have the copy source usage, and the ones with a BGRA format have their channels swapped.
+pub(crate) fn read_texture(device: &Device, queue: &Queue, texture: &Texture) -> Result<RgbaImage, HeadlessError> {
+    let readback_buffer: Buffer = create_readback_buffer(device, texture.width(), texture.height());
+    copy_texture_to_buffer(device, queue, texture, &readback_buffer);
+
+    let (sender, receiver): (mpsc::Sender<_>, mpsc::Receiver<_>) = mpsc::channel::<Result<(), BufferAsyncError>>();
+    readback_buffer.slice(..).map_async(MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device.poll(PollType::wait_indefinitely())?;
+    receiver.recv().expect("Readback buffer should be mapped.")?;
+
+    return Ok(read_mapped_buffer(&readback_buffer, texture.width(), texture.height(), texture.format()));
+}
+
+/// Returns the bytes of a row of pixels on a readback buffer, padded to the copy alignment.
+fn padded_bytes_per_row(width: u32) -> u32 {
+    return (width * 4).div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT) * COPY_BYTES_PER_ROW_ALIGNMENT;
+}
+
+/// Create a buffer that receives the copy of a texture and can be mapped to be read.
+pub(crate) fn create_readback_buffer(device: &Device, width: u32, height: u32) -> Buffer {
+    return device.create_buffer(&BufferDescriptor {
+        label: Some("Readback Buffer"),
+        size: readback_buffer_size(width, height),
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false
+    });
+}
+
+/// Returns the size of the readback buffer of a texture.
+pub(crate) fn readback_buffer_size(width: u32, height: u32) -> BufferAddress {
+    return (padded_bytes_per_row(width) * height) as BufferAddress;
+}
+
+/// Copy a texture into a readback buffer and submit it to the queue.
+pub(crate) fn copy_texture_to_buffer(device: &Device, queue: &Queue, texture: &Texture, readback_buffer: &Buffer) {
+    let mut command_encoder: CommandEncoder = device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("Readback Encoder")
+    });
+
+    command_encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        TexelCopyBufferInfo {
+            buffer: readback_buffer,
+            layout: TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row(texture.width())),
+                rows_per_image: Some(texture.height())
+            }
+        },
+        texture.size()
+    );
+    queue.submit(std::iter::once(command_encoder.finish()));
+}
+
+/// Returns the pixels of a mapped readback buffer as an RGBA image, unmapping the buffer afterwards.
+pub(crate) fn read_mapped_buffer(readback_buffer: &Buffer, width: u32, height: u32, format: TextureFormat) -> RgbaImage {
+    let unpadded_bytes_per_row: u32 = width * 4;
+    let mut pixels: Vec<u8> = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+    {
+        let mapped_range: BufferView = readback_buffer.slice(..).get_mapped_range();
+
+        for row in mapped_range.chunks(padded_bytes_per_row(width) as usize) {
+            pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+    }
+    readback_buffer.unmap();
+
+    if matches!(format, TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb) {
+        pixels.chunks_exact_mut(4).for_each(|pixel| pixel.swap(0, 2));
+    }
+    return RgbaImage::from_raw(width, height, pixels).expect("Pixels should match the texture size.");
+}
+
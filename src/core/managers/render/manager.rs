@@ -1,25 +1,39 @@
 use wgpu::*;
-use uuid::Uuid;
-use cgmath::{ortho, Matrix4, SquareMatrix};
+use cgmath::{ortho, Deg, Matrix4, SquareMatrix, Vector2, Vector3, Vector4};
 use wgpu_text::glyph_brush::Section;
 use winit::event_loop::ActiveEventLoop;
 use winit::{dpi::PhysicalSize, event::WindowEvent, window::Window};
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 use super::cache::{self, buffer::BufferCache, bind_group::BindGroupCache};
-use super::rendering_type::RenderingType;
+use super::{
+    rendering_type::RenderingType,
+    memory::MemoryStats,
+    target::RenderTarget,
+    capture::{FrameCapturer, FrameRecorder},
+    headless::{self, HeadlessError},
+    batch::{BatchBuilder, BatchInstance, BatchGeometry, BatchMaterial, Batch}
+};
 use super::super::super::{
     super::{ColorOption},
     event::dispatcher::{EventDispatcher, Event, EventType, SubEventType},
     shape::{shape::Shape, geometry_type::GeometryType, orientation::Orientation},
-    physics::transform::{Transform, Strategy},
+    physics::transform::{Transform, GlobalTransform, Strategy},
     texture,
     texture::{cache::TextureCache, sprite::Sprite, sprite_sheet::SpriteSheet},
     animation::animation::Animation,
+    material::material::Material,
+    particle::particle_emitter::{ParticleEmitter, ParticleAppearance, ParticleSpace},
+    post_process::post_process::{PostProcess, PostEffect},
+    lighting::light::{AmbientLight, PointLight2d, Occluder, LightingUniforms},
+    physics::collision::Collision,
     text::text::{TextHolder, TextRenderer},
     camera::camera2d::Camera2d,
-    ecs::{entity::Entity, world::World}
+    ecs::{entity::Entity, world::World, resource::ResourceRef}
+};
+use crate::utils::constants::{
+    shader::{SHADER_2D, SHADER_2D_INSTANCED, MAX_MATERIAL_TEXTURES, LIGHTING_SHADER, NORMAL_MAP_SHADER},
+    cache::{FIXED_ID, RENDERING_TYPE_BUFFER, DUMMY_TEXTURE, BATCH_TEXTURE_BIND_GROUP, LIGHTING_BIND_GROUP, LIGHTING_PIPELINE, NORMAL_MAP_PIPELINE}
 };
-use crate::utils::constants::{shader::SHADER_2D, cache::{RENDERING_TYPE_BUFFER, DUMMY_TEXTURE}};
 
 /// Struct to represent the vertices that will be sent to the shader.
 #[repr(C)]
@@ -37,7 +51,7 @@ impl Vertex {
         2 => Float32x4
     ];
 
-    fn descriptor() -> VertexBufferLayout<'static> {
+    pub(crate) fn descriptor() -> VertexBufferLayout<'static> {
         return VertexBufferLayout {
             array_stride: std::mem::size_of::<Self>() as BufferAddress,
             step_mode: VertexStepMode::Vertex,
@@ -47,6 +61,13 @@ impl Vertex {
 }
 
 /// Struct to represent the current rendering state of the engine.
+///
+/// When batching is enabled, sprites, animations and shapes are drawn with instancing,
+/// grouping the consecutive entities that share a texture and a geometry on a single draw call.
+/// The materials of the entities are only applied when batching is enabled.
+///
+/// When the post-processing chain has effects, the frame is rendered on offscreen targets before reaching the window.
+/// The offscreen targets of the camera entities are kept on the texture cache by their names, to be used as sprites.
 pub struct RenderState {
     pub surface: Option<Surface<'static>>,
     pub device: Option<Device>,
@@ -57,6 +78,8 @@ pub struct RenderState {
     pub background_image_path: Option<String>,
     pub window: Option<Arc<Window>>,
     pub render_pipeline_2d: Option<RenderPipeline>,
+    pub render_pipeline_2d_instanced: Option<RenderPipeline>,
+    pub batching: bool,
     pub number_of_indices: Option<u32>,
     pub vertex_buffer: Option<Buffer>,
     pub index_buffer: Option<Buffer>,
@@ -69,6 +92,18 @@ pub struct RenderState {
     pub texture_bind_group: Option<BindGroup>,
     pub transform_bind_group_layout: Option<BindGroupLayout>,
     pub transform_bind_group: Option<BindGroup>,
+    pub camera_bind_group_layout: Option<BindGroupLayout>,
+    pub material_bind_group_layout: Option<BindGroupLayout>,
+    pub post_process_bind_group_layout: Option<BindGroupLayout>,
+    pub render_targets: Vec<RenderTarget>,
+    pub normal_target: Option<RenderTarget>,
+    pub camera_targets: HashMap<String, RenderTarget>,
+    pub screenshot_requests: Vec<PathBuf>,
+    pub frame_recorder: Option<FrameRecorder>,
+    pub(crate) frame_capturer: FrameCapturer,
+    pub instance_buffer: Option<Buffer>,
+    pub pipeline_cache: HashMap<String, Option<RenderPipeline>>,
+    pub elapsed_time: f32,
     pub entities_to_render: Vec<Entity>,
     pub texture_cache: TextureCache,
     pub buffer_cache: BufferCache,
@@ -90,6 +125,8 @@ impl RenderState {
             background_image_path: None,
             window: None,
             render_pipeline_2d: None,
+            render_pipeline_2d_instanced: None,
+            batching: true,
             number_of_indices: None,
             vertex_buffer: None,
             index_buffer: None,
@@ -102,6 +139,18 @@ impl RenderState {
             texture_bind_group: None,
             transform_bind_group_layout: None,
             transform_bind_group: None,
+            camera_bind_group_layout: None,
+            material_bind_group_layout: None,
+            post_process_bind_group_layout: None,
+            render_targets: Vec::new(),
+            normal_target: None,
+            camera_targets: HashMap::new(),
+            screenshot_requests: Vec::new(),
+            frame_recorder: None,
+            frame_capturer: FrameCapturer::default(),
+            instance_buffer: None,
+            pipeline_cache: HashMap::new(),
+            elapsed_time: 0.0,
             entities_to_render: Vec::new(),
             texture_cache: TextureCache::new(),
             buffer_cache: BufferCache::new(),
@@ -146,7 +195,7 @@ impl RenderState {
             .copied()
             .unwrap_or(surface_capabilities.formats[0]);
         let surface_configuration: SurfaceConfiguration = SurfaceConfiguration {
-            usage: TextureUsages::RENDER_ATTACHMENT,
+            usage: TextureUsages::RENDER_ATTACHMENT | (surface_capabilities.usages & TextureUsages::COPY_SRC),
             format: surface_format,
             width: physical_size.width,
             height: physical_size.height,
@@ -157,16 +206,80 @@ impl RenderState {
         };
         surface.configure(&device, &surface_configuration);
 
+        return Self::from_device(Some(surface), device, queue, surface_configuration, physical_size, Some(window));
+    }
+
+    /// Create a new asynchronous rendering state without a window, rendering the frames into offscreen textures.
+    ///
+    /// The device is created on a fallback adapter when there is one, like a software rasterizer,
+    /// so it works on machines and continuous integration runners without a GPU.
+    pub async fn headless(width: u32, height: u32) -> Result<Self, HeadlessError> {
+        let instance: Instance = Instance::new(InstanceDescriptor{
+            backends: Backends::all(),
+            backend_options: BackendOptions::from_env_or_default(),
+            flags: InstanceFlags::default(),
+            memory_budget_thresholds: MemoryBudgetThresholds::default(),
+            display: None
+        });
+
+        let adapter: Adapter = match instance.request_adapter(&RequestAdapterOptions {
+            power_preference: PowerPreference::default(),
+            compatible_surface: None,
+            force_fallback_adapter: true
+        }).await {
+            Ok(adapter) => adapter,
+            Err(_) => instance.request_adapter(&RequestAdapterOptions {
+                power_preference: PowerPreference::default(),
+                compatible_surface: None,
+                force_fallback_adapter: false
+            }).await?
+        };
+
+        let (device, queue): (Device, Queue) = adapter.request_device(
+            &DeviceDescriptor {
+                required_features: Features::default(),
+                required_limits: adapter.limits(),
+                label: None,
+                memory_hints: Default::default(),
+                trace: Trace::Off,
+                experimental_features: ExperimentalFeatures::disabled()
+            }
+        ).await?;
+
+        let surface_configuration: SurfaceConfiguration = SurfaceConfiguration {
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            format: TextureFormat::Rgba8UnormSrgb,
+            width: width.max(1),
+            height: height.max(1),
+            present_mode: PresentMode::Fifo,
+            alpha_mode: CompositeAlphaMode::Opaque,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2
+        };
+        return Ok(Self::from_device(None, device, queue, surface_configuration, PhysicalSize::new(width.max(1), height.max(1)), None));
+    }
+
+    /// Create a new rendering state on a device, with the layouts and pipelines of the engine.
+    fn from_device(
+        surface: Option<Surface<'static>>,
+        device: Device,
+        queue: Queue,
+        surface_configuration: SurfaceConfiguration,
+        physical_size: PhysicalSize<u32>,
+        window: Option<Arc<Window>>
+    ) -> Self {
         let mut render_state: RenderState = Self {
-            surface: Some(surface),
+            surface,
             device: Some(device),
             queue: Some(queue),
             surface_configuration: Some(surface_configuration),
             physical_size: Some(physical_size),
             color: None,
             background_image_path: None,
-            window: Some(window),
+            window,
             render_pipeline_2d: None,
+            render_pipeline_2d_instanced: None,
+            batching: true,
             number_of_indices: None,
             vertex_buffer: None,
             index_buffer: None,
@@ -179,6 +292,18 @@ impl RenderState {
             texture_bind_group: None,
             transform_bind_group_layout: None,
             transform_bind_group: None,
+            camera_bind_group_layout: None,
+            material_bind_group_layout: None,
+            post_process_bind_group_layout: None,
+            render_targets: Vec::new(),
+            normal_target: None,
+            camera_targets: HashMap::new(),
+            screenshot_requests: Vec::new(),
+            frame_recorder: None,
+            frame_capturer: FrameCapturer::default(),
+            instance_buffer: None,
+            pipeline_cache: HashMap::new(),
+            elapsed_time: 0.0,
             entities_to_render: Vec::new(),
             texture_cache: TextureCache::new(),
             buffer_cache: BufferCache::new(),
@@ -258,19 +383,181 @@ impl RenderState {
                 }
             ]
         });
+        let camera_bind_group_layout: BindGroupLayout = render_state.device.as_ref().unwrap().create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Camera Bind Group Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::VERTEX,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None
+                    },
+                    count: None
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::VERTEX,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None
+                    },
+                    count: None
+                }
+            ]
+        });
+        let mut material_entries: Vec<BindGroupLayoutEntry> = vec![
+            BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None
+                },
+                count: None
+            },
+            BindGroupLayoutEntry {
+                binding: 1,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                count: None
+            }
+        ];
+        material_entries.extend((0..MAX_MATERIAL_TEXTURES).map(|index| BindGroupLayoutEntry {
+            binding: 2 + index as u32,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                multisampled: false,
+                view_dimension: wgpu::TextureViewDimension::D2,
+                sample_type: TextureSampleType::Float {
+                    filterable: true
+                }
+            },
+            count: None
+        }));
+        let material_bind_group_layout: BindGroupLayout = render_state.device.as_ref().unwrap().create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Material Bind Group Layout"),
+            entries: &material_entries
+        });
+        let post_process_bind_group_layout: BindGroupLayout = render_state.device.as_ref().unwrap().create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Post Process Bind Group Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None
+                    },
+                    count: None
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        sample_type: TextureSampleType::Float {
+                            filterable: true
+                        }
+                    },
+                    count: None
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None
+                }
+            ]
+        });
 
         let render_pipeline_2d: RenderPipeline = render_state.get_render_pipeline(
             vec![Some(&rendering_type_bind_group_layout), Some(&texture_bind_group_layout), Some(&transform_bind_group_layout)],
+            &[Vertex::descriptor()],
             SHADER_2D
         );
+        let render_pipeline_2d_instanced: RenderPipeline = render_state.get_render_pipeline(
+            vec![Some(&texture_bind_group_layout), Some(&camera_bind_group_layout)],
+            &[Vertex::descriptor(), BatchInstance::descriptor()],
+            SHADER_2D_INSTANCED
+        );
 
         render_state.render_pipeline_2d = Some(render_pipeline_2d);
+        render_state.render_pipeline_2d_instanced = Some(render_pipeline_2d_instanced);
+        render_state.camera_bind_group_layout = Some(camera_bind_group_layout);
+        render_state.material_bind_group_layout = Some(material_bind_group_layout);
+        render_state.post_process_bind_group_layout = Some(post_process_bind_group_layout);
         render_state.rendering_type_bind_group_layout = Some(rendering_type_bind_group_layout);
         render_state.texture_bind_group_layout = Some(texture_bind_group_layout);
         render_state.transform_bind_group_layout = Some(transform_bind_group_layout);
         return render_state;
     }
 
+    /// Render a frame of the world into an offscreen texture and returns its pixels.
+    ///
+    /// Mainly for headless rendering, like golden image tests, but it works with a window as well.
+    pub fn render_to_image(&mut self, world: &mut World) -> Result<image::RgbaImage, HeadlessError> {
+        let physical_size: PhysicalSize<u32> = self.physical_size.unwrap();
+        let frame: texture::texture::Texture = texture::texture::Texture::render_target(
+            self.device.as_ref().unwrap(),
+            physical_size.width,
+            physical_size.height,
+            self.surface_configuration.as_ref().unwrap().format,
+            Some("Headless Frame")
+        );
+
+        super::executor::render_frame(self, world, &frame.texture_view);
+        let image: image::RgbaImage = headless::read_texture(self.device.as_ref().unwrap(), self.queue.as_ref().unwrap(), &frame.wgpu_texture)?;
+
+        let capture_paths: Vec<PathBuf> = self.take_capture_paths();
+        if !capture_paths.is_empty() {
+            self.frame_capturer.encode(image.clone(), capture_paths);
+        }
+        return Ok(image);
+    }
+
+    /// Capture the rendered frame for the requested screenshots and the frame recorder, before it gets presented.
+    ///
+    /// The frame is copied into a readback buffer without waiting for the copy to finish,
+    /// being collected and encoded on a later frame, so the rendering doesn't stall while capturing.
+    pub(crate) fn capture_frame(&mut self, texture: &wgpu::Texture) {
+        self.frame_capturer.collect(self.device.as_ref().unwrap(), false);
+
+        let capture_paths: Vec<PathBuf> = self.take_capture_paths();
+        if capture_paths.is_empty() {
+            return;
+        }
+
+        if !texture.usage().contains(TextureUsages::COPY_SRC) {
+            log::warn!("The surface doesn't support copies, the frame can't be captured.");
+            return;
+        }
+        self.frame_capturer.capture(self.device.as_ref().unwrap(), self.queue.as_ref().unwrap(), texture, capture_paths);
+    }
+
+    /// Returns the paths where the current frame should be captured, consuming the screenshot requests.
+    ///
+    /// A finished recorder is kept until the recording is stopped, so its recorded frames can still be counted.
+    fn take_capture_paths(&mut self) -> Vec<PathBuf> {
+        let mut capture_paths: Vec<PathBuf> = self.screenshot_requests.drain(..).collect();
+
+        if let Some(frame_recorder) = self.frame_recorder.as_mut() {
+            if let Some(path) = frame_recorder.next_frame() {
+                capture_paths.push(path);
+
+                if frame_recorder.is_finished() {
+                    log::info!("Frame recording finished with {} frames.", frame_recorder.frames_recorded);
+                }
+            }
+        }
+        return capture_paths;
+    }
+
     /// Execute the rendering process.
     pub(crate) fn prepare(&mut self, world: &mut World, event_loop: &ActiveEventLoop) {
         match self.surface.as_ref().unwrap().get_current_texture() {
@@ -310,7 +597,7 @@ impl RenderState {
     }
 
     /// Resize the rendering projection.
-    pub(crate) fn resize(&mut self, new_size: PhysicalSize<u32>, camera2d: &Camera2d, text_renderers: &HashMap<Uuid, TextRenderer>) {
+    pub(crate) fn resize(&mut self, new_size: PhysicalSize<u32>, camera2d: &Camera2d, text_renderers: &HashMap<Entity, TextRenderer>) {
         if new_size.width > 0 && new_size.height > 0 {
             self.physical_size = Some(new_size);
             self.surface_configuration.as_mut().unwrap().width = new_size.width;
@@ -339,7 +626,7 @@ impl RenderState {
     pub(crate) fn text(&mut self, world: &mut World) {
         for entity in self.entities_to_render.clone() {
             if world.is_entity_alive(entity) && world.is_entity_visible(entity) {
-                if let Some(text_renderer) = world.get_resource_mut::<TextHolder>().unwrap().text_renderers.get_mut(&entity.0) {
+                if let Some(text_renderer) = world.get_resource_mut::<TextHolder>().unwrap().text_renderers.get_mut(&entity) {
                     let (x, y): (f32, f32) = text_renderer.text.get_position_by_strategy(&self.physical_size.as_ref().unwrap());
                     let width: f32 = self.physical_size.as_ref().unwrap().width as f32;
                     let height: f32 = self.physical_size.as_ref().unwrap().height as f32;
@@ -375,14 +662,63 @@ impl RenderState {
         }
     }
 
+    /// Returns a texture from the cache, loading it if needed, owned by the entity that renders it.
+    fn get_texture(&mut self, key: &str, entity: Option<&Entity>) -> Arc<texture::texture::Texture> {
+        self.texture_cache.acquire(key, entity);
+
+        if let Some(texture_from_cache) = self.texture_cache.get_texture(key.to_string()) {
+            return texture_from_cache;
+        }
+        return self.texture_cache.load_texture(
+            key.to_string(),
+            self.device.as_ref().unwrap(),
+            self.queue.as_ref().unwrap()
+        ).unwrap();
+    }
+
+    /// Helper function to release every rendering resource related to the entity.
+    ///
+    /// Its cached buffers and bind groups are cleaned and the textures that no other entity uses are evicted.
+    pub(crate) fn release_entity_resources(&mut self, entity: &Entity) {
+        self.clean_entity_buffer_cache(entity);
+        self.clean_entity_bind_group_cache(entity);
+        self.release_entity_textures(entity);
+    }
+
+    /// Helper function to release the textures owned by the entity.
+    ///
+    /// The batch bind groups of the evicted textures are cleaned with them.
+    pub(crate) fn release_entity_textures(&mut self, entity: &Entity) {
+        for key in self.texture_cache.release(entity) {
+            self.bind_group_cache.cache.remove(&(FIXED_ID, format!("{}_{}", BATCH_TEXTURE_BIND_GROUP, key)));
+        }
+    }
+
+    /// Returns the memory used by the rendering resources of the engine.
+    pub fn memory_stats(&self, world: &World) -> MemoryStats {
+        let (fonts, text_brushes): (usize, usize) = world.get_resource::<TextHolder>()
+            .map(|text_holder| (text_holder.fonts.len(), text_holder.text_renderers.len()))
+            .unwrap_or_default();
+
+        return MemoryStats {
+            textures: self.texture_cache.len(),
+            texture_bytes: self.texture_cache.size_in_bytes(),
+            buffers: self.buffer_cache.cache.len(),
+            buffer_bytes: self.buffer_cache.cache.values().map(|buffer| buffer.size()).sum(),
+            bind_groups: self.bind_group_cache.cache.len(),
+            fonts,
+            text_brushes
+        };
+    }
+
     /// Helper function to clean the Buffer cache related to the entity.
     pub(crate) fn clean_entity_buffer_cache(&mut self, entity: &Entity) {
-        self.buffer_cache.clean(entity.0.to_string());
+        self.buffer_cache.clean(entity.to_bits());
     }
 
     /// Helper function to clean the Bind Group cache related to the entity.
     pub(crate) fn clean_entity_bind_group_cache(&mut self, entity: &Entity) {
-        self.bind_group_cache.clean(entity.0.to_string());
+        self.bind_group_cache.clean(entity.to_bits());
     }
 
     /// Placeholder function for the input functionality.
@@ -400,6 +736,7 @@ impl RenderState {
         sprite: Option<&Sprite>,
         shape: Option<&Shape>,
         transform: Option<&Transform>,
+        global_transform: Option<&GlobalTransform>,
         animation: Option<&Animation>,
         camera2d: &Camera2d,
         is_background: bool
@@ -410,6 +747,7 @@ impl RenderState {
                 entity,
                 sprite,
                 transform,
+                global_transform,
                 camera2d,
                 is_background
             );
@@ -419,6 +757,7 @@ impl RenderState {
                 entity,
                 animation,
                 transform,
+                global_transform,
                 camera2d,
                 is_background
             );
@@ -428,6 +767,7 @@ impl RenderState {
                 entity,
                 shape,
                 transform,
+                global_transform,
                 camera2d
             );
         }
@@ -440,20 +780,11 @@ impl RenderState {
         entity: Option<&Entity>,
         sprite: &Sprite,
         transform: Option<&Transform>,
+        global_transform: Option<&GlobalTransform>,
         camera2d: &Camera2d,
         is_background: bool
     ) {
-        let texture: Arc<texture::texture::Texture> = {
-            if let Some(texture_from_cache) = self.texture_cache.get_texture(sprite.path.clone()) {
-                texture_from_cache
-            } else {
-                self.texture_cache.load_texture(
-                    sprite.path.clone(),
-                    &self.device.as_ref().unwrap(),
-                    &self.queue.as_ref().unwrap()
-                ).unwrap()
-            }
-        };
+        let texture: Arc<texture::texture::Texture> = self.get_texture(&sprite.path, entity);
         let rendering_type_buffer: Buffer = cache::buffer::get_conditional_buffer(
             self,
             RENDERING_TYPE_BUFFER,
@@ -476,6 +807,7 @@ impl RenderState {
             event_dispatcher,
             entity,
             transform,
+            global_transform,
             None,
             None,
             Some(texture.as_ref()),
@@ -506,23 +838,14 @@ impl RenderState {
         entity: Option<&Entity>,
         animation: &Animation,
         transform: Option<&Transform>,
+        global_transform: Option<&GlobalTransform>,
         camera2d: &Camera2d,
         is_background: bool
     ) {
         let sprite_sheet: Option<&SpriteSheet> = animation.get_playing_animation_now();
 
         if let Some(sprite_sheet) = sprite_sheet {
-            let texture: Arc<texture::texture::Texture> = {
-                if let Some(texture_from_cache) = self.texture_cache.get_texture(sprite_sheet.path.clone()) {
-                    texture_from_cache
-                } else {
-                    self.texture_cache.load_texture(
-                        sprite_sheet.path.clone(),
-                        &self.device.as_ref().unwrap(),
-                        &self.queue.as_ref().unwrap()
-                    ).unwrap()
-                }
-            };
+            let texture: Arc<texture::texture::Texture> = self.get_texture(&sprite_sheet.path, entity);
             let rendering_type_buffer: Buffer = cache::buffer::get_conditional_buffer(
                 self,
                 RENDERING_TYPE_BUFFER,
@@ -554,6 +877,7 @@ impl RenderState {
                 event_dispatcher,
                 entity,
                 transform,
+                global_transform,
                 Some(sprite_sheet.tile_width),
                 Some(sprite_sheet.tile_height),
                 Some(texture.as_ref()),
@@ -585,19 +909,10 @@ impl RenderState {
         entity: Option<&Entity>,
         shape: &Shape,
         transform: Option<&Transform>,
+        global_transform: Option<&GlobalTransform>,
         camera2d: &Camera2d
     ) {
-        let texture: Arc<texture::texture::Texture> = {
-            if let Some(texture_from_cache) = self.texture_cache.get_texture(DUMMY_TEXTURE.to_string()) {
-                texture_from_cache
-            } else {
-                self.texture_cache.load_texture(
-                    DUMMY_TEXTURE.to_string(),
-                    &self.device.as_ref().unwrap(),
-                    &self.queue.as_ref().unwrap()
-                ).unwrap()
-            }
-        };
+        let texture: Arc<texture::texture::Texture> = self.get_texture(DUMMY_TEXTURE, entity);
         let rendering_type_buffer: Buffer = cache::buffer::get_conditional_buffer(
             self,
             RENDERING_TYPE_BUFFER,
@@ -620,6 +935,7 @@ impl RenderState {
             event_dispatcher,
             entity,
             transform,
+            global_transform,
             None,
             None,
             None,
@@ -660,6 +976,7 @@ impl RenderState {
         event_dispatcher: &mut EventDispatcher,
         entity: Option<&Entity>,
         transform: Option<&Transform>,
+        global_transform: Option<&GlobalTransform>,
         tile_width: Option<f32>,
         tile_height: Option<f32>,
         texture: Option<&texture::texture::Texture>,
@@ -677,67 +994,660 @@ impl RenderState {
             entity,
             camera2d
         );
+        let transform_matrix: Matrix4<f32> = self.get_transform_matrix(
+            event_dispatcher,
+            entity,
+            transform,
+            global_transform,
+            tile_width.zip(tile_height),
+            texture
+        );
+        let transform_unwrapped: [[f32; 4]; 4] = *transform_matrix.as_ref();
+        let transform_buffer: Buffer = cache::buffer::get_transform_buffer(self, entity, transform_unwrapped);
+        self.transform_buffer = Some(transform_buffer);
+
+        let transform_bind_group: BindGroup = cache::bind_group::get_transform_bind_group(
+            self,
+            entity,
+            self.transform_buffer.as_ref().unwrap().clone(),
+            projection_buffer.clone(),
+            view_buffer.clone()
+        );
+        return (transform_bind_group, projection_buffer, view_buffer);
+    }
+
+    /// Returns the transform matrix of an entity, converting its pixelated position and scale when needed.
+    pub(crate) fn get_transform_matrix(
+        &self,
+        event_dispatcher: &mut EventDispatcher,
+        entity: Option<&Entity>,
+        transform: Option<&Transform>,
+        global_transform: Option<&GlobalTransform>,
+        tile_size: Option<(f32, f32)>,
+        texture: Option<&texture::texture::Texture>
+    ) -> Matrix4<f32> {
+        let Some(transform_unwrapped) = transform else {
+            return Matrix4::identity();
+        };
         let (width, height): (f32, f32) = (
             (self.physical_size.as_ref().unwrap().width as f32),
             (self.physical_size.as_ref().unwrap().height as f32)
         );
         let aspect_ratio: f32 = width / height;
+        let mut transform_cloned: Transform = transform_unwrapped.clone();
+
+        if transform_cloned.position.strategy == Strategy::Pixelated && transform_cloned.dirty_position {
+            let normalized_x: f32 = transform_cloned.position.x / width * 2.0 * aspect_ratio - aspect_ratio;
+            let normalized_y: f32 = -(transform_cloned.position.y / height * 2.0 - 1.0);
+
+            transform_cloned.position.x = normalized_x;
+            transform_cloned.position.y = normalized_y;
 
-        if let Some(transform_unwrapped) = transform {
-            let mut transform_cloned: Transform = transform_unwrapped.clone();
+            event_dispatcher.send(Event::new(*entity.unwrap(), EventType::Transform(SubEventType::UpdatePixelatedPosition), transform_cloned.position.to_vec()));
+        }
 
-            if transform_cloned.position.strategy == Strategy::Pixelated && transform_cloned.dirty_position {
-                let normalized_x: f32 = transform_cloned.position.x / width * 2.0 * aspect_ratio - aspect_ratio;
-                let normalized_y: f32 = -(transform_cloned.position.y / height * 2.0 - 1.0);
+        if let Some(texture) = texture {
+            if transform_cloned.dirty_scale {
+                let width_in_pixels: f32 = texture.wgpu_texture.size().width as f32;
+                let height_in_pixels: f32 = texture.wgpu_texture.size().height as f32;
+                let world_width: f32;
+                let world_height: f32;
 
-                transform_cloned.position.x = normalized_x;
-                transform_cloned.position.y = normalized_y;
+                if let Some((tile_width, tile_height)) = tile_size {
+                    world_width = (tile_width / width) * 1.0 * aspect_ratio;
+                    world_height = (tile_height / height) * 1.0;
+                } else {
+                    world_width = (width_in_pixels / width) * 1.0 * aspect_ratio;
+                    world_height = (height_in_pixels / height) * 1.0;
+                }
+                transform_cloned.scale.x *= world_width;
+                transform_cloned.scale.y *= world_height;
 
-                event_dispatcher.send(Event::new(*entity.unwrap(), EventType::Transform(SubEventType::UpdatePixelatedPosition), transform_cloned.position.to_vec()));
+                event_dispatcher.send(Event::new(*entity.unwrap(), EventType::Transform(SubEventType::UpdatePixelatedScale), transform_cloned.scale));
             }
+        }
 
-            if let Some(texture) = texture {
-                if transform_cloned.dirty_scale {
-                    let width_in_pixels: f32 = texture.wgpu_texture.size().width as f32;
-                    let height_in_pixels: f32 = texture.wgpu_texture.size().height as f32;
-                    let world_width: f32;
-                    let world_height: f32;
-
-                    if let (Some(tile_width), Some(tile_height)) = (tile_width, tile_height) {
-                        world_width = (tile_width / width) * 1.0 * aspect_ratio;
-                        world_height = (tile_height / height) * 1.0;
-                    } else {
-                        world_width = (width_in_pixels / width) * 1.0 * aspect_ratio;
-                        world_height = (height_in_pixels / height) * 1.0;
-                    }
-                    transform_cloned.scale.x *= world_width;
-                    transform_cloned.scale.y *= world_height;
+        let mut transform_matrix: Matrix4<f32> = transform_cloned.to_matrix();
+
+        if let Some(global_transform) = global_transform {
+            transform_matrix = global_transform.parent_matrix * transform_matrix;
+        }
+        return transform_matrix;
+    }
+
+    /// Returns the batches of the frame, built from the entities sorted by their drawing order.
+    ///
+    /// Only consecutive entities sharing a texture and a geometry are grouped on the same instanced draw,
+    /// so the entities keep stacking like they do without batching.
+    pub(crate) fn batches(
+        &mut self,
+        world: &World,
+        event_dispatcher: &mut EventDispatcher,
+        text_holder: &TextHolder,
+        entities: &[Entity]
+    ) -> BatchBuilder {
+        let mut batch_builder: BatchBuilder = BatchBuilder::new();
+
+        for entity in entities {
+            if !world.is_entity_alive(*entity) || !world.is_entity_visible(*entity) {
+                continue;
+            }
+            let Some((transform, global_transform, animation, sprite, shape, material, particle_emitter)) = world.query_entity::<(
+                Option<&Transform>,
+                Option<&GlobalTransform>,
+                Option<&Animation>,
+                Option<&Sprite>,
+                Option<&Shape>,
+                Option<&Material>,
+                Option<&ParticleEmitter>
+            )>(entity) else {
+                continue;
+            };
+            let transform: Option<&Transform> = transform.as_deref();
+            let global_transform: Option<&GlobalTransform> = global_transform.as_deref();
 
-                    event_dispatcher.send(Event::new(*entity.unwrap(), EventType::Transform(SubEventType::UpdatePixelatedScale), transform_cloned.scale));
+            if let Some(particle_emitter) = particle_emitter.as_deref() {
+                self.push_particles(&mut batch_builder, entity, particle_emitter, transform, global_transform);
+                continue;
+            }
+            let sprite_sheet: Option<&SpriteSheet> = animation.as_deref()
+                .filter(|animation| !animation.playing_stack.is_empty())
+                .and_then(|animation| animation.get_playing_animation_now());
+
+            let (texture_key, geometry, instance): (String, BatchGeometry, BatchInstance) = if let Some(sprite_sheet) = sprite_sheet {
+                let texture: Arc<texture::texture::Texture> = self.get_texture(&sprite_sheet.path, Some(entity));
+                let transform_matrix: Matrix4<f32> = self.get_transform_matrix(
+                    event_dispatcher,
+                    Some(entity),
+                    transform,
+                    global_transform,
+                    Some((sprite_sheet.tile_width, sprite_sheet.tile_height)),
+                    Some(texture.as_ref())
+                );
+                let uv_coordinates: [f32; 8] = sprite_sheet.current_tile_uv_coordinates();
+                let uv_rect: [f32; 4] = [
+                    uv_coordinates[0],
+                    uv_coordinates[5],
+                    uv_coordinates[2] - uv_coordinates[0],
+                    uv_coordinates[1] - uv_coordinates[5]
+                ];
+
+                (sprite_sheet.path.clone(), BatchGeometry::Quad, BatchInstance::new(
+                    *transform_matrix.as_ref(),
+                    uv_rect,
+                    ColorOption::White.to_rgba(),
+                    RenderingType::Texture.to_shader_index()
+                ))
+            } else if let Some(sprite) = sprite.as_deref() {
+                let texture: Arc<texture::texture::Texture> = self.get_texture(&sprite.path, Some(entity));
+                let transform_matrix: Matrix4<f32> = self.get_transform_matrix(
+                    event_dispatcher,
+                    Some(entity),
+                    transform,
+                    global_transform,
+                    None,
+                    Some(texture.as_ref())
+                );
+
+                (sprite.path.clone(), BatchGeometry::Quad, BatchInstance::new(
+                    *transform_matrix.as_ref(),
+                    [0.0, 0.0, 1.0, 1.0],
+                    ColorOption::White.to_rgba(),
+                    RenderingType::Texture.to_shader_index()
+                ))
+            } else if let Some(shape) = shape.as_deref() {
+                self.get_texture(DUMMY_TEXTURE, Some(entity));
+                let transform_matrix: Matrix4<f32> = self.get_transform_matrix(
+                    event_dispatcher,
+                    Some(entity),
+                    transform,
+                    global_transform,
+                    None,
+                    None
+                );
+
+                (DUMMY_TEXTURE.to_string(), BatchGeometry::Shape(shape.geometry_type.clone()), BatchInstance::new(
+                    *transform_matrix.as_ref(),
+                    [0.0, 0.0, 1.0, 1.0],
+                    shape.color.to_array(),
+                    RenderingType::Shape.to_shader_index()
+                ))
+            } else {
+                if text_holder.text_renderers.contains_key(entity) {
+                    batch_builder.push_text(*entity);
                 }
+                continue;
+            };
+
+            match material.as_deref().filter(|material| self.prepare_material(entity, material)) {
+                Some(material) => batch_builder.push_material(&texture_key, geometry, BatchMaterial {
+                    entity: *entity,
+                    shader: material.shader.clone(),
+                    textures: material.bound_textures().to_vec()
+                }, instance),
+                None => batch_builder.push(&texture_key, geometry, instance)
             }
+        }
+        return batch_builder;
+    }
+
+    /// Add the living particles of an emitter to the batches, as instances sharing its texture and geometry.
+    ///
+    /// The size of a particle scales its geometry, with the textured ones keeping the aspect ratio of their texture.
+    fn push_particles(
+        &mut self,
+        batch_builder: &mut BatchBuilder,
+        entity: &Entity,
+        particle_emitter: &ParticleEmitter,
+        transform: Option<&Transform>,
+        global_transform: Option<&GlobalTransform>
+    ) {
+        if particle_emitter.particles.is_empty() {
+            return;
+        }
+        let texture: Arc<texture::texture::Texture> = self.get_texture(particle_emitter.appearance.texture_key(), Some(entity));
+        let aspect_ratio: f32 = match particle_emitter.appearance {
+            ParticleAppearance::Texture(_) => texture.wgpu_texture.size().width as f32 / texture.wgpu_texture.size().height as f32,
+            ParticleAppearance::Shape(_) => 1.0
+        };
+        let space_matrix: Matrix4<f32> = match (particle_emitter.space, transform) {
+            (ParticleSpace::Local, Some(transform)) => {
+                let position: Vector2<f32> = self.get_world_position(transform, global_transform);
+                Matrix4::from_translation(Vector3::new(position.x, position.y, 0.0)) * Matrix4::from_angle_z(Deg(transform.rotation))
+            },
+            _ => Matrix4::identity()
+        };
+        let geometry: BatchGeometry = particle_emitter.appearance.geometry();
+        let rendering_type: u32 = particle_emitter.appearance.rendering_type();
 
-            let transform_unwrapped: [[f32; 4]; 4] = *transform_cloned.to_matrix().as_ref();
-            let transform_buffer: Buffer = cache::buffer::get_transform_buffer(self, entity, transform_unwrapped);
-            self.transform_buffer = Some(transform_buffer);
+        for particle in &particle_emitter.particles {
+            let progress: f32 = particle.progress();
+            let size: f32 = particle_emitter.size.sample(progress);
+            let transform_matrix: Matrix4<f32> = space_matrix
+                * Matrix4::from_translation(Vector3::new(particle.position.x, particle.position.y, 0.0))
+                * Matrix4::from_nonuniform_scale(size * aspect_ratio, size, 1.0);
+
+            batch_builder.push(particle_emitter.appearance.texture_key(), geometry.clone(), BatchInstance::new(
+                *transform_matrix.as_ref(),
+                [0.0, 0.0, 1.0, 1.0],
+                particle_emitter.color.sample(progress),
+                rendering_type
+            ));
+        }
+    }
+
+    /// Returns the pipeline of a material shader, creating and caching it on the first use.
+    ///
+    /// A shader that can't be loaded or compiled is logged and cached as missing, so its entities are drawn without it.
+    pub(crate) fn get_material_pipeline(&mut self, material: &Material) -> Option<RenderPipeline> {
+        if let Some(render_pipeline) = self.pipeline_cache.get(&material.shader) {
+            return render_pipeline.clone();
+        }
+        let render_pipeline: Option<RenderPipeline> = self.get_checked_render_pipeline(
+            "Material",
+            &material.shader,
+            material.load_source(),
+            vec![
+                Some(self.texture_bind_group_layout.as_ref().unwrap()),
+                Some(self.camera_bind_group_layout.as_ref().unwrap()),
+                Some(self.material_bind_group_layout.as_ref().unwrap())
+            ],
+            &[Vertex::descriptor(), BatchInstance::descriptor()]
+        );
+        self.pipeline_cache.insert(material.shader.clone(), render_pipeline.clone());
+        return render_pipeline;
+    }
+
+    /// Returns the pipeline of a post-processing effect, creating and caching it on the first use.
+    ///
+    /// A shader that can't be loaded or compiled is logged and cached as missing, so its effect is skipped.
+    pub(crate) fn get_post_process_pipeline(&mut self, effect: &PostEffect) -> Option<RenderPipeline> {
+        if let Some(render_pipeline) = self.pipeline_cache.get(effect.shader_key()) {
+            return render_pipeline.clone();
+        }
+        let render_pipeline: Option<RenderPipeline> = self.get_checked_render_pipeline(
+            "Post process",
+            effect.shader_key(),
+            effect.load_source(),
+            vec![
+                Some(self.texture_bind_group_layout.as_ref().unwrap()),
+                Some(self.post_process_bind_group_layout.as_ref().unwrap())
+            ],
+            &[]
+        );
+        self.pipeline_cache.insert(effect.shader_key().to_string(), render_pipeline.clone());
+        return render_pipeline;
+    }
+
+    /// Returns the post-processing effects of the frame that are ready to be applied.
+    ///
+    /// The offscreen targets are created or resized to match the window when there is any effect.
+    pub(crate) fn prepare_post_process(&mut self, world: &World) -> Vec<PostEffect> {
+        let effects: Vec<PostEffect> = world.get_resource::<PostProcess>()
+            .map(|post_process| post_process.active_effects().to_vec())
+            .unwrap_or_default();
+        let effects: Vec<PostEffect> = effects.into_iter()
+            .filter(|effect| self.get_post_process_pipeline(effect).is_some())
+            .collect();
+
+        if !effects.is_empty() {
+            self.prepare_render_targets();
+        }
+        return effects;
+    }
+
+    /// Create or resize the two offscreen targets of the frame to match the window.
+    fn prepare_render_targets(&mut self) {
+        let (width, height): (u32, u32) = (self.physical_size.unwrap().width, self.physical_size.unwrap().height);
+
+        if self.render_targets.len() != 2 || self.render_targets.iter().any(|render_target| !render_target.has_size(width, height)) {
+            let format: TextureFormat = self.surface_configuration.as_ref().unwrap().format;
+
+            self.render_targets = (0..2)
+                .map(|index| RenderTarget::new(
+                    self.device.as_ref().unwrap(),
+                    self.texture_bind_group_layout.as_ref().unwrap(),
+                    width,
+                    height,
+                    format,
+                    &format!("Frame Target {}", index)
+                ))
+                .collect();
+        }
+    }
+
+    /// Prepare the lighting of the frame, returning the normal maps of the sprite textures when it is active.
+    ///
+    /// The lighting is active while the world has an ambient light resource or any active point light.
+    /// The lights and the occluders are placed on the screen through the global camera.
+    pub(crate) fn prepare_lighting(&mut self, world: &World) -> Option<HashMap<String, String>> {
+        let point_lights: Vec<(PointLight2d, Vector2<f32>)> = world.query::<(Entity, &PointLight2d, &Transform, Option<&GlobalTransform>)>()
+            .filter(|(entity, point_light, _, _)| point_light.active && world.is_entity_visible(*entity))
+            .map(|(_, point_light, transform, global_transform)| (point_light.clone(), self.get_world_position(&transform, global_transform.as_deref())))
+            .collect();
+        let ambient_light: Option<AmbientLight> = world.get_resource::<AmbientLight>().map(|ambient_light| ambient_light.clone());
+
+        if point_lights.is_empty() && ambient_light.is_none() {
+            return None;
+        }
+        self.prepare_render_targets();
+        self.prepare_lighting_pipelines();
+
+        let (width, height): (u32, u32) = (self.physical_size.unwrap().width, self.physical_size.unwrap().height);
+        if self.normal_target.as_ref().is_none_or(|normal_target| !normal_target.has_size(width, height)) {
+            self.normal_target = Some(RenderTarget::new(
+                self.device.as_ref().unwrap(),
+                self.texture_bind_group_layout.as_ref().unwrap(),
+                width,
+                height,
+                self.surface_configuration.as_ref().unwrap().format,
+                "Normal Target"
+            ));
+            self.bind_group_cache.cache.remove(&(FIXED_ID, LIGHTING_BIND_GROUP.to_string()));
+        }
+
+        let camera2d: ResourceRef<'_, Camera2d> = world.get_resource::<Camera2d>().unwrap();
+        let projection_view: Matrix4<f32> = self.get_projection_matrix(&camera2d) * camera2d.view_matrix;
+        let to_screen = |position: Vector2<f32>| -> [f32; 2] {
+            let clip_position: Vector4<f32> = projection_view * Vector4::new(position.x, position.y, 0.0, 1.0);
+            return [(clip_position.x + 1.0) * 0.5 * width as f32, (1.0 - clip_position.y) * 0.5 * height as f32];
+        };
+        let (origin, unit): ([f32; 2], [f32; 2]) = (to_screen(Vector2::new(0.0, 0.0)), to_screen(Vector2::new(0.0, 1.0)));
+        let pixels_per_unit: f32 = (unit[0] - origin[0]).hypot(unit[1] - origin[1]);
+        let mut lighting_uniforms: LightingUniforms = LightingUniforms::new(&ambient_light.unwrap_or_default(), [width as f32, height as f32]);
+
+        for (point_light, position) in &point_lights {
+            lighting_uniforms.push_light(point_light, to_screen(*position), point_light.radius * pixels_per_unit, point_light.height * pixels_per_unit);
+        }
+
+        for (occluder, collision) in world.query::<(&Occluder, &Collision)>() {
+            if occluder.active {
+                for (start, end) in Occluder::edges(&collision.collider) {
+                    lighting_uniforms.push_edge(to_screen(start), to_screen(end));
+                }
+            }
+        }
+        let lighting_buffer: Buffer = cache::buffer::get_lighting_buffer(self, lighting_uniforms);
+        let normals: Arc<texture::texture::Texture> = self.normal_target.as_ref().unwrap().texture.clone();
+        cache::bind_group::get_lighting_bind_group(self, lighting_buffer, normals.as_ref());
+
+        let sprites: Vec<(Entity, String, String)> = world.query::<(Entity, &Sprite)>()
+            .filter_map(|(entity, sprite)| sprite.normal_map.clone().map(|normal_map| (entity, sprite.path.clone(), normal_map)))
+            .collect();
+        let mut normal_maps: HashMap<String, String> = HashMap::new();
+
+        for (entity, path, normal_map) in sprites {
+            let _ = self.get_texture(&normal_map, Some(&entity));
+            normal_maps.insert(path, normal_map);
+        }
+        return Some(normal_maps);
+    }
+
+    /// Create and cache the built-in pipelines of the lighting on the first use.
+    fn prepare_lighting_pipelines(&mut self) {
+        if self.pipeline_cache.contains_key(LIGHTING_PIPELINE) {
+            return;
+        }
+        let lighting_pipeline: RenderPipeline = self.get_render_pipeline(
+            vec![Some(self.texture_bind_group_layout.as_ref().unwrap()), Some(self.post_process_bind_group_layout.as_ref().unwrap())],
+            &[],
+            LIGHTING_SHADER
+        );
+        let normal_map_pipeline: RenderPipeline = self.get_render_pipeline(
+            vec![
+                Some(self.texture_bind_group_layout.as_ref().unwrap()),
+                Some(self.camera_bind_group_layout.as_ref().unwrap()),
+                Some(self.texture_bind_group_layout.as_ref().unwrap())
+            ],
+            &[Vertex::descriptor(), BatchInstance::descriptor()],
+            NORMAL_MAP_SHADER
+        );
+
+        self.pipeline_cache.insert(LIGHTING_PIPELINE.to_string(), Some(lighting_pipeline));
+        self.pipeline_cache.insert(NORMAL_MAP_PIPELINE.to_string(), Some(normal_map_pipeline));
+    }
+
+    /// Returns the position of a transform in world units, as it is rendered.
+    pub(crate) fn get_world_position(&self, transform: &Transform, global_transform: Option<&GlobalTransform>) -> Vector2<f32> {
+        let mut position: Vector2<f32> = transform.position.to_vec();
+
+        if transform.position.strategy == Strategy::Pixelated && transform.dirty_position {
+            let (width, height): (f32, f32) = (self.physical_size.unwrap().width as f32, self.physical_size.unwrap().height as f32);
+            let aspect_ratio: f32 = width / height;
+
+            position = Vector2::new(position.x / width * 2.0 * aspect_ratio - aspect_ratio, -(position.y / height * 2.0 - 1.0));
+        }
+
+        if let Some(global_transform) = global_transform {
+            let global_position: Vector4<f32> = global_transform.parent_matrix * Vector4::new(position.x, position.y, 0.0, 1.0);
+            position = Vector2::new(global_position.x, global_position.y);
+        }
+        return position;
+    }
+
+    /// Apply the lighting on the frame rendered on the first offscreen target, writing it on a destination.
+    pub(crate) fn apply_lighting(&mut self, command_encoder: &mut CommandEncoder, destination: &TextureView) {
+        let render_pipeline: RenderPipeline = self.pipeline_cache.get(LIGHTING_PIPELINE).cloned().flatten()
+            .expect("Lighting pipeline should be on cache.");
+        let lighting_bind_group: BindGroup = self.bind_group_cache.find((FIXED_ID, LIGHTING_BIND_GROUP.to_string()))
+            .expect("Lighting bind group should be on cache.");
+        let mut render_pass: RenderPass<'_> = super::executor::begin_render_pass(command_encoder, destination, LoadOp::Clear(wgpu::Color::BLACK), "Lighting Pass");
+
+        render_pass.set_pipeline(&render_pipeline);
+        render_pass.set_bind_group(0, &self.render_targets[0].bind_group, &[]);
+        render_pass.set_bind_group(1, &lighting_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    /// Create or resize the offscreen target of a camera, registering its texture on the cache by its name.
+    pub(crate) fn prepare_camera_target(&mut self, name: &str, width: u32, height: u32) {
+        if self.camera_targets.get(name).is_some_and(|render_target| render_target.has_size(width, height)) {
+            return;
+        }
+        let render_target: RenderTarget = RenderTarget::new(
+            self.device.as_ref().unwrap(),
+            self.texture_bind_group_layout.as_ref().unwrap(),
+            width,
+            height,
+            self.surface_configuration.as_ref().unwrap().format,
+            name
+        );
+
+        self.texture_cache.insert_texture(name.to_string(), render_target.texture.clone());
+        self.bind_group_cache.cache.remove(&(FIXED_ID, format!("{}_{}", BATCH_TEXTURE_BIND_GROUP, name)));
+        self.camera_targets.insert(name.to_string(), render_target);
+    }
+
+    /// Add an instance covering the whole viewport of a camera with its clear color, returning its own batch.
+    pub(crate) fn camera_clear_batch(&mut self, batch_builder: &mut BatchBuilder, color: [f32; 4], projection: Matrix4<f32>, view: Matrix4<f32>) -> Batch {
+        let _ = self.get_texture(DUMMY_TEXTURE, None);
+        let transform_matrix: Matrix4<f32> = (projection * view).invert().unwrap_or(Matrix4::identity());
+
+        return batch_builder.push_detached(DUMMY_TEXTURE, BatchGeometry::Shape(GeometryType::Square), BatchInstance::new(
+            *transform_matrix.as_ref(),
+            [0.0, 0.0, 1.0, 1.0],
+            color,
+            RenderingType::Shape.to_shader_index()
+        ));
+    }
+
+    /// Apply the post-processing effects on the frame rendered on an offscreen target, the first one unless the frame was lit.
+    ///
+    /// Each effect reads the output of the previous one, and the last effect writes on the window.
+    pub(crate) fn apply_post_process(&mut self, command_encoder: &mut CommandEncoder, effects: &[PostEffect], first_target: usize, surface_view: &TextureView) {
+        let resolution: (u32, u32) = (self.physical_size.unwrap().width, self.physical_size.unwrap().height);
+
+        for (index, effect) in effects.iter().enumerate() {
+            let render_pipeline: RenderPipeline = self.pipeline_cache.get(effect.shader_key()).cloned().flatten()
+                .expect("Post process pipeline should be on cache.");
+            let texture_key: &str = effect.texture().unwrap_or(DUMMY_TEXTURE);
+            let texture: Arc<texture::texture::Texture> = self.get_texture(texture_key, None);
+            let post_process_buffer: Buffer = cache::buffer::get_post_process_buffer(self, index, effect.to_uniforms(self.elapsed_time, resolution));
+            let post_process_bind_group: BindGroup = cache::bind_group::get_post_process_bind_group(
+                self,
+                index,
+                texture_key,
+                post_process_buffer,
+                texture.as_ref()
+            );
+            let destination: &TextureView = if index == effects.len() - 1 {
+                surface_view
+            } else {
+                self.render_targets[(first_target + index + 1) % 2].view()
+            };
+
+            let mut render_pass: RenderPass<'_> = command_encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("Post Process Pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    depth_slice: None,
+                    view: destination,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(wgpu::Color::BLACK),
+                        store: StoreOp::Store
+                    }
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+                multiview_mask: None
+            });
+            render_pass.set_pipeline(&render_pipeline);
+            render_pass.set_bind_group(0, &self.render_targets[(first_target + index) % 2].bind_group, &[]);
+            render_pass.set_bind_group(1, &post_process_bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+    }
+
+    /// Prepare the pipeline, uniforms and extra textures of the material of an entity.
+    ///
+    /// Returns if the material is ready to be used.
+    pub(crate) fn prepare_material(&mut self, entity: &Entity, material: &Material) -> bool {
+        if self.get_material_pipeline(material).is_none() {
+            return false;
+        }
+        let textures: Vec<Arc<texture::texture::Texture>> = (0..MAX_MATERIAL_TEXTURES)
+            .map(|index| self.get_texture(material.bound_textures().get(index).map(String::as_str).unwrap_or(DUMMY_TEXTURE), Some(entity)))
+            .collect();
+        let material_buffer: Buffer = cache::buffer::get_material_buffer(self, entity, material.to_uniforms(self.elapsed_time));
+
+        cache::bind_group::get_material_bind_group(self, entity, material.bound_textures(), material_buffer, &textures);
+        return true;
+    }
+
+    /// Write the instances of the frame on the instance buffer, growing it when needed.
+    pub(crate) fn write_instances(&mut self, instances: &[BatchInstance]) {
+        if instances.is_empty() {
+            return;
+        }
+        let size: BufferAddress = std::mem::size_of_val(instances) as BufferAddress;
+
+        if self.instance_buffer.as_ref().is_none_or(|instance_buffer| instance_buffer.size() < size) {
+            self.instance_buffer = Some(self.device.as_ref().unwrap().create_buffer(&BufferDescriptor {
+                label: Some("Instance Buffer"),
+                size: size.next_power_of_two(),
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false
+            }));
+        }
+        self.queue.as_ref().unwrap().write_buffer(self.instance_buffer.as_ref().unwrap(), 0, bytemuck::cast_slice(instances));
+    }
+
+    /// Apply render pass with the values of a batch and render all of its instances.
+    ///
+    /// The camera bind group is shared by every batch of the frame.
+    pub(crate) fn render_batch(&mut self, render_pass: &mut RenderPass<'_>, batch: &Batch, camera_bind_group: &BindGroup) {
+        let texture: Arc<texture::texture::Texture> = self.texture_cache.get_texture(batch.texture.clone())
+            .expect("Texture should be on cache.");
+        let texture_bind_group: BindGroup = cache::bind_group::get_batch_texture_bind_group(self, &batch.texture, texture.as_ref());
+
+        render_pass.set_bind_group(0, &texture_bind_group, &[]);
+        render_pass.set_bind_group(1, camera_bind_group, &[]);
+
+        if let Some(material) = &batch.material {
+            let render_pipeline: RenderPipeline = self.pipeline_cache.get(&material.shader).cloned().flatten()
+                .expect("Material pipeline should be on cache.");
+            let material_bind_group: BindGroup = self.bind_group_cache.find(cache::bind_group::material_bind_group_key(&material.entity, &material.textures))
+                .expect("Material bind group should be on cache.");
+
+            render_pass.set_pipeline(&render_pipeline);
+            render_pass.set_bind_group(2, &material_bind_group, &[]);
         } else {
-            let identity_matrix: Matrix4<f32> = Matrix4::identity();
-            let identity_matrix_unwrapped: [[f32; 4]; 4] = *identity_matrix.as_ref();
-            let transform_buffer: Buffer = cache::buffer::get_transform_buffer(self, entity, identity_matrix_unwrapped);
-            self.transform_buffer = Some(transform_buffer);
+            render_pass.set_pipeline(self.render_pipeline_2d_instanced.as_ref().unwrap());
         }
+        self.draw_batch(render_pass, batch);
+    }
 
-        let transform_bind_group: BindGroup = cache::bind_group::get_transform_bind_group(
+    /// Apply render pass with the normal map of a batch and render all of its instances into the normals of the frame.
+    ///
+    /// The texture of the batch is still bound, so its transparent pixels keep the normals behind them.
+    pub(crate) fn render_normal_batch(&mut self, render_pass: &mut RenderPass<'_>, batch: &Batch, normal_map: &str, camera_bind_group: &BindGroup) {
+        let texture: Arc<texture::texture::Texture> = self.texture_cache.get_texture(batch.texture.clone())
+            .expect("Texture should be on cache.");
+        let normal_map_texture: Arc<texture::texture::Texture> = self.texture_cache.get_texture(normal_map.to_string())
+            .expect("Normal map should be on cache.");
+        let texture_bind_group: BindGroup = cache::bind_group::get_batch_texture_bind_group(self, &batch.texture, texture.as_ref());
+        let normal_map_bind_group: BindGroup = cache::bind_group::get_batch_texture_bind_group(self, normal_map, normal_map_texture.as_ref());
+        let render_pipeline: RenderPipeline = self.pipeline_cache.get(NORMAL_MAP_PIPELINE).cloned().flatten()
+            .expect("Normal map pipeline should be on cache.");
+
+        render_pass.set_pipeline(&render_pipeline);
+        render_pass.set_bind_group(0, &texture_bind_group, &[]);
+        render_pass.set_bind_group(1, camera_bind_group, &[]);
+        render_pass.set_bind_group(2, &normal_map_bind_group, &[]);
+        self.draw_batch(render_pass, batch);
+    }
+
+    /// Render all the instances of a batch with its geometry, after its pipeline and bind groups are set.
+    fn draw_batch(&mut self, render_pass: &mut RenderPass<'_>, batch: &Batch) {
+        let (vertex_buffer, index_buffer, number_of_indices): (Buffer, Buffer, u32) = cache::buffer::get_batch_vertex_and_index_buffers(
             self,
-            entity,
-            self.transform_buffer.as_ref().unwrap().clone(),
-            projection_buffer.clone(),
-            view_buffer.clone()
+            &batch.geometry
         );
-        return (transform_bind_group, projection_buffer, view_buffer);
+        let stride: BufferAddress = std::mem::size_of::<BatchInstance>() as BufferAddress;
+
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, self.instance_buffer.as_ref().unwrap().slice(
+            batch.instances.start as BufferAddress * stride..batch.instances.end as BufferAddress * stride
+        ));
+        render_pass.set_index_buffer(index_buffer.slice(..), IndexFormat::Uint16);
+        render_pass.draw_indexed(0..number_of_indices, 0, 0..batch.instances.len() as u32);
+    }
+
+    /// Returns a new pipeline of a custom shader, or none if its source can't be loaded or compiled.
+    fn get_checked_render_pipeline(
+        &self,
+        kind: &str,
+        shader: &str,
+        source: std::io::Result<String>,
+        bind_group_layouts: Vec<Option<&BindGroupLayout>>,
+        vertex_buffers: &[VertexBufferLayout<'_>]
+    ) -> Option<RenderPipeline> {
+        return match source {
+            Ok(source) => {
+                let error_scope: ErrorScopeGuard = self.device.as_ref().unwrap().push_error_scope(ErrorFilter::Validation);
+                let render_pipeline: RenderPipeline = self.get_render_pipeline(bind_group_layouts, vertex_buffers, &source);
+
+                if let Some(error) = pollster::block_on(error_scope.pop()) {
+                    log::error!("{} shader '{}' is invalid: {}", kind, shader, error);
+                    None
+                } else {
+                    Some(render_pipeline)
+                }
+            },
+            Err(error) => {
+                log::error!("{} shader '{}' could not be loaded: {}", kind, shader, error);
+                None
+            }
+        };
     }
 
-    pub(crate) fn get_render_pipeline(&self, bind_group_layouts: Vec<Option<&BindGroupLayout>>, shader_source: &str) -> RenderPipeline {
+    pub(crate) fn get_render_pipeline(
+        &self,
+        bind_group_layouts: Vec<Option<&BindGroupLayout>>,
+        vertex_buffers: &[VertexBufferLayout<'_>],
+        shader_source: &str
+    ) -> RenderPipeline {
         let shader_module: ShaderModule = self.device.as_ref().unwrap().create_shader_module(ShaderModuleDescriptor {
             label: Some("Shader Module"),
             source: ShaderSource::Wgsl(shader_source.into())
@@ -753,7 +1663,7 @@ impl RenderState {
             vertex: VertexState {
                 module: &shader_module,
                 entry_point: Some("vs_main"),
-                buffers: &[Vertex::descriptor()],
+                buffers: vertex_buffers,
                 compilation_options: PipelineCompilationOptions::default()
             },
             fragment: Some(FragmentState {
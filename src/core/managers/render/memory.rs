@@ -0,0 +1,13 @@
+/// Struct to represent the memory used by the rendering resources of the engine.
+///
+/// Useful to verify that the resources of despawned entities are released.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MemoryStats {
+    pub textures: usize,
+    pub texture_bytes: u64,
+    pub buffers: usize,
+    pub buffer_bytes: u64,
+    pub bind_groups: usize,
+    pub fonts: usize,
+    pub text_brushes: usize
+}
@@ -9,3 +9,18 @@ pub mod cache;
 
 /// Rendering type related features.
 pub mod rendering_type;
+
+/// Rendering memory statistics related features.
+pub mod memory;
+
+/// Batched rendering related features.
+pub mod batch;
+
+/// Render target related features.
+pub mod target;
+
+/// Headless rendering and golden image related features.
+pub mod headless;
+
+/// Screenshot and frame recording related features.
+pub mod capture;
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+use wgpu::{Device, BindGroup, BindGroupLayout, BindGroupDescriptor, BindGroupEntry, BindingResource, TextureFormat, TextureView};
+use super::super::super::texture::texture::Texture;
+
+/// Struct to represent an offscreen texture that the frame can be rendered into and sampled from afterwards.
+pub struct RenderTarget {
+    pub texture: Arc<Texture>,
+    pub bind_group: BindGroup,
+    pub width: u32,
+    pub height: u32
+}
+
+impl RenderTarget {
+    /// Create a new render target, with the bind group to sample it on the texture layout.
+    pub fn new(device: &Device, texture_bind_group_layout: &BindGroupLayout, width: u32, height: u32, format: TextureFormat, label: &str) -> Self {
+        let texture: Arc<Texture> = Arc::new(Texture::render_target(device, width, height, format, Some(label)));
+        let bind_group: BindGroup = device.create_bind_group(&BindGroupDescriptor {
+            label: Some(label),
+            layout: texture_bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&texture.texture_view)
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&texture.sampler)
+                }
+            ]
+        });
+
+        return Self {
+            texture,
+            bind_group,
+            width,
+            height
+        };
+    }
+
+    /// Returns the view of the texture to be used as a color attachment.
+    pub fn view(&self) -> &TextureView {
+        return &self.texture.texture_view;
+    }
+
+    /// Returns if the render target has a certain size.
+    pub fn has_size(&self, width: u32, height: u32) -> bool {
+        return self.width == width && self.height == height;
+    }
+}
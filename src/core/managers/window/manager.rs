@@ -13,17 +13,22 @@ use super::{
         super::{
             text::text::TextHolder,
             asset_loader::AssetLoader,
+            post_process::post_process::PostProcess,
             camera::camera2d::Camera2d,
             ecs::{world::World, resource::{ResourceRef, ResourceRefMut}},
             super::{Color, ColorOption},
             game_loop::GameLoop,
             context::Context,
+            schedule::{stage::Stage, system::System},
             bindings::{keyboard::keyboard_input::KeyboardInput, mouse::mouse_input::MouseInput}
         }
     }
 };
+use crate::utils::constants::system::UPDATE;
 
 /// Struct to facilitate the window configuration by the end-user.
+///
+/// Struct literals should end with *..Default::default()*, so the fields added later keep their default values.
 #[derive(Clone)]
 pub struct WindowConfiguration {
     pub icon_path: String,
@@ -39,7 +44,8 @@ pub struct WindowConfiguration {
     pub transparent: bool,
     pub active: bool,
     pub enabled_buttons: WindowButtons,
-    pub present_mode: present_mode::PresentMode
+    pub present_mode: present_mode::PresentMode,
+    pub post_process: Option<PostProcess>
 }
 
 impl Default for WindowConfiguration {
@@ -59,7 +65,8 @@ impl Default for WindowConfiguration {
             transparent: true,
             active: true,
             enabled_buttons: WindowButtons::all(),
-            present_mode: present_mode::PresentMode::AutoNoVsync
+            present_mode: present_mode::PresentMode::AutoNoVsync,
+            post_process: None
         };
     }
 }
@@ -187,6 +194,16 @@ impl WindowConfiguration {
         };
     }
 
+    /// Returns the window configuration with the post-processing chain.
+    ///
+    /// The chain becomes the PostProcess resource of the world once the window is created.
+    pub fn post_process(self, post_process: PostProcess) -> Self {
+        return Self {
+            post_process: Some(post_process),
+            ..self
+        };
+    }
+
     /// Returns a icon by its relative bytes.
     pub fn get_icon_by_bytes(icon_as_bytes: Vec<u8>) -> Option<Icon> {
         if let Ok(image) = image::load_from_memory(&icon_as_bytes) {
@@ -256,17 +273,22 @@ impl ApplicationHandler for Application {
         self.window = Some(window.clone());
         self.window.as_ref().unwrap().focus_window();
 
-        let world: World = World::new();
+        let mut world: World = World::new();
         let mut render_state: RenderState = pollster::block_on(RenderState::new(window, present_mode.to_wgpu(), event_loop));
         render_state.color = color;
         render_state.background_image_path = background_image_path;
 
+        if let Some(post_process) = self.window_configuration.as_ref().and_then(|window_configuration| window_configuration.post_process.clone()) {
+            world.add_resource(Box::new(post_process));
+        }
+
         self.context = Some(Context::new(
             render_state,
             world,
             self.window_configuration.as_ref().unwrap().clone(),
             0.0
         ));
+        self.context.as_mut().unwrap().schedule.add_system(Stage::Update, System::new(UPDATE, self.game_loop.update));
         (self.game_loop.setup)(self.context.as_mut().unwrap());
     }
 
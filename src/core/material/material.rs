@@ -0,0 +1,146 @@
+use lotus_proc_macros::{Component, Reflect};
+use super::super::{asset_loader::AssetLoader, color::color::Color, super::ColorOption};
+use crate::utils::constants::shader::{
+    MATERIAL_PRELUDE,
+    DISSOLVE_MATERIAL,
+    FLASH_MATERIAL,
+    OUTLINE_MATERIAL,
+    DISSOLVE_MATERIAL_PATH,
+    FLASH_MATERIAL_PATH,
+    OUTLINE_MATERIAL_PATH,
+    MAX_MATERIAL_TEXTURES
+};
+
+/// Struct to represent the uniforms of a material that will be sent to the shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MaterialUniforms {
+    pub time: f32,
+    pub _padding: [f32; 3],
+    pub color: [f32; 4],
+    pub params: [f32; 4]
+}
+
+/// Struct to represent a material, a custom WGSL fragment shader applied on a sprite, animation or shape.
+///
+/// The shader file is relative to the assets directory and only declares the fragment stage,
+/// as the engine prelude is added before it with the bindings and the vertex stage:
+///
+/// ```wgsl
+/// @fragment
+/// fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+///     let color: vec4<f32> = base_color(in);
+///     return vec4<f32>(color.rgb * (0.5 + 0.5 * sin(material.time)), color.a);
+/// }
+/// ```
+///
+/// The uniforms are the time in seconds since the start of the engine, the color and up to four custom params.
+/// The extra textures are bound as *material_texture_0* to *material_texture_3* with the *material_sampler*.
+///
+/// Entities with materials are drawn one by one, and only when the batching of the rendering state is enabled.
+#[derive(Clone, Debug, Component, Reflect)]
+pub struct Material {
+    pub shader: String,
+    pub color: Color,
+    pub params: Vec<f32>,
+    pub textures: Vec<String>
+}
+
+impl Material {
+    /// Create a new material with its shader path as the parameter.
+    pub fn new(shader: String) -> Self {
+        return Self {
+            shader,
+            color: Color::by_option(ColorOption::White),
+            params: Vec::new(),
+            textures: Vec::new()
+        };
+    }
+
+    /// Create a new dissolve material, with the dissolved amount and the color of its burning edge.
+    pub fn dissolve(amount: f32, edge_color: Color) -> Self {
+        return Self::new(DISSOLVE_MATERIAL_PATH.to_string()).color(edge_color).params(vec![amount, 0.05]);
+    }
+
+    /// Create a new flash material, with the color of the flash.
+    ///
+    /// The flash intensity is the first param, usually raised on hit and faded over time.
+    pub fn flash(color: Color) -> Self {
+        return Self::new(FLASH_MATERIAL_PATH.to_string()).color(color).params(vec![0.0]);
+    }
+
+    /// Create a new outline material, with the color and the thickness in texels of the outline.
+    pub fn outline(color: Color, thickness: f32) -> Self {
+        return Self::new(OUTLINE_MATERIAL_PATH.to_string()).color(color).params(vec![thickness]);
+    }
+
+    /// Set the color on initialization.
+    pub fn color(self, color: Color) -> Self {
+        return Self {
+            color,
+            ..self
+        };
+    }
+
+    /// Set the custom params on initialization.
+    ///
+    /// Only the first four params are sent to the shader.
+    pub fn params(self, params: Vec<f32>) -> Self {
+        return Self {
+            params,
+            ..self
+        };
+    }
+
+    /// Add an extra texture on initialization.
+    ///
+    /// Only the first four textures are sent to the shader.
+    pub fn texture(self, path: String) -> Self {
+        let mut textures: Vec<String> = self.textures;
+        textures.push(path);
+
+        return Self {
+            textures,
+            ..self
+        };
+    }
+
+    /// Set a custom param by its index, filling the previous ones with zero.
+    pub fn set_param(&mut self, index: usize, value: f32) {
+        if self.params.len() <= index {
+            self.params.resize(index + 1, 0.0);
+        }
+        self.params[index] = value;
+    }
+
+    /// Returns the extra textures that are sent to the shader.
+    pub fn bound_textures(&self) -> &[String] {
+        return &self.textures[..self.textures.len().min(MAX_MATERIAL_TEXTURES)];
+    }
+
+    /// Returns the uniforms of the material at a certain time.
+    pub fn to_uniforms(&self, time: f32) -> MaterialUniforms {
+        let mut params: [f32; 4] = [0.0; 4];
+
+        for (param, value) in params.iter_mut().zip(&self.params) {
+            *param = *value;
+        }
+        return MaterialUniforms {
+            time,
+            _padding: [0.0; 3],
+            color: self.color.to_array(),
+            params
+        };
+    }
+
+    /// Returns the full source code of the material shader, with the engine prelude before it.
+    pub fn load_source(&self) -> std::io::Result<String> {
+        let source: String = match self.shader.as_str() {
+            DISSOLVE_MATERIAL_PATH => DISSOLVE_MATERIAL.to_string(),
+            FLASH_MATERIAL_PATH => FLASH_MATERIAL.to_string(),
+            OUTLINE_MATERIAL_PATH => OUTLINE_MATERIAL.to_string(),
+            path => String::from_utf8_lossy(&AssetLoader::load_bytes(path)?).into_owned()
+        };
+        return Ok(format!("{}\n{}", MATERIAL_PRELUDE, source));
+    }
+}
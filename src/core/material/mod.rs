@@ -0,0 +1,2 @@
+/// Material component and uniforms related features.
+pub mod material;
@@ -16,6 +16,9 @@ pub mod macros;
 /// Entity-Component-System related features and architecture.
 pub mod ecs;
 
+/// System scheduling related features.
+pub mod schedule;
+
 /// Engine context related data stored for the end-user.
 pub mod context;
 
@@ -43,6 +46,12 @@ pub mod asset_loader;
 /// Texture related features.
 pub mod texture;
 
+/// Engine provided bundles of components.
+pub mod bundles;
+
+/// Entity hierarchy related features.
+pub mod hierarchy;
+
 /// Visibility related features.
 pub mod visibility;
 
@@ -52,6 +61,24 @@ pub mod draw_order;
 /// Animation related features.
 pub mod animation;
 
+/// Material and custom shader related features.
+pub mod material;
+
+/// Post-processing related features.
+pub mod post_process;
+
+/// 2D lighting related features.
+pub mod lighting;
+
+/// Particle system related features.
+pub mod particle;
+
+/// Scene files related features.
+pub mod scene;
+
+/// Save game related features.
+pub mod save;
+
 /// Event related features.
 pub mod event;
 
@@ -0,0 +1,103 @@
+use super::super::color::color::Color;
+
+/// Struct to represent a value changing over the lifetime of a particle.
+///
+/// The keys are made of a time, from 0.0 at the birth of the particle to 1.0 at its death, and a value.
+/// The values between the keys are linearly interpolated, and the ones outside of them are clamped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParticleCurve {
+    pub keys: Vec<(f32, f32)>
+}
+
+impl ParticleCurve {
+    /// Create a new curve with the same value over the whole lifetime.
+    pub fn constant(value: f32) -> Self {
+        return Self {
+            keys: vec![(0.0, value)]
+        };
+    }
+
+    /// Create a new curve going from a value at the birth to another at the death.
+    pub fn linear(start: f32, end: f32) -> Self {
+        return Self {
+            keys: vec![(0.0, start), (1.0, end)]
+        };
+    }
+
+    /// Add a key on initialization, keeping the keys sorted by their time.
+    pub fn key(self, time: f32, value: f32) -> Self {
+        return Self {
+            keys: insert_key(self.keys, time, value)
+        };
+    }
+
+    /// Returns the value of the curve at a time of the lifetime.
+    pub fn sample(&self, time: f32) -> f32 {
+        return sample_keys(&self.keys, time, |start, end, amount| start + (end - start) * amount).unwrap_or(0.0);
+    }
+}
+
+/// Struct to represent a color changing over the lifetime of a particle.
+///
+/// It works as a curve, with every channel of the colors being interpolated.
+#[derive(Clone, Debug)]
+pub struct ParticleGradient {
+    pub keys: Vec<(f32, Color)>
+}
+
+impl ParticleGradient {
+    /// Create a new gradient with the same color over the whole lifetime.
+    pub fn constant(color: Color) -> Self {
+        return Self {
+            keys: vec![(0.0, color)]
+        };
+    }
+
+    /// Create a new gradient going from a color at the birth to another at the death.
+    pub fn linear(start: Color, end: Color) -> Self {
+        return Self {
+            keys: vec![(0.0, start), (1.0, end)]
+        };
+    }
+
+    /// Add a key on initialization, keeping the keys sorted by their time.
+    pub fn key(self, time: f32, color: Color) -> Self {
+        return Self {
+            keys: insert_key(self.keys, time, color)
+        };
+    }
+
+    /// Returns the color of the gradient at a time of the lifetime, as an array.
+    pub fn sample(&self, time: f32) -> [f32; 4] {
+        return sample_keys(&self.keys, time, |start, end, amount| {
+            let (start, end): ([f32; 4], [f32; 4]) = (start.to_array(), end.to_array());
+            Color::new(
+                start[0] + (end[0] - start[0]) * amount,
+                start[1] + (end[1] - start[1]) * amount,
+                start[2] + (end[2] - start[2]) * amount,
+                start[3] + (end[3] - start[3]) * amount
+            )
+        }).map(|color| color.to_array()).unwrap_or([1.0, 1.0, 1.0, 1.0]);
+    }
+}
+
+fn insert_key<T>(mut keys: Vec<(f32, T)>, time: f32, value: T) -> Vec<(f32, T)> {
+    let index: usize = keys.partition_point(|(key_time, _)| *key_time <= time);
+    keys.insert(index, (time, value));
+    return keys;
+}
+
+fn sample_keys<T: Copy>(keys: &[(f32, T)], time: f32, interpolate: fn(T, T, f32) -> T) -> Option<T> {
+    let (first, last): (&(f32, T), &(f32, T)) = (keys.first()?, keys.last()?);
+
+    if time <= first.0 {
+        return Some(first.1);
+    } else if time >= last.0 {
+        return Some(last.1);
+    }
+    let index: usize = keys.partition_point(|(key_time, _)| *key_time <= time);
+    let (start, end): (&(f32, T), &(f32, T)) = (&keys[index - 1], &keys[index]);
+    let amount: f32 = if end.0 > start.0 { (time - start.0) / (end.0 - start.0) } else { 0.0 };
+
+    return Some(interpolate(start.1, end.1, amount));
+}
@@ -0,0 +1,5 @@
+/// Curves and gradients sampled over the lifetime of the particles.
+pub mod curve;
+
+/// Particle emitters related features.
+pub mod particle_emitter;
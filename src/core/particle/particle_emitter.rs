@@ -0,0 +1,306 @@
+use cgmath::Vector2;
+use lotus_proc_macros::Component;
+use rand::{RngExt, rng, rngs::ThreadRng};
+use super::{
+    curve::{ParticleCurve, ParticleGradient},
+    super::{
+        color::color::Color,
+        managers::render::{batch::BatchGeometry, rendering_type::RenderingType},
+        shape::geometry_type::GeometryType,
+        super::ColorOption
+    }
+};
+use crate::utils::constants::cache::DUMMY_TEXTURE;
+
+/// Enumerator to represent where the particles of an emitter are simulated.
+///
+/// World space particles stay where they were spawned, leaving a trail while the emitter moves.
+/// Local space particles move along with the transform of the emitter.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ParticleSpace {
+    #[default]
+    World,
+    Local
+}
+
+/// Enumerator to represent how every particle of an emitter is drawn, by a texture or by a shape geometry.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParticleAppearance {
+    Texture(String),
+    Shape(GeometryType)
+}
+
+impl ParticleAppearance {
+    /// Returns the key of the texture used by the particles.
+    pub fn texture_key(&self) -> &str {
+        return match self {
+            ParticleAppearance::Texture(path) => path,
+            ParticleAppearance::Shape(_) => DUMMY_TEXTURE
+        };
+    }
+
+    /// Returns the geometry shared by the particles.
+    pub fn geometry(&self) -> BatchGeometry {
+        return match self {
+            ParticleAppearance::Texture(_) => BatchGeometry::Quad,
+            ParticleAppearance::Shape(geometry_type) => BatchGeometry::Shape(geometry_type.clone())
+        };
+    }
+
+    /// Returns the rendering type index of the particles on the shader.
+    pub fn rendering_type(&self) -> u32 {
+        return match self {
+            ParticleAppearance::Texture(_) => RenderingType::Texture.to_shader_index(),
+            ParticleAppearance::Shape(_) => RenderingType::Shape.to_shader_index()
+        };
+    }
+}
+
+/// Struct to represent a single particle of an emitter.
+///
+/// The position and velocity are in world units, relative to the emitter when it simulates on local space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Particle {
+    pub position: Vector2<f32>,
+    pub velocity: Vector2<f32>,
+    pub age: f32,
+    pub lifetime: f32
+}
+
+impl Particle {
+    /// Returns the progress of the particle over its lifetime, from 0.0 to 1.0.
+    pub fn progress(&self) -> f32 {
+        if self.lifetime <= 0.0 {
+            return 1.0;
+        }
+        return (self.age / self.lifetime).clamp(0.0, 1.0);
+    }
+}
+
+/// Struct to represent a particle emitter component, spawning and simulating lightweight particles.
+///
+/// The particles are not entities, they live inside of the emitter and are drawn in a single instanced draw call.
+/// The emitter spawns from the position of its entity transform, so it needs a transform as well.
+///
+/// The spawn rate is in particles per second and the burst is spawned at once on the first update.
+/// The lifetime and the velocity are random values between their minimum and maximum.
+/// The color and size curves are sampled over the lifetime of each particle, with the size scaling the geometry of the particles.
+///
+/// The particles are only drawn while batching is enabled, as they are drawn with instancing.
+#[derive(Clone, Debug, Component)]
+pub struct ParticleEmitter {
+    pub appearance: ParticleAppearance,
+    pub space: ParticleSpace,
+    pub spawn_rate: f32,
+    pub burst: u32,
+    pub lifetime: (f32, f32),
+    pub velocity: (Vector2<f32>, Vector2<f32>),
+    pub gravity: Vector2<f32>,
+    pub spawn_radius: f32,
+    pub color: ParticleGradient,
+    pub size: ParticleCurve,
+    pub max_particles: usize,
+    pub emitting: bool,
+    pub particles: Vec<Particle>,
+    spawn_accumulator: f32,
+    pending_burst: u32
+}
+
+impl ParticleEmitter {
+    /// Create a new emitter with an appearance and default parameters.
+    pub fn new(appearance: ParticleAppearance) -> Self {
+        return Self {
+            appearance,
+            space: ParticleSpace::World,
+            spawn_rate: 10.0,
+            burst: 0,
+            lifetime: (1.0, 1.0),
+            velocity: (Vector2::new(-0.1, 0.2), Vector2::new(0.1, 0.4)),
+            gravity: Vector2::new(0.0, 0.0),
+            spawn_radius: 0.0,
+            color: ParticleGradient::constant(Color::by_option(ColorOption::White)),
+            size: ParticleCurve::constant(0.02),
+            max_particles: 1000,
+            emitting: true,
+            particles: Vec::new(),
+            spawn_accumulator: 0.0,
+            pending_burst: 0
+        };
+    }
+
+    /// Create a new emitter of textured particles.
+    pub fn texture(path: String) -> Self {
+        return Self::new(ParticleAppearance::Texture(path));
+    }
+
+    /// Create a new emitter of shape particles.
+    pub fn shape(geometry_type: GeometryType) -> Self {
+        return Self::new(ParticleAppearance::Shape(geometry_type));
+    }
+
+    /// Set the simulation space on initialization.
+    pub fn space(self, space: ParticleSpace) -> Self {
+        return Self {
+            space,
+            ..self
+        };
+    }
+
+    /// Set the spawn rate in particles per second on initialization.
+    pub fn spawn_rate(self, spawn_rate: f32) -> Self {
+        return Self {
+            spawn_rate,
+            ..self
+        };
+    }
+
+    /// Set the amount of particles spawned at once on the first update on initialization.
+    pub fn burst(self, burst: u32) -> Self {
+        return Self {
+            burst,
+            pending_burst: burst,
+            ..self
+        };
+    }
+
+    /// Set the minimum and maximum lifetime in seconds on initialization.
+    pub fn lifetime(self, minimum: f32, maximum: f32) -> Self {
+        return Self {
+            lifetime: (minimum, maximum),
+            ..self
+        };
+    }
+
+    /// Set the minimum and maximum initial velocity on initialization.
+    pub fn velocity(self, minimum: Vector2<f32>, maximum: Vector2<f32>) -> Self {
+        return Self {
+            velocity: (minimum, maximum),
+            ..self
+        };
+    }
+
+    /// Set the gravity, the acceleration applied to every particle, on initialization.
+    pub fn gravity(self, gravity: Vector2<f32>) -> Self {
+        return Self {
+            gravity,
+            ..self
+        };
+    }
+
+    /// Set the radius around the emitter where the particles are spawned on initialization.
+    pub fn spawn_radius(self, spawn_radius: f32) -> Self {
+        return Self {
+            spawn_radius,
+            ..self
+        };
+    }
+
+    /// Set the color over the lifetime on initialization.
+    pub fn color(self, color: ParticleGradient) -> Self {
+        return Self {
+            color,
+            ..self
+        };
+    }
+
+    /// Set the size over the lifetime on initialization.
+    pub fn size(self, size: ParticleCurve) -> Self {
+        return Self {
+            size,
+            ..self
+        };
+    }
+
+    /// Set the maximum amount of living particles on initialization.
+    pub fn max_particles(self, max_particles: usize) -> Self {
+        return Self {
+            max_particles,
+            ..self
+        };
+    }
+
+    /// Set if the emitter spawns particles continuously on initialization.
+    pub fn emitting(self, emitting: bool) -> Self {
+        return Self {
+            emitting,
+            ..self
+        };
+    }
+
+    /// Spawn an amount of particles at once on the next update.
+    pub fn emit(&mut self, count: u32) {
+        self.pending_burst += count;
+    }
+
+    /// Remove every living particle and restart the burst.
+    pub fn restart(&mut self) {
+        self.particles.clear();
+        self.spawn_accumulator = 0.0;
+        self.pending_burst = self.burst;
+    }
+
+    /// Returns if the emitter won't show anything anymore, without living and upcoming particles.
+    pub fn is_finished(&self) -> bool {
+        return !self.emitting && self.pending_burst == 0 && self.particles.is_empty();
+    }
+
+    /// Advance the simulation by a delta time, with the origin being the world position of the emitter.
+    ///
+    /// The particles that reached their lifetime are removed before the new ones are spawned.
+    pub fn update(&mut self, delta: f32, origin: Vector2<f32>) {
+        for particle in &mut self.particles {
+            particle.age += delta;
+            particle.velocity += self.gravity * delta;
+            particle.position += particle.velocity * delta;
+        }
+        self.particles.retain(|particle| particle.age < particle.lifetime);
+
+        let mut spawn_count: u32 = std::mem::take(&mut self.pending_burst);
+
+        if self.emitting {
+            self.spawn_accumulator += self.spawn_rate * delta;
+            let spawned: f32 = self.spawn_accumulator.floor();
+            self.spawn_accumulator -= spawned;
+            spawn_count += spawned as u32;
+        }
+        let available: usize = self.max_particles.saturating_sub(self.particles.len());
+        let spawn_count: usize = (spawn_count as usize).min(available);
+
+        if spawn_count > 0 {
+            let mut thread_rng: ThreadRng = rng();
+            let origin: Vector2<f32> = if self.space == ParticleSpace::World { origin } else { Vector2::new(0.0, 0.0) };
+
+            for _ in 0..spawn_count {
+                let particle: Particle = self.spawn_particle(&mut thread_rng, origin);
+                self.particles.push(particle);
+            }
+        }
+    }
+
+    fn spawn_particle(&self, thread_rng: &mut ThreadRng, origin: Vector2<f32>) -> Particle {
+        let offset: Vector2<f32> = if self.spawn_radius > 0.0 {
+            let theta: f32 = thread_rng.random_range(0.0..std::f32::consts::TAU);
+            let distance: f32 = self.spawn_radius * thread_rng.random_range(0.0..1.0_f32).sqrt();
+            Vector2::new(distance * theta.cos(), distance * theta.sin())
+        } else {
+            Vector2::new(0.0, 0.0)
+        };
+
+        return Particle {
+            position: origin + offset,
+            velocity: Vector2::new(
+                random_between(thread_rng, self.velocity.0.x, self.velocity.1.x),
+                random_between(thread_rng, self.velocity.0.y, self.velocity.1.y)
+            ),
+            age: 0.0,
+            lifetime: random_between(thread_rng, self.lifetime.0, self.lifetime.1)
+        };
+    }
+}
+
+fn random_between(thread_rng: &mut ThreadRng, minimum: f32, maximum: f32) -> f32 {
+    if minimum >= maximum {
+        return minimum;
+    }
+    return thread_rng.random_range(minimum..maximum);
+}
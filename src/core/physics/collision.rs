@@ -1,9 +1,9 @@
 use cgmath::Vector2;
-use lotus_proc_macros::Component;
+use lotus_proc_macros::{Component, Reflect};
 use super::super::shape::geometry_type::GeometryType;
 
 /// Struct to represent the real collider of the object.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Reflect)]
 pub struct Collider {
     pub geometry_type: GeometryType,
     pub position: Vector2<f32>,
@@ -67,7 +67,7 @@ impl CollisionAlgorithm {
 }
 
 /// Struct to represent the collision characteristic that an object can have.
-#[derive(Clone, Debug, Component)]
+#[derive(Clone, Debug, Component, Reflect)]
 pub struct Collision {
     pub collider: Collider
 }
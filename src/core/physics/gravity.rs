@@ -1,11 +1,11 @@
-use lotus_proc_macros::Component;
+use lotus_proc_macros::{Component, Reflect};
 
 /// Struct to represent the gravity in our world.
 ///
 /// It starts with value equal to 9.8 (standard gravity of Earth).
 ///
 /// Gravity will only be applied to entities with the 'RigidBody' and 'Velocity' components.
-#[derive(Clone, Component)]
+#[derive(Clone, Component, Reflect)]
 pub struct Gravity {
     pub value: f32
 }
@@ -1,7 +1,7 @@
-use lotus_proc_macros::Component;
+use lotus_proc_macros::{Component, Reflect};
 
 /// Enumerator to represent the types of bodies in our physics system.
-#[derive(Clone, Default, PartialEq)]
+#[derive(Clone, Default, PartialEq, Reflect)]
 pub enum BodyType {
     /// The Static body never moves.
     Static,
@@ -13,7 +13,7 @@ pub enum BodyType {
 }
 
 /// Struct to represent the rigid body of an entity.
-#[derive(Clone, Component)]
+#[derive(Clone, Component, Reflect)]
 pub struct RigidBody {
     /// The type of the rigid body.
     pub body_type: BodyType,
@@ -1,9 +1,9 @@
-use cgmath::{Deg, Matrix4, Vector2, Vector3};
-use lotus_proc_macros::Component;
+use cgmath::{Deg, InnerSpace, Matrix4, SquareMatrix, Vector2, Vector3};
+use lotus_proc_macros::{Component, Reflect};
 use super::super::managers::render::manager::RenderState;
 
 /// Enumerator to represent the strategy that will be used for positioning.
-#[derive(Clone, Default, Debug, PartialEq)]
+#[derive(Clone, Default, Debug, PartialEq, Reflect)]
 pub enum Strategy {
     /// The default value.
     ///
@@ -24,7 +24,7 @@ pub enum Strategy {
 }
 
 /// Struct to represent the position of the transformation matrix.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Reflect)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
@@ -54,12 +54,15 @@ impl Position {
 }
 
 /// Struct to represent the transform matrix of every object rendered.
-#[derive(Clone, Debug, Component)]
+#[derive(Clone, Debug, Component, Reflect)]
+#[reflect(default)]
 pub struct Transform {
     pub position: Position,
     pub rotation: f32,
     pub scale: Vector2<f32>,
+    #[reflect(ignore)]
     pub(crate) dirty_position: bool,
+    #[reflect(ignore)]
     pub(crate) dirty_scale: bool
 }
 
@@ -218,3 +221,44 @@ impl Transform {
         return self.scale;
     }
 }
+
+/// Struct to represent the transform of an entity in world space, computed from its hierarchy on each frame.
+///
+/// Entities spawned as children receive it automatically, as well as their parents.
+#[derive(Clone, Debug, Component)]
+pub struct GlobalTransform {
+    pub matrix: Matrix4<f32>,
+    pub(crate) parent_matrix: Matrix4<f32>
+}
+
+impl Default for GlobalTransform {
+    /// Returns a default global transform struct, with identity matrices.
+    fn default() -> Self {
+        return Self {
+            matrix: Matrix4::identity(),
+            parent_matrix: Matrix4::identity()
+        };
+    }
+}
+
+impl GlobalTransform {
+    /// Returns the global transform matrix.
+    pub fn to_matrix(&self) -> Matrix4<f32> {
+        return self.matrix;
+    }
+
+    /// Returns the matrix of the parent, which the local transform is relative to.
+    pub fn get_parent_matrix(&self) -> Matrix4<f32> {
+        return self.parent_matrix;
+    }
+
+    /// Returns the global position.
+    pub fn get_position(&self) -> Vector2<f32> {
+        return Vector2::new(self.matrix.w.x, self.matrix.w.y);
+    }
+
+    /// Returns the global scale.
+    pub fn get_scale(&self) -> Vector2<f32> {
+        return Vector2::new(self.matrix.x.truncate().magnitude(), self.matrix.y.truncate().magnitude());
+    }
+}
@@ -1,8 +1,8 @@
 use cgmath::Vector2;
-use lotus_proc_macros::Component;
+use lotus_proc_macros::{Component, Reflect};
 
 /// Struct to represent the velocity that can be applied over objects for smooth movemnet.
-#[derive(Clone, Component)]
+#[derive(Clone, Component, Reflect)]
 pub struct Velocity {
     pub x: f32,
     pub y: f32
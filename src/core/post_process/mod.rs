@@ -0,0 +1,2 @@
+/// Post-processing chain and effects related features.
+pub mod post_process;
@@ -0,0 +1,199 @@
+use lotus_proc_macros::Resource;
+use super::super::asset_loader::AssetLoader;
+use crate::utils::constants::shader::{
+    POST_PROCESS_PRELUDE,
+    BLOOM_POST_PROCESS,
+    VIGNETTE_POST_PROCESS,
+    CRT_POST_PROCESS,
+    COLOR_GRADING_POST_PROCESS,
+    BLOOM_POST_PROCESS_PATH,
+    VIGNETTE_POST_PROCESS_PATH,
+    CRT_POST_PROCESS_PATH,
+    COLOR_GRADING_POST_PROCESS_PATH
+};
+
+/// Struct to represent the uniforms of a post-processing effect that will be sent to the shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PostProcessUniforms {
+    pub time: f32,
+    pub _padding: f32,
+    pub resolution: [f32; 2],
+    pub params: [f32; 4]
+}
+
+/// Enumerator to represent a full-screen pass of the post-processing chain.
+///
+/// A custom effect has its WGSL shader path relative to the assets directory, only declaring the fragment stage,
+/// as the engine prelude is added before it with the bindings and the vertex stage:
+///
+/// ```wgsl
+/// @fragment
+/// fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+///     let color: vec4<f32> = source_color(in.texture_coordinates);
+///     return vec4<f32>(vec3<f32>(dot(color.rgb, vec3<f32>(0.299, 0.587, 0.114))), 1.0);
+/// }
+/// ```
+///
+/// The uniforms are the time in seconds since the start of the engine, the resolution of the frame and up to four custom params.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PostEffect {
+    Bloom { threshold: f32, intensity: f32 },
+    Vignette { intensity: f32, smoothness: f32 },
+    Crt { scanline_intensity: f32, curvature: f32 },
+    ColorGrading { lut: String, intensity: f32 },
+    Custom { shader: String, params: Vec<f32> }
+}
+
+impl PostEffect {
+    /// Create a new bloom effect, making the colors brighter than the threshold glow.
+    pub fn bloom(threshold: f32, intensity: f32) -> Self {
+        return PostEffect::Bloom { threshold, intensity };
+    }
+
+    /// Create a new vignette effect, darkening the borders of the frame.
+    pub fn vignette(intensity: f32, smoothness: f32) -> Self {
+        return PostEffect::Vignette { intensity, smoothness };
+    }
+
+    /// Create a new CRT effect, with scanlines and a curved screen.
+    pub fn crt(scanline_intensity: f32, curvature: f32) -> Self {
+        return PostEffect::Crt { scanline_intensity, curvature };
+    }
+
+    /// Create a new color grading effect, with the path of a lookup table texture.
+    ///
+    /// The lookup table has its blue slices side by side, like a 256x16 strip for a lookup table of size 16.
+    pub fn color_grading(lut: String, intensity: f32) -> Self {
+        return PostEffect::ColorGrading { lut, intensity };
+    }
+
+    /// Create a new custom effect, with its shader path and custom params.
+    ///
+    /// Only the first four params are sent to the shader.
+    pub fn custom(shader: String, params: Vec<f32>) -> Self {
+        return PostEffect::Custom { shader, params };
+    }
+
+    /// Returns the path of the shader of the effect, used for caching its pipeline.
+    pub fn shader_key(&self) -> &str {
+        return match self {
+            PostEffect::Bloom { .. } => BLOOM_POST_PROCESS_PATH,
+            PostEffect::Vignette { .. } => VIGNETTE_POST_PROCESS_PATH,
+            PostEffect::Crt { .. } => CRT_POST_PROCESS_PATH,
+            PostEffect::ColorGrading { .. } => COLOR_GRADING_POST_PROCESS_PATH,
+            PostEffect::Custom { shader, .. } => shader
+        };
+    }
+
+    /// Returns the params of the effect that are sent to the shader.
+    pub fn params(&self) -> [f32; 4] {
+        return match self {
+            PostEffect::Bloom { threshold, intensity } => [*threshold, *intensity, 2.0, 0.0],
+            PostEffect::Vignette { intensity, smoothness } => [*intensity, *smoothness, 0.0, 0.0],
+            PostEffect::Crt { scanline_intensity, curvature } => [*scanline_intensity, *curvature, 0.0, 0.0],
+            PostEffect::ColorGrading { intensity, .. } => [*intensity, 0.0, 0.0, 0.0],
+            PostEffect::Custom { params, .. } => {
+                let mut custom_params: [f32; 4] = [0.0; 4];
+
+                for (param, value) in custom_params.iter_mut().zip(params) {
+                    *param = *value;
+                }
+                custom_params
+            }
+        };
+    }
+
+    /// Returns the path of the extra texture of the effect, if any.
+    pub fn texture(&self) -> Option<&str> {
+        return match self {
+            PostEffect::ColorGrading { lut, .. } => Some(lut),
+            _ => None
+        };
+    }
+
+    /// Returns the uniforms of the effect at a certain time and resolution.
+    pub fn to_uniforms(&self, time: f32, resolution: (u32, u32)) -> PostProcessUniforms {
+        return PostProcessUniforms {
+            time,
+            _padding: 0.0,
+            resolution: [resolution.0 as f32, resolution.1 as f32],
+            params: self.params()
+        };
+    }
+
+    /// Returns the full source code of the effect shader, with the engine prelude before it.
+    pub fn load_source(&self) -> std::io::Result<String> {
+        let source: String = match self.shader_key() {
+            BLOOM_POST_PROCESS_PATH => BLOOM_POST_PROCESS.to_string(),
+            VIGNETTE_POST_PROCESS_PATH => VIGNETTE_POST_PROCESS.to_string(),
+            CRT_POST_PROCESS_PATH => CRT_POST_PROCESS.to_string(),
+            COLOR_GRADING_POST_PROCESS_PATH => COLOR_GRADING_POST_PROCESS.to_string(),
+            path => String::from_utf8_lossy(&AssetLoader::load_bytes(path)?).into_owned()
+        };
+        return Ok(format!("{}\n{}", POST_PROCESS_PRELUDE, source));
+    }
+}
+
+/// Struct to represent the post-processing chain of the frame, as a stack of full-screen effects applied in order.
+///
+/// When it has enabled effects, the scene is rendered on an offscreen target before being processed to the window.
+/// It can be set up on the window configuration or modified at runtime as a resource.
+#[derive(Clone, Debug, PartialEq, Resource)]
+pub struct PostProcess {
+    pub effects: Vec<PostEffect>,
+    pub enabled: bool
+}
+
+impl Default for PostProcess {
+    fn default() -> Self {
+        return Self {
+            effects: Vec::new(),
+            enabled: true
+        };
+    }
+}
+
+impl PostProcess {
+    /// Create a new empty post-processing chain.
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Add an effect at the end of the chain on initialization.
+    pub fn effect(self, effect: PostEffect) -> Self {
+        let mut effects: Vec<PostEffect> = self.effects;
+        effects.push(effect);
+
+        return Self {
+            effects,
+            ..self
+        };
+    }
+
+    /// Set the enabled state on initialization.
+    pub fn enabled(self, enabled: bool) -> Self {
+        return Self {
+            enabled,
+            ..self
+        };
+    }
+
+    /// Add an effect at the end of the chain.
+    pub fn push(&mut self, effect: PostEffect) {
+        self.effects.push(effect);
+    }
+
+    /// Remove all the effects of the chain.
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+
+    /// Returns the effects that will be applied on the frame.
+    pub fn active_effects(&self) -> &[PostEffect] {
+        if !self.enabled {
+            return &[];
+        }
+        return &self.effects;
+    }
+}
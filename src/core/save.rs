@@ -0,0 +1,254 @@
+use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
+use lotus_proc_macros::{Component, Reflect};
+use super::{
+    asset_loader::AssetLoader,
+    scene::{Scene, SceneError},
+    ecs::{
+        entity::Entity,
+        filter::With,
+        reflect::{Reflect, ReflectError, ReflectValue},
+        registry::ResourceRegistration,
+        resource::Resource,
+        world::World
+    }
+};
+
+/// Struct to represent the tag of the entities that are part of the save game snapshots.
+///
+/// The children of a persistent entity are saved with it.
+#[derive(Clone, Debug, Default, Component, Reflect)]
+pub struct Persistent;
+
+/// Function that migrates a snapshot from a format version to the next one.
+pub type SaveMigration = Arc<dyn Fn(&mut SaveData) -> Result<(), SaveError> + Send + Sync>;
+
+/// Enumerator to represent the different errors that the save game flow can throw.
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Scene(SceneError),
+    Reflect(ReflectError),
+    Format(String),
+    UnsupportedVersion(u32),
+    MissingMigration(u32)
+}
+
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(formatter, "IO Save Error: {}.", error),
+            SaveError::Json(error) => write!(formatter, "JSON Save Error: {}.", error),
+            SaveError::Scene(error) => write!(formatter, "Scene Save Error: {}", error),
+            SaveError::Reflect(error) => write!(formatter, "Reflect Save Error: {}", error),
+            SaveError::Format(message) => write!(formatter, "Format Save Error: {}.", message),
+            SaveError::UnsupportedVersion(version) => write!(formatter, "Unsupported Version Save Error: version {} is newer than the current one.", version),
+            SaveError::MissingMigration(version) => write!(formatter, "Missing Migration Save Error: no migration from version {}.", version)
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<std::io::Error> for SaveError {
+    fn from(error: std::io::Error) -> Self {
+        return SaveError::Io(error);
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(error: serde_json::Error) -> Self {
+        return SaveError::Json(error);
+    }
+}
+
+impl From<SceneError> for SaveError {
+    fn from(error: SceneError) -> Self {
+        return SaveError::Scene(error);
+    }
+}
+
+impl From<ReflectError> for SaveError {
+    fn from(error: ReflectError) -> Self {
+        return SaveError::Reflect(error);
+    }
+}
+
+/// Struct to represent a versioned snapshot of resources and persistent entities, detached from their types.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaveData {
+    pub version: u32,
+    pub resources: Vec<(String, ReflectValue)>,
+    pub scene: Scene
+}
+
+impl SaveData {
+    /// Create a new snapshot with its version, resources and entities.
+    pub fn new(version: u32, resources: Vec<(String, ReflectValue)>, scene: Scene) -> Self {
+        return Self {
+            version,
+            resources,
+            scene
+        };
+    }
+
+    /// Returns the reflected value of a resource of the snapshot by its type name.
+    pub fn get_resource(&self, type_name: &str) -> Option<&ReflectValue> {
+        return self.resources.iter().find(|(resource_type_name, _)| resource_type_name == type_name).map(|(_, value)| value);
+    }
+
+    /// Returns the reflected value of a resource of the snapshot by its type name as mutable.
+    ///
+    /// Useful for migrations that change the fields of a resource.
+    pub fn get_resource_mut(&mut self, type_name: &str) -> Option<&mut ReflectValue> {
+        return self.resources.iter_mut().find(|(resource_type_name, _)| resource_type_name == type_name).map(|(_, value)| value);
+    }
+
+    /// Returns the snapshot as a JSON value.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json: serde_json::Value = self.scene.to_json();
+        json["version"] = serde_json::Value::from(self.version);
+        json["resources"] = serde_json::Value::Object(
+            self.resources.iter().map(|(type_name, value)| (type_name.clone(), value.to_json())).collect()
+        );
+        return json;
+    }
+
+    /// Create a new snapshot from a JSON value.
+    pub fn from_json(json: &serde_json::Value) -> Result<Self, SaveError> {
+        let version: u32 = json.get("version")
+            .and_then(|version| version.as_u64())
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| SaveError::Format("expected a version number".to_string()))?;
+
+        let resources: Vec<(String, ReflectValue)> = json.get("resources")
+            .and_then(|resources| resources.as_object())
+            .ok_or_else(|| SaveError::Format("expected a map of resources".to_string()))?
+            .iter()
+            .map(|(type_name, value)| (type_name.clone(), ReflectValue::from_json(value)))
+            .collect();
+
+        return Ok(Self::new(version, resources, Scene::from_json(json)?));
+    }
+}
+
+/// Struct to represent the format of the save game snapshots of a game.
+///
+/// A snapshot holds the selected resources and the entities tagged as Persistent, with their registered components.
+///
+/// When the format changes, the version is increased and a migration from the previous version is added,
+/// so older save files are migrated step by step when loaded.
+///
+/// ```rust,no_run
+/// use lotus_engine::*;
+///
+/// let save_game: SaveGame = SaveGame::new(2)
+///     .resource::<Timer>()
+///     .migration(1, |save_data| {
+///         save_data.resources.retain(|(type_name, _)| type_name != "Score");
+///         return Ok(());
+///     });
+/// ```
+#[derive(Clone)]
+pub struct SaveGame {
+    pub version: u32,
+    resources: Vec<ResourceRegistration>,
+    migrations: HashMap<u32, SaveMigration>
+}
+
+impl SaveGame {
+    /// Create a new save game format by its current version.
+    pub fn new(version: u32) -> Self {
+        return Self {
+            version,
+            resources: Vec::new(),
+            migrations: HashMap::new()
+        };
+    }
+
+    /// Select a resource type to be part of the snapshots.
+    pub fn resource<T: Resource + Reflect>(self) -> Self {
+        let mut resources: Vec<ResourceRegistration> = self.resources;
+        resources.retain(|registration| registration.type_name != T::static_type_name());
+        resources.push(ResourceRegistration::new::<T>());
+
+        return Self {
+            resources,
+            ..self
+        };
+    }
+
+    /// Add a migration of the snapshots from a version to the next one.
+    pub fn migration(self, from_version: u32, migration: impl Fn(&mut SaveData) -> Result<(), SaveError> + Send + Sync + 'static) -> Self {
+        let mut migrations: HashMap<u32, SaveMigration> = self.migrations;
+        migrations.insert(from_version, Arc::new(migration));
+
+        return Self {
+            migrations,
+            ..self
+        };
+    }
+
+    /// Returns the snapshot of the selected resources and the persistent entities of a world.
+    pub fn snapshot(&self, world: &World) -> Result<SaveData, SaveError> {
+        let mut resources: Vec<(String, ReflectValue)> = Vec::with_capacity(self.resources.len());
+
+        for registration in &self.resources {
+            let mut value: Option<Result<ReflectValue, ReflectError>> = None;
+
+            if !registration.reflect(world, &mut |resource| value = Some(resource.to_value())) {
+                return Err(SaveError::Reflect(ReflectError::MissingResource(registration.type_name.to_string())));
+            }
+            if let Some(value) = value {
+                resources.push((registration.type_name.to_string(), value?));
+            }
+        }
+
+        let persistent_entities: Vec<Entity> = world.query_filtered::<Entity, With<Persistent>>().collect();
+        return Ok(SaveData::new(self.version, resources, Scene::from_entities(world, &persistent_entities)?));
+    }
+
+    /// Migrate a snapshot to the current version, running its migrations in order.
+    pub fn migrate(&self, save_data: &mut SaveData) -> Result<(), SaveError> {
+        if save_data.version > self.version {
+            return Err(SaveError::UnsupportedVersion(save_data.version));
+        }
+
+        while save_data.version < self.version {
+            let migration: &SaveMigration = self.migrations.get(&save_data.version)
+                .ok_or(SaveError::MissingMigration(save_data.version))?;
+
+            migration(save_data)?;
+            save_data.version += 1;
+        }
+        return Ok(());
+    }
+
+    /// Create the selected resources of a snapshot, skipping the ones that it doesn't have.
+    pub(crate) fn construct_resources(&self, save_data: &SaveData) -> Result<Vec<Box<dyn Resource>>, SaveError> {
+        return self.resources.iter()
+            .filter_map(|registration| save_data.get_resource(registration.type_name).map(|value| registration.from_value(value).map_err(SaveError::from)))
+            .collect();
+    }
+
+    /// Save the snapshot of a world as a JSON file, relative to the assets directory.
+    pub fn save(&self, world: &World, path: &str) -> Result<(), SaveError> {
+        let path: PathBuf = AssetLoader::get_path(path);
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.snapshot(world)?.to_json())?)?;
+        return Ok(());
+    }
+
+    /// Load a snapshot from a JSON file, relative to the assets directory, migrated to the current version.
+    pub fn load(&self, path: &str) -> Result<SaveData, SaveError> {
+        let bytes: Vec<u8> = AssetLoader::load_bytes(path)?;
+        let mut save_data: SaveData = SaveData::from_json(&serde_json::from_slice(&bytes)?)?;
+
+        self.migrate(&mut save_data)?;
+        save_data.scene.validate()?;
+        return Ok(save_data);
+    }
+}
@@ -0,0 +1,254 @@
+use std::{collections::HashSet, fs, path::PathBuf};
+use super::{
+    asset_loader::AssetLoader,
+    hierarchy::{Parent, Children},
+    text::font::Fonts,
+    ecs::{
+        entity::Entity,
+        component::Component,
+        filter::Without,
+        reflect::{ReflectError, ReflectValue},
+        world::{World, Fps}
+    }
+};
+
+/// Enumerator to represent the different errors that the scene flow can throw.
+#[derive(Debug)]
+pub enum SceneError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Reflect(ReflectError),
+    Format(String),
+    MissingAsset(String),
+    InvalidParent(usize)
+}
+
+impl std::fmt::Display for SceneError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SceneError::Io(error) => write!(formatter, "IO Scene Error: {}.", error),
+            SceneError::Json(error) => write!(formatter, "JSON Scene Error: {}.", error),
+            SceneError::Reflect(error) => write!(formatter, "Reflect Scene Error: {}", error),
+            SceneError::Format(message) => write!(formatter, "Format Scene Error: {}.", message),
+            SceneError::MissingAsset(path) => write!(formatter, "Missing Asset Scene Error: '{}' was not found.", path),
+            SceneError::InvalidParent(index) => write!(formatter, "Invalid Parent Scene Error: entity {} must come after its parent.", index)
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+impl From<std::io::Error> for SceneError {
+    fn from(error: std::io::Error) -> Self {
+        return SceneError::Io(error);
+    }
+}
+
+impl From<serde_json::Error> for SceneError {
+    fn from(error: serde_json::Error) -> Self {
+        return SceneError::Json(error);
+    }
+}
+
+impl From<ReflectError> for SceneError {
+    fn from(error: ReflectError) -> Self {
+        return SceneError::Reflect(error);
+    }
+}
+
+/// Struct to represent an entity of a scene, with its components reflected by their type names.
+///
+/// The parent is the index of another entity of the scene, which always comes before its children.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SceneEntity {
+    pub parent: Option<usize>,
+    pub components: Vec<(String, ReflectValue)>
+}
+
+impl SceneEntity {
+    /// Create a new scene entity without a parent.
+    pub fn new(components: Vec<(String, ReflectValue)>) -> Self {
+        return Self {
+            parent: None,
+            components
+        };
+    }
+
+    /// Set the parent on initialization.
+    pub fn parent(self, parent: usize) -> Self {
+        return Self {
+            parent: Some(parent),
+            ..self
+        };
+    }
+}
+
+/// Struct to represent a scene, a list of entities that can be saved to and spawned from a JSON file.
+///
+/// Only the components registered for reflection on the world type registry are part of a scene.
+///
+/// ```json
+/// {
+///     "entities": [
+///         { "components": { "Transform": { "position": { "x": 0.0, "y": 0.0, "strategy": "Normalized" } } } },
+///         { "parent": 0, "components": { "Sprite": { "path": "textures/lotus_pink_256x256.png" } } }
+///     ]
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scene {
+    pub entities: Vec<SceneEntity>
+}
+
+impl Scene {
+    /// Create a new scene with its entities.
+    pub fn new(entities: Vec<SceneEntity>) -> Self {
+        return Self {
+            entities
+        };
+    }
+
+    /// Create a new scene from the living entities of a world.
+    ///
+    /// The FPS text of the engine is not part of the scene.
+    ///
+    /// A component that can't be reflected is returned as an error.
+    pub fn from_world(world: &World) -> Result<Self, ReflectError> {
+        let roots: Vec<Entity> = world.query_filtered::<Entity, (Without<Parent>, Without<Fps>)>().collect();
+        return Self::from_entities(world, &roots);
+    }
+
+    /// Create a new scene from a list of living entities of a world and their children.
+    ///
+    /// The entities are sorted by their indexes, and the children of an entity come right after it.
+    ///
+    /// An entity reached more than once, like on a cycle of children, is only part of the scene once.
+    ///
+    /// A component that can't be reflected is returned as an error.
+    pub fn from_entities(world: &World, entities: &[Entity]) -> Result<Self, ReflectError> {
+        let mut roots: Vec<Entity> = entities.iter()
+            .filter(|entity| world.get_entity_component::<Parent>(entity).is_none_or(|parent| !entities.contains(&parent.0)))
+            .copied()
+            .collect();
+        roots.sort_by_key(|entity| entity.index);
+        roots.dedup();
+
+        let mut scene_entities: Vec<SceneEntity> = Vec::new();
+        let mut visited: HashSet<Entity> = HashSet::new();
+        let mut pending: Vec<(Entity, Option<usize>)> = roots.into_iter().rev().map(|entity| (entity, None)).collect();
+
+        while let Some((entity, parent)) = pending.pop() {
+            if !visited.insert(entity) {
+                continue;
+            }
+            let index: usize = scene_entities.len();
+            let components: Vec<(String, ReflectValue)> = world.reflect_entity(&entity)?.into_iter()
+                .map(|(type_name, value)| (type_name.to_string(), value))
+                .collect();
+
+            scene_entities.push(SceneEntity { parent, components });
+
+            if let Some(children) = world.get_entity_component::<Children>(&entity) {
+                pending.extend(children.0.iter().rev().map(|child| (*child, Some(index))));
+            }
+        }
+        return Ok(Self::new(scene_entities));
+    }
+
+    /// Returns the scene as a JSON value.
+    pub fn to_json(&self) -> serde_json::Value {
+        let entities: Vec<serde_json::Value> = self.entities.iter().map(|entity| {
+            let mut object: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
+
+            if let Some(parent) = entity.parent {
+                object.insert("parent".to_string(), serde_json::Value::from(parent));
+            }
+            object.insert("components".to_string(), serde_json::Value::Object(
+                entity.components.iter().map(|(type_name, value)| (type_name.clone(), value.to_json())).collect()
+            ));
+            return serde_json::Value::Object(object);
+        }).collect();
+
+        return serde_json::json!({ "entities": entities });
+    }
+
+    /// Create a new scene from a JSON value.
+    pub fn from_json(json: &serde_json::Value) -> Result<Self, SceneError> {
+        let entities: &Vec<serde_json::Value> = json.get("entities")
+            .and_then(|entities| entities.as_array())
+            .ok_or_else(|| SceneError::Format("expected a list of entities".to_string()))?;
+
+        let mut scene_entities: Vec<SceneEntity> = Vec::with_capacity(entities.len());
+        for (index, entity) in entities.iter().enumerate() {
+            let parent: Option<usize> = match entity.get("parent") {
+                None | Some(serde_json::Value::Null) => None,
+                Some(parent) => Some(parent.as_u64()
+                    .ok_or_else(|| SceneError::Format(format!("the parent of entity {} must be an index", index)))? as usize)
+            };
+
+            let components: Vec<(String, ReflectValue)> = entity.get("components")
+                .and_then(|components| components.as_object())
+                .ok_or_else(|| SceneError::Format(format!("expected a map of components on entity {}", index)))?
+                .iter()
+                .map(|(type_name, value)| (type_name.clone(), ReflectValue::from_json(value)))
+                .collect();
+
+            scene_entities.push(SceneEntity { parent, components });
+        }
+        return Ok(Self::new(scene_entities));
+    }
+
+    /// Save the scene as a JSON file, relative to the assets directory.
+    pub fn save(&self, path: &str) -> Result<(), SceneError> {
+        let path: PathBuf = AssetLoader::get_path(path);
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.to_json())?)?;
+        return Ok(());
+    }
+
+    /// Load a scene from a JSON file, relative to the assets directory.
+    ///
+    /// The sprites and fonts of the scene are resolved through the asset loader, so a missing asset fails the loading.
+    pub fn load(path: &str) -> Result<Self, SceneError> {
+        let bytes: Vec<u8> = AssetLoader::load_bytes(path)?;
+        let scene: Scene = Self::from_json(&serde_json::from_slice(&bytes)?)?;
+        scene.validate()?;
+        return Ok(scene);
+    }
+
+    /// Validate the parents and the asset paths of the scene entities.
+    pub fn validate(&self) -> Result<(), SceneError> {
+        for (index, entity) in self.entities.iter().enumerate() {
+            if entity.parent.is_some_and(|parent| parent >= index) {
+                return Err(SceneError::InvalidParent(index));
+            }
+
+            for (type_name, value) in &entity.components {
+                let asset_path: Option<&str> = match type_name.as_str() {
+                    "Sprite" => value.get("path").and_then(|path| path.as_str()),
+                    "Text" => value.get("font").and_then(|font| font.get("path")).and_then(|path| path.as_str()),
+                    _ => None
+                };
+
+                if let Some(asset_path) = asset_path {
+                    if Fonts::from_path(asset_path.to_string()).is_none() && !AssetLoader::get_path(asset_path).exists() {
+                        return Err(SceneError::MissingAsset(asset_path.to_string()));
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    /// Create the components of the scene entities by their type names on the world type registry.
+    pub(crate) fn construct_components(&self, world: &World) -> Result<Vec<Vec<Box<dyn Component>>>, SceneError> {
+        return self.entities.iter().map(|entity| {
+            return entity.components.iter()
+                .map(|(type_name, value)| world.type_registry.construct_component(type_name, value).map_err(SceneError::from))
+                .collect::<Result<Vec<Box<dyn Component>>, SceneError>>();
+        }).collect();
+    }
+}
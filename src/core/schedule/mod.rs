@@ -0,0 +1,11 @@
+/// Stages in which the systems are executed on each frame.
+pub mod stage;
+
+/// Systems that hold the game logic.
+pub mod system;
+
+/// Schedule that orders and executes the systems.
+pub mod schedule;
+
+/// Game states and their transition hooks.
+pub mod state;
@@ -0,0 +1,571 @@
+use std::{any::TypeId, collections::{BTreeMap, HashMap, HashSet}};
+use super::{
+    stage::Stage,
+    state::{States, State, NextState, OnEnter, OnExit, StateHook, StateSystem},
+    system::{System, SystemFunction, ParallelFunction, ParallelContext},
+    super::{context::Context, event::{synchronizer, channel::Events}, hierarchy, ecs::{world::World, command::Commands}}
+};
+use crate::utils::constants::system::{EVENTS, CAMERA, ANIMATIONS, COLLISIONS, GRAVITY, PARTICLES, GAMEPAD, TRANSFORMS};
+
+/// Function that applies the pending transition of a specific state type.
+pub type StateTransition = fn(&mut Schedule, &mut Context);
+
+/// Function that swaps the buffers of a specific event channel.
+pub type EventUpdate = fn(&World);
+
+/// Trait to represent where a system can be added on the schedule, a stage or a state hook.
+pub trait ScheduleLabel {
+    /// Add the system on the schedule at the place represented by the label.
+    fn add_system_to(self, schedule: &mut Schedule, system: System);
+}
+
+impl ScheduleLabel for Stage {
+    fn add_system_to(self, schedule: &mut Schedule, system: System) {
+        schedule.stages.entry(self).or_default().push(system);
+        schedule.dirty_stages.insert(self);
+    }
+}
+
+impl<S: States> ScheduleLabel for OnEnter<S> {
+    fn add_system_to(self, schedule: &mut Schedule, system: System) {
+        schedule.state_systems.push(StateSystem { hook: StateHook::Enter, state: Box::new(self.0), system });
+    }
+}
+
+impl<S: States> ScheduleLabel for OnExit<S> {
+    fn add_system_to(self, schedule: &mut Schedule, system: System) {
+        schedule.state_systems.push(StateSystem { hook: StateHook::Exit, state: Box::new(self.0), system });
+    }
+}
+
+/// Enumerator to represent a change on a system of a running schedule, applied after it runs.
+enum SystemEdit {
+    Enable(String),
+    Disable(String),
+    Remove(String)
+}
+
+/// Struct to represent the schedule that stores the systems by stage and executes them on each frame.
+///
+/// Inside of a stage, the systems run in the order they were added unless their before/after constraints say otherwise.
+///
+/// The names of the systems are unique across the schedule.
+///
+/// While the schedule of the context runs, the context holds a deferred schedule in its place.
+/// The systems added to it are appended after the run, and the ones enabled, disabled or removed on it
+/// are changed on the running schedule after the run as well.
+pub struct Schedule {
+    pub stages: BTreeMap<Stage, Vec<System>>,
+    pub state_systems: Vec<StateSystem>,
+    pub state_transitions: Vec<(TypeId, StateTransition)>,
+    pub event_updates: Vec<(TypeId, EventUpdate)>,
+    dirty_stages: HashSet<Stage>,
+    running_systems: HashMap<String, (Option<Stage>, bool)>,
+    system_edits: Vec<SystemEdit>
+}
+
+impl Schedule {
+    /// Create a new schedule with the engine built-in systems on the PreUpdate stage.
+    ///
+    /// The built-in systems are the synchronizers of events, camera, animations, collisions, gravity, particles and gamepads.
+    pub fn new() -> Self {
+        let mut schedule: Schedule = Self::empty();
+        schedule
+            .add_system(Stage::PreUpdate, System::new(EVENTS, |context: &mut Context| synchronizer::events(&mut context.world, &context.render_state)))
+            .add_system(Stage::PreUpdate, System::new(CAMERA, |context: &mut Context| synchronizer::camera(&mut context.world, &mut context.render_state)))
+            .add_system(Stage::PreUpdate, System::new(ANIMATIONS, |context: &mut Context| synchronizer::animations(&mut context.world, context.delta)))
+            .add_system(Stage::PreUpdate, System::new(COLLISIONS, |context: &mut Context| synchronizer::collisions(&mut context.world)))
+            .add_system(Stage::PreUpdate, System::new(GRAVITY, |context: &mut Context| synchronizer::gravity(&mut context.world, &mut context.render_state, context.delta)))
+            .add_system(Stage::PreUpdate, System::new(PARTICLES, |context: &mut Context| synchronizer::particles(&mut context.world, &context.render_state, context.delta)))
+            .add_system(Stage::PreUpdate, System::new(GAMEPAD, |context: &mut Context| {
+                if context.gamepad_listener.enabled {
+                    context.gamepad_listener.manage(&mut context.world);
+                }
+            }))
+            .add_system(Stage::PostUpdate, System::new(TRANSFORMS, |context: &mut Context| hierarchy::propagate_transforms(&context.world)));
+        return schedule;
+    }
+
+    /// Create a new schedule without any system.
+    pub fn empty() -> Self {
+        return Self {
+            stages: BTreeMap::new(),
+            state_systems: Vec::new(),
+            state_transitions: Vec::new(),
+            event_updates: Vec::new(),
+            dirty_stages: HashSet::new(),
+            running_systems: HashMap::new(),
+            system_edits: Vec::new()
+        };
+    }
+
+    /// Create a new schedule without any system that stands for this one while it runs.
+    ///
+    /// The changes made on it are applied to this schedule with apply_deferred.
+    pub fn deferred(&self) -> Self {
+        let mut running_systems: HashMap<String, (Option<Stage>, bool)> = HashMap::new();
+
+        for (stage, systems) in &self.stages {
+            for system in systems {
+                running_systems.insert(system.name.clone(), (Some(*stage), system.enabled));
+            }
+        }
+
+        for state_system in &self.state_systems {
+            running_systems.insert(state_system.system.name.clone(), (None, state_system.system.enabled));
+        }
+
+        return Self {
+            running_systems,
+            ..Self::empty()
+        };
+    }
+
+    /// Apply the changes made on a deferred schedule, in the order they were made, and then move its systems into this one.
+    pub fn apply_deferred(&mut self, deferred: Schedule) {
+        let mut deferred: Schedule = deferred;
+
+        for system_edit in std::mem::take(&mut deferred.system_edits) {
+            match system_edit {
+                SystemEdit::Enable(name) => {
+                    self.enable_system(&name);
+                },
+                SystemEdit::Disable(name) => {
+                    self.disable_system(&name);
+                },
+                SystemEdit::Remove(name) => {
+                    self.remove_system(&name);
+                }
+            }
+        }
+        self.append(deferred);
+    }
+
+    /// Add a new system to a stage or to a state hook, like OnEnter and OnExit.
+    ///
+    /// If a system with the same name already exists, it is replaced.
+    pub fn add_system(&mut self, label: impl ScheduleLabel, system: System) -> &mut Self {
+        self.remove_system(&system.name);
+        label.add_system_to(self, system);
+        return self;
+    }
+
+    /// Register a state type, so its pending transitions are applied at the start of each frame.
+    ///
+    /// The State and NextState resources are expected on the world, see Context::init_state.
+    pub fn add_state<S: States>(&mut self) -> &mut Self {
+        if !self.state_transitions.iter().any(|(type_id, _)| *type_id == TypeId::of::<S>()) {
+            self.state_transitions.push((TypeId::of::<S>(), apply_state_transition::<S>));
+        }
+        return self;
+    }
+
+    /// Register an event channel type, so its buffers are swapped at the start of each frame.
+    pub fn add_event<T: Send + Sync + 'static>(&mut self) -> &mut Self {
+        if !self.event_updates.iter().any(|(type_id, _)| *type_id == TypeId::of::<T>()) {
+            self.event_updates.push((TypeId::of::<T>(), update_events::<T>));
+        }
+        return self;
+    }
+
+    /// Remove a system by its name and returns it.
+    ///
+    /// Useful to move a system to another stage or to declare new ordering constraints on it.
+    ///
+    /// On a deferred schedule, a running system is removed after the run, so it's not returned.
+    pub fn remove_system(&mut self, name: &str) -> Option<System> {
+        if self.running_systems.remove(name).is_some() {
+            self.system_edits.push(SystemEdit::Remove(name.to_string()));
+        }
+
+        for (stage, systems) in self.stages.iter_mut() {
+            if let Some(index) = systems.iter().position(|system| system.name == name) {
+                self.dirty_stages.insert(*stage);
+                return Some(systems.remove(index));
+            }
+        }
+
+        if let Some(index) = self.state_systems.iter().position(|state_system| state_system.system.name == name) {
+            return Some(self.state_systems.remove(index).system);
+        }
+        return None;
+    }
+
+    /// Enable a system by its name.
+    ///
+    /// Returns if the system was found.
+    pub fn enable_system(&mut self, name: &str) -> bool {
+        return self.set_system_enabled(name, true);
+    }
+
+    /// Disable a system by its name, so it is skipped until enabled again.
+    ///
+    /// Returns if the system was found.
+    pub fn disable_system(&mut self, name: &str) -> bool {
+        return self.set_system_enabled(name, false);
+    }
+
+    /// Returns if a system exists and is enabled.
+    pub fn is_system_enabled(&self, name: &str) -> bool {
+        if let Some((_, enabled)) = self.running_systems.get(name) {
+            return *enabled;
+        }
+        return self.get_system(name).is_some_and(|system| system.enabled);
+    }
+
+    /// Returns if a system exists on the schedule.
+    pub fn contains_system(&self, name: &str) -> bool {
+        return self.running_systems.contains_key(name) || self.get_system(name).is_some();
+    }
+
+    /// Returns the stage in which a system is stored.
+    pub fn get_system_stage(&self, name: &str) -> Option<Stage> {
+        if let Some((stage, _)) = self.running_systems.get(name) {
+            return *stage;
+        }
+        return self.stages.iter()
+            .find(|(_, systems)| systems.iter().any(|system| system.name == name))
+            .map(|(stage, _)| *stage);
+    }
+
+    /// Returns the names of the systems of a stage in their execution order.
+    pub fn get_system_names(&mut self, stage: Stage) -> Vec<String> {
+        self.sort_stage(stage);
+        return self.stages.get(&stage)
+            .map(|systems| systems.iter().map(|system| system.name.clone()).collect())
+            .unwrap_or_default();
+    }
+
+    /// Move all the systems and states of another schedule into this one.
+    pub fn append(&mut self, other: Schedule) {
+        for (stage, systems) in other.stages {
+            for system in systems {
+                self.add_system(stage, system);
+            }
+        }
+
+        for state_system in other.state_systems {
+            self.remove_system(&state_system.system.name);
+            self.state_systems.push(state_system);
+        }
+
+        for (type_id, state_transition) in other.state_transitions {
+            if !self.state_transitions.iter().any(|(other_type_id, _)| *other_type_id == type_id) {
+                self.state_transitions.push((type_id, state_transition));
+            }
+        }
+
+        for (type_id, event_update) in other.event_updates {
+            if !self.event_updates.iter().any(|(other_type_id, _)| *other_type_id == type_id) {
+                self.event_updates.push((type_id, event_update));
+            }
+        }
+    }
+
+    /// Swap the event channels buffers, apply the pending state transitions and then execute all the enabled systems, stage by stage.
+    pub fn run(&mut self, context: &mut Context) {
+        self.update_events(context);
+        self.apply_state_transitions(context);
+
+        for stage in Stage::ALL {
+            self.run_stage(stage, context);
+        }
+    }
+
+    /// Returns the names of the enabled systems of a stage grouped by the batches in which they run.
+    ///
+    /// The systems of the same batch run in parallel.
+    pub fn get_system_batches(&mut self, stage: Stage) -> Vec<Vec<String>> {
+        self.sort_stage(stage);
+        let Some(systems) = self.stages.get(&stage) else {
+            return Vec::new();
+        };
+
+        let enabled: Vec<bool> = systems.iter().map(|system| system.enabled).collect();
+
+        return get_batches(systems, &enabled).into_iter()
+            .map(|batch| batch.into_iter().map(|index| systems[index].name.clone()).collect())
+            .collect();
+    }
+
+    /// Execute all the enabled systems of a specific stage.
+    ///
+    /// Consecutive parallel systems without conflicting accesses or ordering constraints between them run together on the thread pool.
+    ///
+    /// The run conditions of a system are checked right before its batch runs, so they see the changes of the previous batches.
+    ///
+    /// The end of the stage is a sync point, where the commands buffered by its systems are flushed in the order the systems ran.
+    pub fn run_stage(&mut self, stage: Stage, context: &mut Context) {
+        self.sort_stage(stage);
+
+        if let Some(systems) = self.stages.get_mut(&stage) {
+            let enabled: Vec<bool> = systems.iter().map(|system| system.enabled).collect();
+
+            for batch in get_batches(systems, &enabled) {
+                let mut batch_systems: Vec<&mut System> = systems.iter_mut()
+                    .enumerate()
+                    .filter(|(index, _)| batch.contains(index))
+                    .map(|(_, system)| system)
+                    .filter_map(|system| system.should_run(&context.world).then_some(system))
+                    .collect();
+                run_batch(&mut batch_systems, context);
+            }
+        }
+
+        context.commands.flush_commands(&mut context.world, &mut context.render_state);
+    }
+
+    /// Apply the pending transitions of all the registered state types.
+    ///
+    /// The OnExit systems of the previous state run before the OnEnter systems of the new one.
+    pub fn apply_state_transitions(&mut self, context: &mut Context) {
+        let state_transitions: Vec<StateTransition> = self.state_transitions.iter().map(|(_, state_transition)| *state_transition).collect();
+
+        for state_transition in state_transitions {
+            state_transition(self, context);
+        }
+    }
+
+    /// Swap the buffers of all the registered event channels.
+    ///
+    /// The events sent two frames ago are dropped.
+    pub fn update_events(&self, context: &mut Context) {
+        for (_, event_update) in &self.event_updates {
+            event_update(&context.world);
+        }
+    }
+
+    /// Execute the systems hooked to a specific moment of a specific state.
+    fn run_state_systems<S: States>(&mut self, hook: StateHook, state: &S, context: &mut Context) {
+        for state_system in self.state_systems.iter_mut().filter(|state_system| state_system.is_hooked_to(hook, state)) {
+            if state_system.system.should_run(&context.world) {
+                state_system.system.run(context);
+            }
+        }
+    }
+
+    /// Returns a system by its name.
+    fn get_system(&self, name: &str) -> Option<&System> {
+        return self.stages.values().flatten()
+            .chain(self.state_systems.iter().map(|state_system| &state_system.system))
+            .find(|system| system.name == name);
+    }
+
+    /// Set the enabled state of a system by its name.
+    fn set_system_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        if let Some((_, running_enabled)) = self.running_systems.get_mut(name) {
+            *running_enabled = enabled;
+            self.system_edits.push(if enabled { SystemEdit::Enable(name.to_string()) } else { SystemEdit::Disable(name.to_string()) });
+            return true;
+        }
+
+        if let Some(system) = self.stages.values_mut().flatten()
+            .chain(self.state_systems.iter_mut().map(|state_system| &mut state_system.system))
+            .find(|system| system.name == name)
+        {
+            system.enabled = enabled;
+            return true;
+        }
+        return false;
+    }
+
+    /// Sort the systems of a stage by their ordering constraints, if needed.
+    ///
+    /// Constraints that point to systems outside of the stage are ignored.
+    fn sort_stage(&mut self, stage: Stage) {
+        if !self.dirty_stages.remove(&stage) {
+            return;
+        }
+
+        let Some(systems) = self.stages.get_mut(&stage) else {
+            return;
+        };
+        let indexes_by_name: HashMap<&str, usize> = systems.iter().enumerate().map(|(index, system)| (system.name.as_str(), index)).collect();
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); systems.len()];
+        let mut predecessors_count: Vec<usize> = vec![0; systems.len()];
+
+        for (index, system) in systems.iter().enumerate() {
+            for before in &system.before {
+                if let Some(&other) = indexes_by_name.get(before.as_str()) {
+                    successors[index].push(other);
+                    predecessors_count[other] += 1;
+                }
+            }
+
+            for after in &system.after {
+                if let Some(&other) = indexes_by_name.get(after.as_str()) {
+                    successors[other].push(index);
+                    predecessors_count[index] += 1;
+                }
+            }
+        }
+
+        let mut order: Vec<usize> = Vec::with_capacity(systems.len());
+        let mut ready: Vec<usize> = (0..systems.len()).filter(|index| predecessors_count[*index] == 0).collect();
+
+        while !ready.is_empty() {
+            let (position, _) = ready.iter().enumerate().min_by_key(|(_, index)| **index).unwrap();
+            let index: usize = ready.swap_remove(position);
+            order.push(index);
+
+            for &successor in &successors[index] {
+                predecessors_count[successor] -= 1;
+
+                if predecessors_count[successor] == 0 {
+                    ready.push(successor);
+                }
+            }
+        }
+
+        if order.len() != systems.len() {
+            panic!("Cyclic ordering constraints between the systems of the {:?} stage.", stage);
+        }
+
+        let mut unordered: Vec<Option<System>> = systems.drain(..).map(Some).collect();
+        systems.extend(order.into_iter().map(|index| unordered[index].take().unwrap()));
+    }
+}
+
+/// Swaps the buffers of a specific event channel, if it was added to the world.
+fn update_events<T: Send + Sync + 'static>(world: &World) {
+    if let Some(mut events) = world.get_resource_mut::<Events<T>>() {
+        events.update();
+    }
+}
+
+/// Applies the pending transition of a specific state type.
+///
+/// On the first frame, the OnEnter systems of the initial state run.
+fn apply_state_transition<S: States>(schedule: &mut Schedule, context: &mut Context) {
+    let initial: Option<S> = {
+        let Some(mut state) = context.world.get_resource_mut::<State<S>>() else {
+            return;
+        };
+
+        if state.entered {
+            None
+        } else {
+            state.entered = true;
+            Some(state.current.clone())
+        }
+    };
+
+    if let Some(initial) = initial {
+        schedule.run_state_systems(StateHook::Enter, &initial, context);
+    }
+
+    let Some(next) = context.world.get_resource_mut::<NextState<S>>().and_then(|mut next_state| next_state.pending.take()) else {
+        return;
+    };
+    let previous: S = {
+        let mut state = context.world.get_resource_mut::<State<S>>().unwrap();
+
+        if state.current == next {
+            return;
+        }
+        std::mem::replace(&mut state.current, next.clone())
+    };
+
+    schedule.run_state_systems(StateHook::Exit, &previous, context);
+    schedule.run_state_systems(StateHook::Enter, &next, context);
+}
+
+/// Returns the indexes of the enabled systems grouped by the batches in which they run.
+///
+/// An exclusive system always has its own batch.
+///
+/// The systems of a batch share their last run, so their Added and Changed filters match the same changes.
+///
+/// Systems ordered through others, even the ones that don't run, never share a batch.
+fn get_batches(systems: &[System], enabled: &[bool]) -> Vec<Vec<usize>> {
+    let reachability: Vec<Vec<bool>> = get_reachability(systems);
+    let mut batches: Vec<Vec<usize>> = Vec::new();
+    let mut batch: Vec<usize> = Vec::new();
+
+    for (index, system) in systems.iter().enumerate().filter(|(index, _)| enabled[*index]) {
+        let fits_on_batch: bool = system.is_parallel() && batch.iter().all(|other| {
+            systems[*other].is_parallel()
+                && !system.conflicts_with(&systems[*other])
+                && !reachability[*other][index]
+                && systems[*other].last_run == system.last_run
+        });
+
+        if !fits_on_batch && !batch.is_empty() {
+            batches.push(std::mem::take(&mut batch));
+        }
+        batch.push(index);
+    }
+
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    return batches;
+}
+
+/// Returns which systems must run after each system, following their ordering constraints transitively.
+///
+/// The systems are already sorted, so a system can only reach the ones after it.
+fn get_reachability(systems: &[System]) -> Vec<Vec<bool>> {
+    let mut reachability: Vec<Vec<bool>> = vec![vec![false; systems.len()]; systems.len()];
+
+    for index in (0..systems.len()).rev() {
+        for other in index + 1..systems.len() {
+            if systems[index].is_ordered_with(&systems[other]) {
+                let reachable_from_other: Vec<bool> = reachability[other].clone();
+                reachability[index][other] = true;
+
+                for (reachable, reachable_from_other) in reachability[index].iter_mut().zip(reachable_from_other) {
+                    *reachable |= reachable_from_other;
+                }
+            }
+        }
+    }
+    return reachability;
+}
+
+/// Execute a batch of systems and clear it, running them on the thread pool if there is more than one.
+///
+/// The commands of each system are appended to the context commands in the order of the batch.
+fn run_batch(batch: &mut Vec<&mut System>, context: &mut Context) {
+    if batch.len() <= 1 {
+        if let Some(system) = batch.pop() {
+            system.run(context);
+        }
+        return;
+    }
+
+    let run_tick: u32 = context.world.begin_system_run(batch[0].last_run);
+    let world: &World = &context.world;
+    let delta: f32 = context.delta;
+    let mut commands_by_system: Vec<Commands> = batch.iter().map(|_| world.commands()).collect();
+    let mut functions: Vec<(&mut ParallelFunction, &mut Commands)> = batch.iter_mut()
+        .zip(commands_by_system.iter_mut())
+        .filter_map(|(system, commands)| match &mut system.function {
+            SystemFunction::Parallel(function) => Some((function, commands)),
+            SystemFunction::Exclusive(_) => None
+        })
+        .collect();
+
+    rayon::scope(|scope| {
+        for (function, commands) in functions.drain(..) {
+            scope.spawn(move |_| function(&mut ParallelContext { world, commands, delta }));
+        }
+    });
+
+    context.world.end_system_run();
+
+    for system in batch.iter_mut() {
+        system.last_run = run_tick;
+    }
+
+    for mut commands in commands_by_system {
+        context.commands.append(&mut commands);
+    }
+    batch.clear();
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
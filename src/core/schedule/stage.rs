@@ -0,0 +1,19 @@
+/// Enumerator to represent the stages of a frame in which systems are executed.
+///
+/// The stages are executed in the order they are declared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Stage {
+    /// Executed right after the pending commands are flushed, where the engine synchronizers live.
+    PreUpdate,
+    /// Executed after the synchronizers, where the end-user update function lives.
+    Update,
+    /// Executed after the update logic.
+    PostUpdate,
+    /// Executed right before the frame is drawn.
+    Render
+}
+
+impl Stage {
+    /// All the stages in execution order.
+    pub const ALL: [Stage; 4] = [Stage::PreUpdate, Stage::Update, Stage::PostUpdate, Stage::Render];
+}
@@ -0,0 +1,114 @@
+use std::{any::Any, fmt::Debug};
+use super::{system::System, super::ecs::{resource::Resource, world::World}};
+
+/// Trait to annotate the types that can be used as game states, usually enumerators.
+pub trait States: Clone + PartialEq + Debug + Send + Sync + 'static {}
+
+impl<T: Clone + PartialEq + Debug + Send + Sync + 'static> States for T {}
+
+/// Struct to represent the resource that stores the current game state.
+///
+/// The state is only changed between frames, by the value set on the NextState resource.
+pub struct State<S: States> {
+    pub(crate) current: S,
+    pub(crate) entered: bool
+}
+
+impl<S: States> State<S> {
+    /// Create a new state resource with its initial value.
+    pub fn new(initial: S) -> Self {
+        return Self {
+            current: initial,
+            entered: false
+        };
+    }
+
+    /// Returns the current state.
+    pub fn get(&self) -> &S {
+        return &self.current;
+    }
+}
+
+impl<S: States> Resource for State<S> {
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
+}
+
+/// Struct to represent the resource that stores the state to be applied at the start of the next frame.
+pub struct NextState<S: States> {
+    pub(crate) pending: Option<S>
+}
+
+impl<S: States> NextState<S> {
+    /// Create a new next state resource without a pending state.
+    pub fn new() -> Self {
+        return Self {
+            pending: None
+        };
+    }
+
+    /// Set the state to be applied at the start of the next frame.
+    pub fn set(&mut self, state: S) {
+        self.pending = Some(state);
+    }
+
+    /// Returns the state to be applied, if any.
+    pub fn get(&self) -> Option<&S> {
+        return self.pending.as_ref();
+    }
+}
+
+impl<S: States> Default for NextState<S> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<S: States> Resource for NextState<S> {
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
+}
+
+/// Struct to represent the hook of systems that run once when a state is entered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OnEnter<S: States>(pub S);
+
+/// Struct to represent the hook of systems that run once when a state is exited.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OnExit<S: States>(pub S);
+
+/// Returns a run condition that is true while the current state is the one passed.
+pub fn in_state<S: States>(state: S) -> impl FnMut(&World) -> bool {
+    return move |world: &World| world.get_resource::<State<S>>().is_some_and(|current| current.current == state);
+}
+
+/// Enumerator that store the moments of a state transition in which systems can run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateHook {
+    Enter,
+    Exit
+}
+
+/// Struct to represent a system that runs once when a specific state is entered or exited.
+pub struct StateSystem {
+    pub hook: StateHook,
+    pub state: Box<dyn Any + Send + Sync>,
+    pub system: System
+}
+
+impl StateSystem {
+    /// Returns if the system is hooked to a specific moment of a specific state.
+    pub fn is_hooked_to<S: States>(&self, hook: StateHook, state: &S) -> bool {
+        return self.hook == hook && self.state.downcast_ref::<S>() == Some(state);
+    }
+}
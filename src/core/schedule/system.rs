@@ -0,0 +1,180 @@
+use std::{any::TypeId, collections::HashSet};
+use super::super::{context::Context, ecs::{world::World, command::Commands}};
+
+/// Struct to represent the data available to a system that runs in parallel with others.
+///
+/// The world is shared between the systems, so all the mutations made through it go by interior mutability.
+/// The commands are buffered by system and appended to the context commands after the parallel batch.
+pub struct ParallelContext<'a> {
+    pub world: &'a World,
+    pub commands: &'a mut Commands,
+    pub delta: f32
+}
+
+/// Function of a system that runs in parallel with others.
+pub type ParallelFunction = Box<dyn FnMut(&mut ParallelContext) + Send>;
+
+/// Condition that decides if a system runs on the current frame.
+pub type RunCondition = Box<dyn FnMut(&World) -> bool>;
+
+/// Enumerator that store the kinds of functions a system can hold.
+pub enum SystemFunction {
+    /// Function with exclusive access to the whole context, running alone.
+    Exclusive(Box<dyn FnMut(&mut Context)>),
+    /// Function with shared access to the world, running in parallel with the non conflicting systems.
+    Parallel(ParallelFunction)
+}
+
+/// Struct to represent a system, a named function that runs over the context on each frame.
+///
+/// The ordering relative to other systems of the same stage is declared by their names.
+///
+/// The components and resources read or written by a parallel system are declared by their types.
+///
+/// The world tick of its last run is kept, so its Added and Changed filters match the changes made since then.
+pub struct System {
+    pub name: String,
+    pub function: SystemFunction,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+    pub reads: HashSet<TypeId>,
+    pub writes: HashSet<TypeId>,
+    pub conditions: Vec<RunCondition>,
+    pub enabled: bool,
+    pub(crate) last_run: u32
+}
+
+impl System {
+    /// Create a new system by its name and function.
+    ///
+    /// The system has exclusive access to the context, so it never runs in parallel.
+    pub fn new(name: impl Into<String>, function: impl FnMut(&mut Context) + 'static) -> Self {
+        return Self::with_function(name, SystemFunction::Exclusive(Box::new(function)));
+    }
+
+    /// Create a new system that can run in parallel by its name and function.
+    ///
+    /// Systems that run in parallel must declare the components and resources they access with reads and writes.
+    pub fn parallel(name: impl Into<String>, function: impl FnMut(&mut ParallelContext) + Send + 'static) -> Self {
+        return Self::with_function(name, SystemFunction::Parallel(Box::new(function)));
+    }
+
+    /// Create a new system by its name and kind of function.
+    fn with_function(name: impl Into<String>, function: SystemFunction) -> Self {
+        return Self {
+            name: name.into(),
+            function,
+            before: Vec::new(),
+            after: Vec::new(),
+            reads: HashSet::new(),
+            writes: HashSet::new(),
+            conditions: Vec::new(),
+            enabled: true,
+            last_run: 0
+        };
+    }
+
+    /// Returns the system with the constraint of running before another one.
+    pub fn before(self, name: impl Into<String>) -> Self {
+        let mut before: Vec<String> = self.before;
+        before.push(name.into());
+
+        return Self {
+            before,
+            ..self
+        };
+    }
+
+    /// Returns the system with the constraint of running after another one.
+    pub fn after(self, name: impl Into<String>) -> Self {
+        let mut after: Vec<String> = self.after;
+        after.push(name.into());
+
+        return Self {
+            after,
+            ..self
+        };
+    }
+
+    /// Returns the system declaring that it reads a component or resource.
+    pub fn reads<T: 'static>(self) -> Self {
+        let mut reads: HashSet<TypeId> = self.reads;
+        reads.insert(TypeId::of::<T>());
+
+        return Self {
+            reads,
+            ..self
+        };
+    }
+
+    /// Returns the system declaring that it writes a component or resource.
+    pub fn writes<T: 'static>(self) -> Self {
+        let mut writes: HashSet<TypeId> = self.writes;
+        writes.insert(TypeId::of::<T>());
+
+        return Self {
+            writes,
+            ..self
+        };
+    }
+
+    /// Returns the system with a condition that must be true for it to run, like in_state.
+    pub fn run_if(self, condition: impl FnMut(&World) -> bool + 'static) -> Self {
+        let mut conditions: Vec<RunCondition> = self.conditions;
+        conditions.push(Box::new(condition));
+
+        return Self {
+            conditions,
+            ..self
+        };
+    }
+
+    /// Returns the system with its initial enabled state.
+    pub fn enabled(self, enabled: bool) -> Self {
+        return Self {
+            enabled,
+            ..self
+        };
+    }
+
+    /// Returns if the system can run in parallel with others.
+    pub fn is_parallel(&self) -> bool {
+        return matches!(self.function, SystemFunction::Parallel(_));
+    }
+
+    /// Returns if the system is enabled and all of its run conditions are true.
+    pub(crate) fn should_run(&mut self, world: &World) -> bool {
+        return self.enabled && self.conditions.iter_mut().all(|condition| condition(world));
+    }
+
+    /// Returns if the declared accesses of two systems conflict, which means that one writes what the other accesses.
+    pub fn conflicts_with(&self, other: &System) -> bool {
+        return !self.writes.is_disjoint(&other.writes)
+            || !self.writes.is_disjoint(&other.reads)
+            || !self.reads.is_disjoint(&other.writes);
+    }
+
+    /// Returns if the system has an ordering constraint related to another one.
+    pub(crate) fn is_ordered_with(&self, other: &System) -> bool {
+        return self.before.contains(&other.name)
+            || self.after.contains(&other.name)
+            || other.before.contains(&self.name)
+            || other.after.contains(&self.name);
+    }
+
+    /// Execute the system function with exclusive access to the context.
+    pub(crate) fn run(&mut self, context: &mut Context) {
+        let run_tick: u32 = context.world.begin_system_run(self.last_run);
+
+        match &mut self.function {
+            SystemFunction::Exclusive(function) => function(context),
+            SystemFunction::Parallel(function) => {
+                let mut commands: Commands = context.world.commands();
+                function(&mut ParallelContext { world: &context.world, commands: &mut commands, delta: context.delta });
+                context.commands.append(&mut commands);
+            }
+        }
+        context.world.end_system_run();
+        self.last_run = run_tick;
+    }
+}
@@ -1,7 +1,8 @@
+use lotus_proc_macros::Reflect;
 use super::{shape::Circle, orientation::Orientation, super::{managers::render::manager::Vertex}};
 
 /// Enumerator that represent the actual shape of the geometric form.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Reflect)]
 pub enum GeometryType {
     Triangle,
     Square,
@@ -1,5 +1,7 @@
+use lotus_proc_macros::Reflect;
+
 /// Enumerator that represent the orientation of the geometric form.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Reflect)]
 pub enum Orientation {
     Horizontal,
     Vertical
@@ -1,8 +1,8 @@
-use lotus_proc_macros::Component;
+use lotus_proc_macros::{Component, Reflect};
 use super::{super::color::color::Color, geometry_type::GeometryType, orientation::Orientation};
 
 /// Struct that represents every solid geometric form on the engine.
-#[derive(Clone, Debug, Component)]
+#[derive(Clone, Debug, Component, Reflect)]
 pub struct Shape {
     pub orientation: Orientation,
     pub geometry_type: GeometryType,
@@ -36,7 +36,7 @@ impl Shape {
 }
 
 /// Struct to represent the specific characteristics of a circle.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Reflect)]
 pub struct Circle {
     pub number_of_segments: u16,
     pub radius: f32
@@ -1,4 +1,6 @@
+use std::any::Any;
 use super::super::{
+    ecs::reflect::{Reflect, ReflectValue, ReflectError, FieldInfo},
     super::utils::constants::font::{
         UNDERDOG_REGULAR_PATH,
         CODYSTAR_LIGHT_PATH,
@@ -12,25 +14,110 @@ use super::super::{
 /// Struct to represent a font.
 #[derive(Clone)]
 pub struct Font {
+    pub path: String,
     pub bytes: Vec<u8>,
     pub size: f32
 }
 
 impl Font {
     /// Create a new font struct.
+    ///
+    /// Panics if the font can't be loaded, use Font::try_new to handle it.
     pub fn new(path: String, size: f32) -> Self {
+        return Self::try_new(path, size).unwrap();
+    }
+
+    /// Create a new font struct, returning an error if the font can't be loaded.
+    pub fn try_new(path: String, size: f32) -> std::io::Result<Self> {
         let bytes: Vec<u8> = if path.contains("../../") {
-            let font: Fonts = Fonts::from_path(path).unwrap();
+            let font: Fonts = Fonts::from_path(path.clone())
+                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, format!("'{}' is not an engine font", path)))?;
             font.get_bytes()
         } else {
-            AssetLoader::load_bytes(&path).ok().unwrap()
+            AssetLoader::load_bytes(&path)?
         };
 
-        return Self {
+        return Ok(Self {
+            path,
             bytes,
             size
+        });
+    }
+
+    fn load_reflected(path: String, size: f32) -> Result<Self, ReflectError> {
+        return Self::try_new(path.clone(), size).map_err(|_| ReflectError::AssetNotLoaded(path));
+    }
+}
+
+/// Fonts are reflected by their path and size, as their bytes are loaded from the path.
+///
+/// A path that can't be loaded is returned as an error, keeping the current font.
+impl Reflect for Font {
+    fn static_type_name() -> &'static str {
+        return "Font";
+    }
+
+    fn type_name(&self) -> &'static str {
+        return "Font";
+    }
+
+    fn fields(&self) -> Vec<FieldInfo> {
+        return vec![FieldInfo::new("path", self.path.type_name()), FieldInfo::new("size", self.size.type_name())];
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        return match name {
+            "path" => Some(&self.path),
+            "size" => Some(&self.size),
+            _ => None
+        };
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        return match name {
+            "path" => Some(&mut self.path),
+            "size" => Some(&mut self.size),
+            _ => None
         };
     }
+
+    fn to_value(&self) -> Result<ReflectValue, ReflectError> {
+        return Ok(ReflectValue::Map(vec![("path".to_string(), self.path.to_value()?), ("size".to_string(), self.size.to_value()?)]));
+    }
+
+    fn set_value(&mut self, value: &ReflectValue) -> Result<(), ReflectError> {
+        let path: String = value.get("path").map(String::from_value).transpose()?.unwrap_or_else(|| self.path.clone());
+        let size: f32 = value.get("size").map(f32::from_value).transpose()?.unwrap_or(self.size);
+
+        if path != self.path {
+            *self = Self::load_reflected(path, size)?;
+        } else {
+            self.size = size;
+        }
+        return Ok(());
+    }
+
+    fn from_value(value: &ReflectValue) -> Result<Self, ReflectError> {
+        let path: &ReflectValue = value.get("path").ok_or_else(|| ReflectError::MissingField("path".to_string()))?;
+        let size: &ReflectValue = value.get("size").ok_or_else(|| ReflectError::MissingField("size".to_string()))?;
+        return Self::load_reflected(String::from_value(path)?, f32::from_value(size)?);
+    }
+
+    fn set_path(&mut self, path: &str, value: &ReflectValue) -> Result<(), ReflectError> {
+        return match path {
+            "" => self.set_value(value),
+            "path" | "size" => self.set_value(&ReflectValue::Map(vec![(path.to_string(), value.clone())])),
+            _ => Err(ReflectError::FieldNotFound(path.to_string()))
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
 }
 
 /// Enumerator that represents the available default fonts on the engine.
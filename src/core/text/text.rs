@@ -1,7 +1,6 @@
-use std::collections::HashMap;
+use std::{any::Any, collections::HashMap};
 use cgmath::Vector2;
 use lotus_proc_macros::{Component, Resource};
-use uuid::Uuid;
 use wgpu::Queue;
 use wgpu_text::{glyph_brush::ab_glyph::FontArc, BrushBuilder, TextBrush};
 use winit::dpi::PhysicalSize;
@@ -9,27 +8,85 @@ use super::{
     font::Font,
     super::{
         event::dispatcher::{EventDispatcher, Event, EventType, SubEventType},
-        ecs::{entity::Entity, world::World, resource::ResourceRefMut},
+        ecs::{entity::Entity, world::World, resource::ResourceRefMut, reflect::{Reflect, ReflectValue, ReflectError, FieldInfo}},
         physics::transform::{Position, Strategy},
         super::Color,
         managers::render::manager::RenderState
     }
 };
 
+// Struct to represent a font shared by the text renderers that use it.
+pub(crate) struct SharedFont {
+    pub(crate) font: FontArc,
+    pub(crate) users: usize
+}
+
 // Struct to represent the resource that holds the text rendering context.
+//
+// The fonts are reference counted by the text renderers, and freed when no text uses them anymore.
 #[derive(Resource)]
 pub(crate) struct TextHolder {
-    pub(crate) text_renderers: HashMap<Uuid, TextRenderer>
+    pub(crate) text_renderers: HashMap<Entity, TextRenderer>,
+    pub(crate) fonts: HashMap<String, SharedFont>
 }
 
 impl Default for TextHolder {
     fn default() -> Self {
         return Self {
-            text_renderers: HashMap::new()
+            text_renderers: HashMap::new(),
+            fonts: HashMap::new()
         };
     }
 }
 
+impl TextHolder {
+    /// Create the text renderer of an entity, replacing its current one.
+    pub(crate) fn insert(&mut self, render_state: &RenderState, entity: Entity, text: &Text) {
+        self.remove(&entity);
+
+        let font: FontArc = self.acquire_font(&text.font);
+        self.text_renderers.insert(entity, TextRenderer::new(render_state, text, font));
+    }
+
+    /// Remove the text renderer of an entity, releasing its text brush and its font if no other text uses it.
+    pub(crate) fn remove(&mut self, entity: &Entity) {
+        if let Some(text_renderer) = self.text_renderers.remove(entity) {
+            self.release_font(&text_renderer.text.font.path);
+        }
+    }
+
+    /// Recreate the text renderer of an entity with a new font.
+    pub(crate) fn update_font(&mut self, render_state: &RenderState, entity: Entity, font: Font) {
+        if let Some(text_renderer) = self.text_renderers.get(&entity) {
+            let mut text: Text = text_renderer.text.clone();
+            text.font = font;
+            self.insert(render_state, entity, &text);
+        }
+    }
+
+    /// Returns the shared font of a path, loading it if no text uses it yet.
+    fn acquire_font(&mut self, font: &Font) -> FontArc {
+        let shared_font: &mut SharedFont = self.fonts.entry(font.path.clone()).or_insert_with(|| SharedFont {
+            font: FontArc::try_from_vec(font.bytes.clone()).expect("Failed to load font."),
+            users: 0
+        });
+
+        shared_font.users += 1;
+        return shared_font.font.clone();
+    }
+
+    /// Release a shared font, freeing it when no text uses it anymore.
+    fn release_font(&mut self, path: &str) {
+        if let Some(shared_font) = self.fonts.get_mut(path) {
+            shared_font.users -= 1;
+
+            if shared_font.users == 0 {
+                self.fonts.remove(path);
+            }
+        }
+    }
+}
+
 /// Struct to represent a text to be rendered.
 #[derive(Clone, Component)]
 pub struct Text {
@@ -40,6 +97,91 @@ pub struct Text {
     pub(crate) original_resolution: Vector2<f32>
 }
 
+/// Texts are reflected without their original resolution.
+///
+/// Texts created by reflection take the resolution of the window when spawned as their original one.
+impl Reflect for Text {
+    fn static_type_name() -> &'static str {
+        return "Text";
+    }
+
+    fn type_name(&self) -> &'static str {
+        return "Text";
+    }
+
+    fn fields(&self) -> Vec<FieldInfo> {
+        return vec![
+            FieldInfo::new("font", self.font.type_name()),
+            FieldInfo::new("position", self.position.type_name()),
+            FieldInfo::new("color", self.color.type_name()),
+            FieldInfo::new("content", self.content.type_name())
+        ];
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        return match name {
+            "font" => Some(&self.font),
+            "position" => Some(&self.position),
+            "color" => Some(&self.color),
+            "content" => Some(&self.content),
+            _ => None
+        };
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        return match name {
+            "font" => Some(&mut self.font),
+            "position" => Some(&mut self.position),
+            "color" => Some(&mut self.color),
+            "content" => Some(&mut self.content),
+            _ => None
+        };
+    }
+
+    fn to_value(&self) -> Result<ReflectValue, ReflectError> {
+        return Ok(ReflectValue::Map(vec![
+            ("font".to_string(), self.font.to_value()?),
+            ("position".to_string(), self.position.to_value()?),
+            ("color".to_string(), self.color.to_value()?),
+            ("content".to_string(), self.content.to_value()?)
+        ]));
+    }
+
+    fn set_value(&mut self, value: &ReflectValue) -> Result<(), ReflectError> {
+        let ReflectValue::Map(entries) = value else {
+            return Err(ReflectError::TypeMismatch { expected: "map", found: value.kind() });
+        };
+
+        for (key, entry) in entries {
+            match self.field_mut(key) {
+                Some(field) => field.set_value(entry)?,
+                None => return Err(ReflectError::FieldNotFound(key.clone()))
+            }
+        }
+        return Ok(());
+    }
+
+    fn from_value(value: &ReflectValue) -> Result<Self, ReflectError> {
+        let field = |name: &str| value.get(name).ok_or_else(|| ReflectError::MissingField(name.to_string()));
+
+        return Ok(Self {
+            font: Font::from_value(field("font")?)?,
+            position: Position::from_value(field("position")?)?,
+            color: Color::from_value(field("color")?)?,
+            content: String::from_value(field("content")?)?,
+            original_resolution: Vector2::new(0.0, 0.0)
+        });
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
+}
+
 impl Text {
     /// Create a new text struct.
     pub fn new(render_state: &mut RenderState, font: Font, position: Position, color: Color, content: String) -> Self {
@@ -55,6 +197,17 @@ impl Text {
         };
     }
 
+    /// Set the original resolution as the current one, if the text doesn't have any.
+    ///
+    /// Texts created by reflection don't know the resolution in which they were positioned.
+    pub(crate) fn resolve_original_resolution(&mut self, render_state: &RenderState) {
+        if self.original_resolution.x == 0.0 || self.original_resolution.y == 0.0 {
+            if let Some(physical_size) = render_state.physical_size.as_ref() {
+                self.original_resolution = Vector2::new(physical_size.width as f32, physical_size.height as f32);
+            }
+        }
+    }
+
     /// Returns the text position by its positioning strategy.
     ///
     /// We need to send the data as pixelated values to our rasterizer (glyph_brush).
@@ -108,8 +261,7 @@ pub(crate) struct TextRenderer {
 
 impl TextRenderer {
     /// Create a new text renderer struct.
-    pub(crate) fn new(render_state: &RenderState, text: &Text) -> Self {
-        let font: FontArc = FontArc::try_from_vec(text.font.bytes.clone()).expect("Failed to load font.");
+    pub(crate) fn new(render_state: &RenderState, text: &Text, font: FontArc) -> Self {
         let text_brush: TextBrush<FontArc> = BrushBuilder::using_font(font).build(
             &render_state.device.as_ref().unwrap(),
             render_state.physical_size.unwrap().width,
@@ -123,16 +275,6 @@ impl TextRenderer {
         };
     }
 
-    /// Updates the text rendering context with the new font.
-    pub(crate) fn font(&mut self, font: Font, queue: Option<Queue>, physical_size: Option<PhysicalSize<u32>>) {
-        self.text.font = font;
-
-        self.text_brush.update_matrix(
-            wgpu_text::ortho(physical_size.as_ref().unwrap().width as f32, physical_size.as_ref().unwrap().height as f32),
-            queue.as_ref().unwrap()
-        );
-    }
-
     /// Updates the text rendering context with the new position.
     pub(crate) fn position(&mut self, position: Position, queue: Option<Queue>, physical_size: Option<PhysicalSize<u32>>) {
         self.text.position = position;
@@ -1,20 +1,24 @@
-
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::{HashMap, HashSet}, sync::Arc};
 use image::DynamicImage;
 use wgpu::{Device, Queue};
-use super::{texture::Texture, super::asset_loader::AssetLoader};
-use crate::utils::constants::cache::DUMMY_TEXTURE;
+use super::{texture::Texture, super::{asset_loader::AssetLoader, ecs::entity::Entity}};
+use crate::utils::constants::cache::{DUMMY_TEXTURE, FIXED_ID};
 
 /// Struct to represent the textures current on the application cache.
+///
+/// The textures are reference counted by the entities that render them, and evicted when no entity uses them anymore.
+/// Textures used without an entity, like the background, are kept until the cache is dropped.
 pub struct TextureCache {
-    textures: HashMap<String, Arc<Texture>>
+    textures: HashMap<String, Arc<Texture>>,
+    owners: HashMap<String, HashSet<u64>>
 }
 
 impl TextureCache {
     /// Create a new texture cache cleaned.
     pub fn new() -> Self {
         return Self {
-            textures: HashMap::new()
+            textures: HashMap::new(),
+            owners: HashMap::new()
         };
     }
 
@@ -40,4 +44,63 @@ impl TextureCache {
         }
         return Some(self.get_texture(key).expect("Texture should be on cache."));
     }
+
+    /// Add a texture created by the engine to the cache, replacing the previous one with the same key.
+    ///
+    /// It is kept until the cache is dropped, like the textures used without an entity.
+    pub fn insert_texture(&mut self, key: String, texture: Arc<Texture>) {
+        self.acquire(&key, None);
+        self.textures.insert(key, texture);
+    }
+
+    /// Register an entity as an owner of a texture, so it is kept while the entity uses it.
+    pub fn acquire(&mut self, key: &str, entity: Option<&Entity>) {
+        let owner: u64 = entity.map(|entity| entity.to_bits()).unwrap_or(FIXED_ID);
+        self.owners.entry(key.to_string()).or_default().insert(owner);
+    }
+
+    /// Release the textures owned by an entity, evicting the ones that no other entity uses.
+    ///
+    /// Returns the keys of the evicted textures.
+    pub fn release(&mut self, entity: &Entity) -> Vec<String> {
+        let owner: u64 = entity.to_bits();
+        let mut evicted: Vec<String> = Vec::new();
+
+        self.owners.retain(|key, owners| {
+            if owners.remove(&owner) && owners.is_empty() {
+                evicted.push(key.clone());
+                return false;
+            }
+            return true;
+        });
+
+        for key in &evicted {
+            self.textures.remove(key);
+        }
+        return evicted;
+    }
+
+    /// Returns the number of owners of a texture.
+    pub fn owners_count(&self, key: &str) -> usize {
+        return self.owners.get(key).map(|owners| owners.len()).unwrap_or(0);
+    }
+
+    /// Returns the number of textures on the cache.
+    pub fn len(&self) -> usize {
+        return self.textures.len();
+    }
+
+    /// Returns if the cache has no textures.
+    pub fn is_empty(&self) -> bool {
+        return self.textures.is_empty();
+    }
+
+    /// Returns the estimated size in bytes of the textures on the cache.
+    pub fn size_in_bytes(&self) -> u64 {
+        return self.textures.values().map(|texture| {
+            let size: wgpu::Extent3d = texture.wgpu_texture.size();
+            let block_size: u64 = texture.wgpu_texture.format().block_copy_size(None).unwrap_or(4) as u64;
+            return size.width as u64 * size.height as u64 * size.depth_or_array_layers as u64 * block_size;
+        }).sum();
+    }
 }
@@ -1,5 +1,7 @@
+use std::any::Any;
 use lotus_proc_macros::Component;
 use super::super::{
+    ecs::reflect::{Reflect, ReflectValue, ReflectError, FieldInfo},
     managers::render::manager::Vertex,
     super::ColorOption,
     shape::{geometry_type::GeometryType, orientation::Orientation}
@@ -8,9 +10,12 @@ use super::super::{
 /// Struct to represent a sprite with its data.
 ///
 /// A sprite is represented as two triangles, or a square.
+///
+/// Its optional normal map is used by the lighting, and is shared by every sprite with the same texture.
 #[derive(Clone, Debug, Component)]
 pub struct Sprite {
     pub path: String,
+    pub normal_map: Option<String>,
     pub vertices: Vec<Vertex>,
     pub indices: Vec<u16>
 }
@@ -22,9 +27,93 @@ impl Sprite {
         let indices: Vec<u16> = GeometryType::Square.to_index_array();
         let sprite: Sprite = Self {
             path,
+            normal_map: None,
             vertices,
             indices
         };
         return sprite;
     }
+
+    /// Set the file path of the normal map on initialization.
+    pub fn normal_map(self, normal_map: String) -> Self {
+        return Self {
+            normal_map: Some(normal_map),
+            ..self
+        };
+    }
+}
+
+/// Sprites are reflected by their path and normal map, as their vertices and indices are created from the path.
+impl Reflect for Sprite {
+    fn static_type_name() -> &'static str {
+        return "Sprite";
+    }
+
+    fn type_name(&self) -> &'static str {
+        return "Sprite";
+    }
+
+    fn fields(&self) -> Vec<FieldInfo> {
+        return vec![
+            FieldInfo::new("path", self.path.type_name()),
+            FieldInfo::new("normal_map", self.normal_map.type_name())
+        ];
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        return match name {
+            "path" => Some(&self.path as &dyn Reflect),
+            "normal_map" => Some(&self.normal_map as &dyn Reflect),
+            _ => None
+        };
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        return match name {
+            "path" => Some(&mut self.path as &mut dyn Reflect),
+            "normal_map" => Some(&mut self.normal_map as &mut dyn Reflect),
+            _ => None
+        };
+    }
+
+    fn to_value(&self) -> Result<ReflectValue, ReflectError> {
+        return Ok(ReflectValue::Map(vec![
+            ("path".to_string(), self.path.to_value()?),
+            ("normal_map".to_string(), self.normal_map.to_value()?)
+        ]));
+    }
+
+    fn set_value(&mut self, value: &ReflectValue) -> Result<(), ReflectError> {
+        let ReflectValue::Map(entries) = value else {
+            return Err(ReflectError::TypeMismatch { expected: "map", found: value.kind() });
+        };
+
+        for (key, entry) in entries {
+            match self.field_mut(key) {
+                Some(field) => field.set_value(entry)?,
+                None => return Err(ReflectError::FieldNotFound(key.clone()))
+            }
+        }
+        return Ok(());
+    }
+
+    fn from_value(value: &ReflectValue) -> Result<Self, ReflectError> {
+        let path: &ReflectValue = value.get("path").ok_or_else(|| ReflectError::MissingField("path".to_string()))?;
+        let normal_map: Option<String> = match value.get("normal_map") {
+            Some(normal_map) => Option::<String>::from_value(normal_map)?,
+            None => None
+        };
+        return Ok(Self {
+            normal_map,
+            ..Self::new(String::from_value(path)?)
+        });
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
 }
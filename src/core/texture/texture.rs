@@ -158,4 +158,45 @@ impl Texture {
             sampler
         });
     }
+
+    /// Returns a texture that can be rendered into and sampled afterwards, like an offscreen frame.
+    pub fn render_target(
+        device: &Device,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+        label: Option<&str>
+    ) -> Self {
+        let texture: wgpu::Texture = device.create_texture(&TextureDescriptor {
+            label,
+            size: Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_SRC,
+            view_formats: &[]
+        });
+
+        let texture_view: TextureView = texture.create_view(&TextureViewDescriptor::default());
+        let sampler: Sampler = device.create_sampler(&SamplerDescriptor {
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: MipmapFilterMode::Nearest,
+            ..Default::default()
+        });
+
+        return Self {
+            wgpu_texture: texture,
+            texture_view,
+            sampler
+        };
+    }
 }
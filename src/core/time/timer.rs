@@ -1,8 +1,8 @@
 use std::time::Duration;
-use lotus_proc_macros::Resource;
+use lotus_proc_macros::{Resource, Reflect};
 
 /// Enumerator to define the type of the timer.
-#[derive(Clone, Default, Debug, PartialEq)]
+#[derive(Clone, Default, Debug, PartialEq, Reflect)]
 pub enum TimerType {
     #[default]
     Once,
@@ -10,7 +10,7 @@ pub enum TimerType {
 }
 
 /// Struct to represent a timer that can be used at easy by the end-user.
-#[derive(Clone, Debug, Resource)]
+#[derive(Clone, Debug, Resource, Reflect)]
 pub struct Timer {
     pub timer_type: TimerType,
     pub duration: Duration,
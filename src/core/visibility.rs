@@ -1,7 +1,7 @@
-use lotus_proc_macros::Component;
+use lotus_proc_macros::{Component, Reflect};
 
 /// Struct to represent an entity visibility in the world.
-#[derive(Clone, Component)]
+#[derive(Clone, Component, Reflect)]
 pub struct Visibility(pub bool);
 
 impl Visibility {
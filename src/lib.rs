@@ -78,6 +78,10 @@ As a brief overview:
 
 - Structs defined with the derive macro *Component* are Components that can be spawned in our World within an Entity.
 - Structs defined with the derive macro *Resource* are Resources that can be added to in our World.
+- Structs defined with the derive macro *Bundle* group Components, or other Bundles, that are spawned together, like a tuple of Components.
+- Structs and enums defined with the derive macro *Reflect* expose their fields by name, so Components and Resources registered on the World can be inspected and edited by their type names.
+- The registered Components of a World can be saved as a JSON scene file with *World::save_scene* and spawned back with *Commands::spawn_scene*, so levels can be edited without recompiling.
+- The Resources selected on a *SaveGame* and the Entities tagged as *Persistent* can be saved as versioned snapshots and restored with *Commands::load_game*, migrating older save files.
 - *Entities* are defined by it's components and every entity has a unique ID.
 - Entities are stored in what is called as *Archetypes* in our World.
 - Archetypes are defined by the Components that our Entities have, so a Archetype will only have Entities with the same Components.
@@ -88,6 +92,48 @@ As a brief overview:
 
 ----------------
 
+## Systems and stages
+
+The update function is just one of the *Systems* stored on the *Schedule* of the context.<br>
+Each frame runs the stages PreUpdate, Update, PostUpdate and Render, in this order.<br>
+The engine synchronizers (events, camera, animations, collisions, gravity and gamepads) are built-in systems of the PreUpdate stage, so they can be reordered or disabled too.<br>
+The transforms of entities spawned with *Commands::spawn_child* are propagated from their parents by the built-in *transforms* system of the PostUpdate stage.<br>
+The commands buffered by the systems are applied at the end of each stage, and *Commands::spawn* returns the entity reserved for the spawned one.<br>
+User-defined events are sent and read between systems through the *Events* channels registered with *Context::add_event*.<br>
+Systems created with *System::parallel* declare the components and resources they read or write, and the ones without conflicts run together on a thread pool.
+
+```rust,no_run
+use lotus_engine::*;
+
+your_game!(
+    WindowConfiguration::default(),
+    setup,
+    update
+);
+
+fn setup(context: &mut Context) {
+    context.schedule
+        .add_system(Stage::Update, System::new("movement", movement).before("update"))
+        .add_system(Stage::PostUpdate, System::new("scoring", scoring))
+        .add_system(Stage::Update, System::parallel("friction", friction).writes::<Velocity>());
+    context.schedule.disable_system("gravity");
+}
+
+fn update(_context: &mut Context) {}
+
+fn movement(_context: &mut Context) {}
+
+fn scoring(_context: &mut Context) {}
+
+fn friction(parallel_context: &mut ParallelContext) {
+    for mut velocity in parallel_context.world.query::<&mut Velocity>() {
+        velocity.x *= 0.9;
+    }
+}
+```
+
+----------------
+
 ## Examples
 
 The classic hello world:
@@ -115,8 +161,15 @@ And here are some more complex initial examples to demonstrate the engine's pote
 - **Breakout:** [`examples/breakout.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/breakout.rs)
 - **Rendering geometric forms:** [`examples/simple_shapes.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/simple_shapes.rs)
 - **Rendering sprites:** [`examples/simple_sprite.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/simple_sprite.rs)
+- **Custom shaders and materials:** [`examples/materials.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/materials.rs)
+- **Post-processing effects:** [`examples/post_processing.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/post_processing.rs)
+- **Split-screen cameras and minimap:** [`examples/split_screen.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/split_screen.rs)
+- **Screenshots and frame recording:** [`examples/screenshot.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/screenshot.rs)
+- **2D lighting with normal maps and shadows:** [`examples/lighting.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/lighting.rs)
+- **Particle emitters:** [`examples/particles.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/particles.rs)
 - **Physics simulation:** [`examples/physics_simulation.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/physics_simulation.rs)
 - **Gravity simulation:** [`examples/gravity_simulation.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/gravity_simulation.rs)
+- **Rendering thousands of entities:** [`examples/performance.rs`](https://github.com/zenialexandre/lotus/blob/main/examples/performance.rs), as sprites sharing a texture and shapes sharing a geometry are drawn in batches.
 
 ----------------
 
@@ -131,6 +184,11 @@ pub mod core;
 pub mod utils;
 
 pub use core::managers::render::manager::*;
+pub use core::managers::render::memory::*;
+pub use core::managers::render::batch::*;
+pub use core::managers::render::target::*;
+pub use core::managers::render::headless::*;
+pub use core::managers::render::capture::*;
 pub use core::managers::window::manager::*;
 pub use core::managers::window::present_mode::*;
 pub use core::game_loop::*;
@@ -139,6 +197,10 @@ pub use core::context::*;
 pub use core::color::color::*;
 pub use core::color::option::*;
 pub use core::visibility::*;
+pub use core::hierarchy::*;
+pub use core::bundles::*;
+pub use core::scene::*;
+pub use core::save::*;
 pub use core::shape::shape::*;
 pub use core::shape::orientation::*;
 pub use core::shape::geometry_type::*;
@@ -160,7 +222,13 @@ pub use core::text::font::*;
 pub use core::animation::animation::*;
 pub use core::animation::animation_state::*;
 pub use core::animation::looping_state::*;
+pub use core::material::material::*;
+pub use core::post_process::post_process::*;
+pub use core::lighting::light::*;
+pub use core::particle::curve::*;
+pub use core::particle::particle_emitter::*;
 pub use core::camera::camera2d::*;
+pub use core::camera::camera::*;
 pub use core::physics::transform::Transform;
 pub use core::physics::transform::*;
 pub use core::physics::acceleration::*;
@@ -172,15 +240,28 @@ pub use core::time::timer::*;
 pub use core::draw_order::*;
 pub use core::audio::audio_source::*;
 pub use core::audio::audio_error::*;
+pub use core::event::channel::*;
 pub use core::ecs::world::*;
 pub use core::ecs::archetype::*;
+pub use core::ecs::column::*;
 pub use core::ecs::command::*;
 pub use core::ecs::entity::*;
 pub use core::ecs::component::*;
+pub use core::ecs::hook::*;
+pub use core::ecs::bundle::*;
 pub use core::ecs::resource::*;
+pub use core::ecs::reflect::*;
+pub use core::ecs::registry::*;
 pub use core::ecs::query::*;
+pub use core::ecs::filter::*;
+pub use core::schedule::stage::*;
+pub use core::schedule::system::*;
+pub use core::schedule::schedule::*;
+pub use core::schedule::state::*;
 pub use lotus_proc_macros::Component;
 pub use lotus_proc_macros::Resource;
+pub use lotus_proc_macros::Bundle;
+pub use lotus_proc_macros::Reflect;
 pub use cgmath::*;
 pub use kira::*;
 pub use pollster::block_on;
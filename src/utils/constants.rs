@@ -1,11 +1,31 @@
 /// Constants related to adding the WGSL source code on the WGPU process.
 pub mod shader {
     pub const SHADER_2D: &str = include_str!("../../assets/shaders/shader_2d.wgsl");
+    pub const SHADER_2D_INSTANCED: &str = include_str!("../../assets/shaders/shader_2d_instanced.wgsl");
+    pub const MATERIAL_PRELUDE: &str = include_str!("../../assets/shaders/material_prelude.wgsl");
+    pub const DISSOLVE_MATERIAL: &str = include_str!("../../assets/shaders/materials/dissolve.wgsl");
+    pub const FLASH_MATERIAL: &str = include_str!("../../assets/shaders/materials/flash.wgsl");
+    pub const OUTLINE_MATERIAL: &str = include_str!("../../assets/shaders/materials/outline.wgsl");
+    pub const DISSOLVE_MATERIAL_PATH: &str = "../../assets/shaders/materials/dissolve.wgsl";
+    pub const FLASH_MATERIAL_PATH: &str = "../../assets/shaders/materials/flash.wgsl";
+    pub const OUTLINE_MATERIAL_PATH: &str = "../../assets/shaders/materials/outline.wgsl";
+    pub const MAX_MATERIAL_TEXTURES: usize = 4;
+    pub const POST_PROCESS_PRELUDE: &str = include_str!("../../assets/shaders/post_process_prelude.wgsl");
+    pub const BLOOM_POST_PROCESS: &str = include_str!("../../assets/shaders/post_process/bloom.wgsl");
+    pub const VIGNETTE_POST_PROCESS: &str = include_str!("../../assets/shaders/post_process/vignette.wgsl");
+    pub const CRT_POST_PROCESS: &str = include_str!("../../assets/shaders/post_process/crt.wgsl");
+    pub const COLOR_GRADING_POST_PROCESS: &str = include_str!("../../assets/shaders/post_process/color_grading.wgsl");
+    pub const BLOOM_POST_PROCESS_PATH: &str = "../../assets/shaders/post_process/bloom.wgsl";
+    pub const VIGNETTE_POST_PROCESS_PATH: &str = "../../assets/shaders/post_process/vignette.wgsl";
+    pub const CRT_POST_PROCESS_PATH: &str = "../../assets/shaders/post_process/crt.wgsl";
+    pub const COLOR_GRADING_POST_PROCESS_PATH: &str = "../../assets/shaders/post_process/color_grading.wgsl";
+    pub const LIGHTING_SHADER: &str = include_str!("../../assets/shaders/lighting.wgsl");
+    pub const NORMAL_MAP_SHADER: &str = include_str!("../../assets/shaders/normal_map.wgsl");
 }
 
 /// Constants related to the cache rendering process.
 pub mod cache {
-    pub const FIXED_UUID: &str = "fixed_uuid";
+    pub const FIXED_ID: u64 = u64::MAX;
     pub const VERTEX: &str = "vertex";
     pub const INDEX: &str = "index";
     pub const PROJECTION: &str = "projection";
@@ -16,6 +36,31 @@ pub mod cache {
     pub const TRANSFORM_BIND_GROUP: &str = "transform_bind_group";
     pub const RENDERING_TYPE_BIND_GROUP: &str = "rendering_type_bind_group";
     pub const DUMMY_TEXTURE: &str = "dummy_texture";
+    pub const BATCH_VERTEX: &str = "batch_vertex";
+    pub const BATCH_INDEX: &str = "batch_index";
+    pub const BATCH_TEXTURE_BIND_GROUP: &str = "batch_texture_bind_group";
+    pub const CAMERA_BIND_GROUP: &str = "camera_bind_group";
+    pub const MATERIAL_BUFFER: &str = "material_buffer";
+    pub const MATERIAL_BIND_GROUP: &str = "material_bind_group";
+    pub const POST_PROCESS_BUFFER: &str = "post_process_buffer";
+    pub const POST_PROCESS_BIND_GROUP: &str = "post_process_bind_group";
+    pub const LIGHTING_BUFFER: &str = "lighting_buffer";
+    pub const LIGHTING_BIND_GROUP: &str = "lighting_bind_group";
+    pub const LIGHTING_PIPELINE: &str = "lighting_pipeline";
+    pub const NORMAL_MAP_PIPELINE: &str = "normal_map_pipeline";
+}
+
+/// Constants related to the names of the engine built-in systems.
+pub mod system {
+    pub const EVENTS: &str = "events";
+    pub const CAMERA: &str = "camera";
+    pub const ANIMATIONS: &str = "animations";
+    pub const COLLISIONS: &str = "collisions";
+    pub const GRAVITY: &str = "gravity";
+    pub const PARTICLES: &str = "particles";
+    pub const GAMEPAD: &str = "gamepad";
+    pub const UPDATE: &str = "update";
+    pub const TRANSFORMS: &str = "transforms";
 }
 
 /// Constants related to native engine fonts.
@@ -26,3 +71,21 @@ pub mod font {
     pub const ROBOTO_MONO_PATH: &str = "../../assets/fonts/RobotoMono-VariableFont_wght.ttf";
     pub const ROBOTO_MONO_ITALIC_PATH: &str = "../../assets/fonts/RobotoMono-Italic-VariableFont_wght.ttf";
 }
+
+/// Constants related to the headless rendering process.
+pub mod headless {
+    pub const UPDATE_GOLDEN_VARIABLE: &str = "LOTUS_UPDATE_GOLDEN";
+    pub const ACTUAL_IMAGE_SUFFIX: &str = ".actual.png";
+}
+
+/// Constants related to the 2D lighting process, matching the limits of the lighting shader.
+pub mod lighting {
+    pub const MAX_POINT_LIGHTS: usize = 16;
+    pub const MAX_OCCLUDER_EDGES: usize = 128;
+    pub const OCCLUDER_CIRCLE_SEGMENTS: u32 = 12;
+}
+
+/// Constants related to the frame capturing process.
+pub mod capture {
+    pub const MAX_QUEUED_CAPTURES: usize = 8;
+}
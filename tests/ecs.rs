@@ -1,5 +1,6 @@
 #[cfg(test)]
 pub mod tests {
+    use std::sync::{Arc, Mutex};
     use lotus_engine::*;
 
     #[test]
@@ -41,6 +42,59 @@ pub mod tests {
         assert!(!world.is_entity_alive(entity));
     }
 
+    #[test]
+    fn despawning_keeps_entity_locations_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        commands.spawn(Velocity::new(Vector2::new(1.0, 0.0)));
+        commands.spawn(Velocity::new(Vector2::new(2.0, 0.0)));
+        commands.spawn(Velocity::new(Vector2::new(3.0, 0.0)));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let entities: Vec<Entity> = world.query::<(Entity, &Velocity)>().map(|(entity, _)| entity).collect();
+        let first: Entity = entities[0];
+
+        commands.despawn(first);
+        commands.flush_commands(&mut world, &mut render_state);
+
+        assert!(!world.is_entity_alive(first));
+        assert!(world.get_entity_location(&first).is_none());
+
+        for entity in &entities[1..] {
+            let location: EntityLocation = world.get_entity_location(entity).unwrap();
+            assert!(world.archetypes[&location.archetype_id].entities[location.row] == *entity);
+            assert!(world.get_entity_component::<Velocity>(entity).is_some());
+        }
+        assert!(world.query::<&Velocity>().count() == 2);
+    }
+
+    #[test]
+    fn recycled_entities_are_generational_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        commands.spawn(Velocity::new(Vector2::new(0.0, 0.0)));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let stale: Entity = world.query::<(Entity, &Velocity)>().map(|(entity, _)| entity).next().unwrap();
+        commands.despawn(stale);
+        commands.flush_commands(&mut world, &mut render_state);
+
+        commands.spawn(Velocity::new(Vector2::new(1.0, 0.0)));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let recycled: Entity = world.query::<(Entity, &Velocity)>().map(|(entity, _)| entity).next().unwrap();
+        assert!(recycled.index == stale.index);
+        assert!(recycled.generation != stale.generation);
+        assert!(world.is_entity_alive(recycled));
+        assert!(!world.is_entity_alive(stale));
+        assert!(world.get_entity_component::<Velocity>(&stale).is_none());
+        assert!(!world.is_entity_alive(Entity::PLACEHOLDER));
+    }
+
     #[test]
     fn get_component_from_entity_as_immutable_test() {
         let mut commands: Commands = Commands::new();
@@ -111,6 +165,274 @@ pub mod tests {
         assert!(!world.get_entity_components_mut(&entity).unwrap().is_empty());
     }
 
+    #[test]
+    fn commands_inserting_and_removing_components_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        commands.spawn(Velocity::new(Vector2::new(1.0, 0.0)));
+        commands.spawn(Velocity::new(Vector2::new(2.0, 0.0)));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let entities: Vec<Entity> = world.query::<(Entity, &Velocity)>().map(|(entity, _)| entity).collect();
+        let (entity, other): (Entity, Entity) = (entities[0], entities[1]);
+        let velocity_before: f32 = world.get_entity_component::<Velocity>(&entity).unwrap().x;
+
+        commands.insert(entity, Box::new(Gravity::new(9.8)));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        assert!(world.get_entity_component::<Gravity>(&entity).is_some());
+        assert!(world.get_entity_component::<Velocity>(&entity).unwrap().x == velocity_before);
+        assert!(world.get_entity_component::<Gravity>(&other).is_none());
+        assert!(world.get_entity_component::<Velocity>(&other).is_some());
+        assert!(world.get_entity_location(&entity).unwrap().archetype_id != world.get_entity_location(&other).unwrap().archetype_id);
+        assert!(render_state.entities_to_render.len() == 2);
+
+        commands.insert(entity, Box::new(Velocity::new(Vector2::new(5.0, 5.0))));
+        commands.flush_commands(&mut world, &mut render_state);
+        assert!(world.get_entity_component::<Velocity>(&entity).unwrap().x == 5.0);
+
+        commands.remove::<Gravity>(entity);
+        commands.flush_commands(&mut world, &mut render_state);
+
+        assert!(world.get_entity_component::<Gravity>(&entity).is_none());
+        assert!(world.get_entity_component::<Velocity>(&entity).is_some());
+        assert!(world.get_entity_location(&entity).unwrap().archetype_id == world.get_entity_location(&other).unwrap().archetype_id);
+        assert!(world.query::<&Velocity>().count() == 2);
+    }
+
+    #[test]
+    fn despawning_releases_rendering_resources_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        let player: Entity = commands.spawn(Sprite::new("textures/lotus_pink_256x256.png".to_string()));
+        let enemy: Entity = commands.spawn(Sprite::new("textures/lotus_pink_256x256.png".to_string()));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        render_state.texture_cache.acquire("textures/lotus_pink_256x256.png", Some(&player));
+        render_state.texture_cache.acquire("textures/lotus_pink_256x256.png", Some(&enemy));
+        render_state.texture_cache.acquire("textures/lotus_pink_128x128.png", Some(&enemy));
+        render_state.texture_cache.acquire("textures/lotus_pink_64x64.png", None);
+
+        commands.insert(enemy, Box::new(Velocity::new(Vector2::new(1.0, 0.0))));
+        commands.despawn(player);
+        commands.flush_commands(&mut world, &mut render_state);
+        assert!(render_state.texture_cache.owners_count("textures/lotus_pink_256x256.png") == 1);
+        assert!(render_state.texture_cache.owners_count("textures/lotus_pink_128x128.png") == 1);
+
+        commands.insert(enemy, Box::new(Sprite::new("textures/lotus_pink_32x32.png".to_string())));
+        commands.flush_commands(&mut world, &mut render_state);
+        assert!(render_state.texture_cache.owners_count("textures/lotus_pink_256x256.png") == 0);
+        assert!(render_state.texture_cache.owners_count("textures/lotus_pink_128x128.png") == 0);
+
+        render_state.texture_cache.acquire("textures/lotus_pink_32x32.png", Some(&enemy));
+        assert!(render_state.texture_cache.release(&enemy) == vec!["textures/lotus_pink_32x32.png".to_string()]);
+        assert!(render_state.texture_cache.owners_count("textures/lotus_pink_64x64.png") == 1);
+        assert!(render_state.memory_stats(&world) == MemoryStats::default());
+    }
+
+    #[test]
+    fn commands_spawning_children_and_despawning_recursively_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        commands.spawn(Velocity::new(Vector2::new(1.0, 0.0)));
+        commands.flush_commands(&mut world, &mut render_state);
+        let parent: Entity = world.query::<(Entity, &Velocity)>().next().unwrap().0;
+
+        commands.spawn_child(parent, Gravity::new(9.8));
+        commands.spawn_child(parent, Gravity::new(4.9));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let children: Vec<Entity> = world.get_entity_component::<Children>(&parent).unwrap().0.clone();
+        assert!(children.len() == 2);
+        assert!(children.iter().all(|child| world.get_entity_component::<Parent>(child).unwrap().get() == parent));
+        assert!(world.get_entity_component::<GlobalTransform>(&parent).is_some());
+        assert!(world.get_entity_component::<Velocity>(&parent).unwrap().x == 1.0);
+
+        commands.spawn_child(children[0], ());
+        commands.despawn(children[1]);
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let grandchild: Entity = world.get_entity_component::<Children>(&children[0]).unwrap().0[0];
+        assert!(world.get_entity_component::<Children>(&parent).unwrap().get() == [children[0]]);
+
+        commands.despawn(parent);
+        commands.flush_commands(&mut world, &mut render_state);
+
+        assert!(!world.is_entity_alive(parent));
+        assert!(!world.is_entity_alive(children[0]));
+        assert!(!world.is_entity_alive(grandchild));
+        assert!(world.entity_locations.is_empty());
+        assert!(render_state.entities_to_render.is_empty());
+    }
+
+    #[test]
+    fn despawning_cyclic_children_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        let parent: Entity = commands.spawn(Velocity::new(Vector2::new(1.0, 0.0)));
+        let child: Entity = commands.spawn_child(parent, Gravity::new(9.8));
+        commands.insert(child, Box::new(Children(vec![parent, child])));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        commands.despawn(parent);
+        commands.flush_commands(&mut world, &mut render_state);
+
+        assert!(!world.is_entity_alive(parent));
+        assert!(!world.is_entity_alive(child));
+        assert!(world.entity_locations.is_empty());
+    }
+
+    #[test]
+    fn component_hooks_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+        let log: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let (add_log, remove_log, despawn_log) = (log.clone(), log.clone(), log.clone());
+
+        world.component_hooks_mut::<Velocity>()
+            .on_add(move |world: &mut World, _: &mut Commands, entity: Entity| {
+                add_log.lock().unwrap().push(format!("add {}", world.get_entity_component::<Velocity>(&entity).unwrap().x));
+            })
+            .on_remove(move |world: &mut World, _: &mut Commands, entity: Entity| {
+                remove_log.lock().unwrap().push(format!("remove {}", world.get_entity_component::<Velocity>(&entity).unwrap().x));
+            })
+            .on_despawn(move |_: &mut World, commands: &mut Commands, _: Entity| {
+                despawn_log.lock().unwrap().push("despawn".to_string());
+                commands.spawn(Gravity::new(9.8));
+            });
+
+        commands.spawn(Velocity::new(Vector2::new(1.0, 0.0)));
+        commands.flush_commands(&mut world, &mut render_state);
+        let entity: Entity = world.query::<(Entity, &Velocity)>().next().unwrap().0;
+
+        commands.insert(entity, Box::new(Velocity::new(Vector2::new(2.0, 0.0))));
+        commands.insert(entity, Box::new(Gravity::new(1.0)));
+        commands.remove::<Velocity>(entity);
+        commands.insert(entity, Box::new(Velocity::new(Vector2::new(3.0, 0.0))));
+        commands.despawn(entity);
+        commands.flush_commands(&mut world, &mut render_state);
+
+        assert!(*log.lock().unwrap() == vec!["add 1", "remove 1", "add 2", "remove 2", "add 3", "despawn"]);
+        assert!(!world.is_entity_alive(entity));
+        assert!(world.query::<&Gravity>().count() == 1);
+    }
+
+    #[test]
+    fn commands_spawning_returns_reserved_entities_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        let entity: Entity = commands.spawn(Velocity::new(Vector2::new(1.0, 0.0)));
+        let child: Entity = commands.spawn_child(entity, Gravity::new(9.8));
+        commands.add(move |world: &mut World| {
+            world.get_entity_component_mut::<Velocity>(&entity).unwrap().y = 2.0;
+        });
+        assert!(!world.is_entity_alive(entity));
+
+        commands.flush_commands(&mut world, &mut render_state);
+        assert!(world.is_entity_alive(entity) && world.is_entity_alive(child));
+        assert!(world.get_entity_component::<Velocity>(&entity).unwrap().y == 2.0);
+        assert!(world.get_entity_component::<Parent>(&child).unwrap().get() == entity);
+
+        let mut detached_commands: Commands = Commands::new();
+        let detached: Entity = detached_commands.spawn(Gravity::new(1.0));
+        detached_commands.insert(detached, Box::new(Velocity::new(Vector2::new(4.0, 0.0))));
+        detached_commands.despawn(detached);
+        detached_commands.flush_commands(&mut world, &mut render_state);
+        assert!(detached == Entity::PLACEHOLDER);
+        assert!(world.query::<&Gravity>().count() == 2);
+        assert!(world.query::<&Velocity>().count() == 1);
+    }
+
+    #[test]
+    fn commands_spawning_child_of_dead_parent_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        let parent: Entity = commands.spawn(Gravity::new(9.8));
+        commands.despawn(parent);
+        let orphan: Entity = commands.spawn_child(parent, Gravity::new(4.9));
+        commands.flush_commands(&mut world, &mut render_state);
+        assert!(!world.is_entity_alive(orphan));
+
+        let recycled: Entity = commands.spawn(Gravity::new(1.0));
+        assert!(recycled.index == orphan.index && recycled.generation != orphan.generation);
+    }
+
+    #[test]
+    fn dropping_unflushed_commands_frees_reserved_entities_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+
+        let mut dropped_commands: Commands = world.commands();
+        let dropped: Entity = dropped_commands.spawn(Gravity::new(9.8));
+        assert!(world.entities.len() == 1);
+        drop(dropped_commands);
+        assert!(world.entities.is_empty());
+
+        let mut commands: Commands = world.commands();
+        let recycled: Entity = commands.spawn(Gravity::new(4.9));
+        assert!(recycled.index == dropped.index && recycled.generation != dropped.generation);
+
+        let mut appended_commands: Commands = world.commands();
+        let appended: Entity = appended_commands.spawn(Gravity::new(1.0));
+        commands.append(&mut appended_commands);
+        drop(appended_commands);
+
+        commands.flush_commands(&mut world, &mut render_state);
+        drop(commands);
+        assert!(world.is_entity_alive(recycled) && world.is_entity_alive(appended));
+        assert!(world.entities.len() == 2);
+    }
+
+    #[test]
+    fn commands_spawning_bundles_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        let falling_sprite: Entity = commands.spawn((
+            SpriteBundle::new(
+                Sprite::new("textures/lotus_pink_256x256.png".to_string()),
+                Transform::new_simple(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized))
+            ).draw_order(DrawOrder::new(2)),
+            PhysicsBodyBundle::new(
+                Collision::new(Collider::new_simple(GeometryType::Square)),
+                RigidBody::new(BodyType::Dynamic, 1.0, 0.9, 1.0)
+            ).velocity(Velocity::new(Vector2::new(1.0, 0.0))),
+            Gravity::new(9.8)
+        ));
+        let shape: Entity = commands.spawn((
+            ShapeBundle::new(
+                Shape::new(Orientation::Horizontal, GeometryType::Square, Color::by_option(ColorOption::Black)),
+                Transform::new_simple(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized))
+            ),
+            Velocity::new(Vector2::new(1.0, 0.0)),
+            Velocity::new(Vector2::new(2.0, 0.0))
+        ));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        assert!(world.get_entity_components_types_ids(&falling_sprite).len() == 8);
+        assert!(world.get_entity_component::<DrawOrder>(&falling_sprite).unwrap().0 == 2);
+        assert!(world.get_entity_component::<Velocity>(&falling_sprite).unwrap().x == 1.0);
+        assert!(world.get_entity_component::<Gravity>(&falling_sprite).unwrap().value == 9.8);
+
+        assert!(world.get_entity_components_types_ids(&shape).len() == 5);
+        assert!(world.is_entity_visible(shape));
+        assert!(world.get_entity_component::<Velocity>(&shape).unwrap().x == 2.0);
+    }
+
     #[test]
     fn entities_with_components_test() {
         let mut commands: Commands = Commands::new();
@@ -143,6 +465,116 @@ pub mod tests {
         assert!(entities.len() == 1);
     }
 
+    #[test]
+    fn typed_query_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        commands.spawn(Velocity::new(Vector2::new(1.0, 2.0)));
+        commands.spawn((Velocity::new(Vector2::new(3.0, 4.0)), Visibility::new(false)));
+        commands.spawn(Shape::new(Orientation::Horizontal, GeometryType::Square, Color::by_option(ColorOption::Black)));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        for (mut transform, velocity) in world.query::<(&mut Transform, &Velocity)>() {
+            transform.position.x += velocity.x;
+        }
+
+        let mut positions: Vec<f32> = world.query::<(&Transform, &Velocity)>()
+            .map(|(transform, _)| transform.position.x)
+            .collect();
+        positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        assert!(positions == vec![1.0, 3.0]);
+        assert!(world.query::<&Shape>().count() == 1);
+    }
+
+    #[test]
+    fn typed_query_with_optional_components_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        commands.spawn(Velocity::new(Vector2::new(0.0, 0.0)));
+        commands.spawn((
+            Velocity::new(Vector2::new(0.0, 0.0)),
+            Collision::new(Collider::new_simple(GeometryType::Square))
+        ));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let results: Vec<(Entity, bool)> = world.query::<(Entity, &Velocity, Option<&Collision>)>()
+            .map(|(entity, _, collision)| (entity, collision.is_some()))
+            .collect();
+
+        assert!(results.len() == 2);
+        assert!(results.iter().filter(|(_, has_collision)| *has_collision).count() == 1);
+    }
+
+    #[test]
+    fn typed_query_skips_conflicting_borrows_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        commands.spawn(Velocity::new(Vector2::new(0.0, 0.0)));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let entity: Entity = world.query::<(Entity, &Velocity)>().next().unwrap().0;
+        let _velocity: ComponentRefMut<'_, Velocity> = world.get_entity_component_mut::<Velocity>(&entity).unwrap();
+
+        assert!(world.query::<&Velocity>().count() == 0);
+        assert!(world.query_entity::<&Velocity>(&entity).is_none());
+        assert!(world.query_entity::<&Transform>(&entity).is_some());
+    }
+
+    #[test]
+    fn query_filters_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        commands.spawn(Velocity::new(Vector2::new(0.0, 0.0)));
+        commands.spawn((
+            Velocity::new(Vector2::new(0.0, 0.0)),
+            Collision::new(Collider::new_simple(GeometryType::Square))
+        ));
+        commands.spawn(Shape::new(Orientation::Horizontal, GeometryType::Square, Color::by_option(ColorOption::Black)));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        assert!(world.query_filtered::<Entity, With<Velocity>>().count() == 2);
+        assert!(world.query_filtered::<Entity, (With<Velocity>, Without<Collision>)>().count() == 1);
+        assert!(world.query_filtered::<Entity, Or<(With<Collision>, With<Shape>)>>().count() == 2);
+        assert!(world.query_filtered::<&Transform, Without<Velocity>>().count() == 1);
+    }
+
+    #[test]
+    fn query_change_detection_filters_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        commands.spawn(Velocity::new(Vector2::new(0.0, 0.0)));
+        commands.spawn(Velocity::new(Vector2::new(1.0, 1.0)));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        assert!(world.query_filtered::<Entity, Added<Velocity>>().count() == 2);
+        assert!(world.query_filtered::<Entity, Changed<Transform>>().count() == 2);
+
+        world.increment_change_tick();
+        assert!(world.query_filtered::<Entity, Added<Velocity>>().count() == 0);
+        assert!(world.query_filtered::<Entity, Changed<Transform>>().count() == 0);
+
+        for (mut transform, velocity) in world.query::<(&mut Transform, &Velocity)>() {
+            if velocity.x > 0.0 {
+                transform.position.x = velocity.x;
+            }
+        }
+
+        let changed: Vec<ComponentRef<'_, Velocity>> = world.query_filtered::<&Velocity, Changed<Transform>>().collect();
+        assert!(changed.len() == 1);
+        assert!(changed[0].x == 1.0);
+    }
+
     #[test]
     fn get_resource_as_immutable_test() {
         let world: World = World::new();
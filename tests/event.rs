@@ -0,0 +1,78 @@
+#[cfg(test)]
+pub mod tests {
+    use std::{cell::RefCell, rc::Rc};
+    use lotus_engine::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct ScoreChanged(u32);
+
+    fn dummy_context() -> Context {
+        return Context::new(RenderState::dummy(), World::new(), WindowConfiguration::default(), 0.0);
+    }
+
+    #[test]
+    fn events_are_double_buffered_test() {
+        let mut events: Events<ScoreChanged> = Events::new();
+        let mut reader: EventReader<ScoreChanged> = EventReader::new();
+
+        events.send(ScoreChanged(1));
+        events.update();
+        events.send(ScoreChanged(2));
+        assert!(events.len() == 2);
+
+        events.update();
+        assert!(events.iter().cloned().collect::<Vec<_>>() == vec![ScoreChanged(2)]);
+        assert!(reader.read(&events).cloned().collect::<Vec<_>>() == vec![ScoreChanged(2)]);
+
+        events.update();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn readers_track_their_own_cursor_test() {
+        let mut events: Events<ScoreChanged> = Events::new();
+        let mut first_reader: EventReader<ScoreChanged> = EventReader::new();
+        let mut second_reader: EventReader<ScoreChanged> = EventReader::new();
+
+        events.send(ScoreChanged(1));
+        assert!(first_reader.read(&events).count() == 1);
+        assert!(first_reader.is_empty(&events));
+
+        events.send(ScoreChanged(2));
+        assert!(first_reader.read(&events).cloned().collect::<Vec<_>>() == vec![ScoreChanged(2)]);
+        assert!(second_reader.len(&events) == 2);
+        assert!(second_reader.read(&events).cloned().collect::<Vec<_>>() == vec![ScoreChanged(1), ScoreChanged(2)]);
+        assert!(second_reader.read(&events).next().is_none());
+    }
+
+    #[test]
+    fn events_are_sent_and_read_between_systems_test() {
+        let mut context: Context = dummy_context();
+        let mut schedule: Schedule = Schedule::empty();
+        let received: Rc<RefCell<Vec<ScoreChanged>>> = Rc::new(RefCell::new(Vec::new()));
+        let received_by_system: Rc<RefCell<Vec<ScoreChanged>>> = received.clone();
+        let mut reader: EventReader<ScoreChanged> = EventReader::new();
+
+        context.add_event::<ScoreChanged>();
+        schedule.append(std::mem::replace(&mut context.schedule, Schedule::empty()));
+        schedule
+            .add_system(Stage::Update, System::new("scoring", |context: &mut Context| {
+                context.event_writer::<ScoreChanged>().unwrap().send(ScoreChanged(10));
+            }))
+            .add_system(Stage::PostUpdate, System::new("score_ui", move |context: &mut Context| {
+                let events: ResourceRef<'_, Events<ScoreChanged>> = context.world.get_resource::<Events<ScoreChanged>>().unwrap();
+                received_by_system.borrow_mut().extend(reader.read(&events).cloned());
+            }));
+
+        schedule.run(&mut context);
+        schedule.run(&mut context);
+        assert!(*received.borrow() == vec![ScoreChanged(10), ScoreChanged(10)]);
+        assert!(context.world.get_resource::<Events<ScoreChanged>>().unwrap().len() == 2);
+
+        schedule.disable_system("scoring");
+        schedule.run(&mut context);
+        schedule.run(&mut context);
+        assert!(received.borrow().len() == 2);
+        assert!(context.world.get_resource::<Events<ScoreChanged>>().unwrap().is_empty());
+    }
+}
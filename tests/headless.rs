@@ -0,0 +1,257 @@
+#[cfg(test)]
+pub mod tests {
+    use lotus_engine::*;
+
+    /// Returns a headless rendering state, panicking when the machine has no adapter at all.
+    fn headless_render_state() -> RenderState {
+        return block_on(RenderState::headless(64, 64)).expect("The golden image tests need a wgpu adapter");
+    }
+
+    #[test]
+    fn count_different_pixels_test() {
+        let image: image::RgbaImage = image::RgbaImage::from_pixel(4, 4, image::Rgba([100, 100, 100, 255]));
+        let mut other: image::RgbaImage = image.clone();
+        other.put_pixel(0, 0, image::Rgba([102, 100, 100, 255]));
+        other.put_pixel(1, 0, image::Rgba([100, 110, 100, 255]));
+
+        assert!(count_different_pixels(&image, &image, 0) == 0);
+        assert!(count_different_pixels(&image, &other, 2) == 1);
+        assert!(count_different_pixels(&image, &other, 0) == 2);
+        assert!(count_different_pixels(&image, &image::RgbaImage::new(2, 2), 255) == 16);
+        assert!(GoldenImage::new("tests/golden/shapes.png".to_string()).actual_path().ends_with("shapes.actual.png"));
+
+        if std::env::var_os("LOTUS_UPDATE_GOLDEN").is_none() {
+            let missing: GoldenImage = GoldenImage::new(std::env::temp_dir().join("lotus_golden/missing.png").to_string_lossy().to_string());
+            assert!(matches!(missing.compare(&image), Err(HeadlessError::MissingGolden(path)) if path == missing.path));
+            assert!(!missing.path.exists() && missing.actual_path().exists());
+        }
+    }
+
+    #[test]
+    #[ignore = "needs a wgpu adapter, run with cargo test -- --ignored"]
+    fn headless_shapes_golden_test() {
+        let mut render_state: RenderState = headless_render_state();
+        render_state.color = Some(Color::by_option(ColorOption::White));
+
+        let mut world: World = World::new();
+        let mut commands: Commands = world.commands();
+        commands.spawn((
+            Shape::new(Orientation::Horizontal, GeometryType::Square, Color::by_option(ColorOption::Red)),
+            Transform::new(Position::new(Vector2::new(-0.5, 0.5), Strategy::Normalized), 0.0, Vector2::new(0.25, 0.25))
+        ));
+        commands.spawn((
+            Shape::new(Orientation::Horizontal, GeometryType::Circle(Circle::new(64, 0.5)), Color::by_option(ColorOption::Blue)),
+            Transform::new(Position::new(Vector2::new(0.5, -0.5), Strategy::Normalized), 0.0, Vector2::new(0.5, 0.5))
+        ));
+        commands.spawn((
+            Shape::new(Orientation::Horizontal, GeometryType::Triangle, Color::by_option(ColorOption::Green)),
+            Transform::new(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized), 0.0, Vector2::new(0.5, 0.5)),
+            DrawOrder(1)
+        ));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let frame: image::RgbaImage = render_state.render_to_image(&mut world).unwrap();
+        assert!(frame.dimensions() == (64, 64));
+        assert!(frame.get_pixel(0, 63).0 == [255, 255, 255, 255]);
+        GoldenImage::new("tests/golden/shapes.png".to_string()).compare(&frame).unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs a wgpu adapter, run with cargo test -- --ignored"]
+    fn headless_sprites_golden_test() {
+        let mut render_state: RenderState = headless_render_state();
+        render_state.color = Some(Color::by_option(ColorOption::Black));
+
+        let mut world: World = World::new();
+        let mut commands: Commands = world.commands();
+        commands.spawn(SpriteBundle::new(
+            Sprite::new("textures/lotus_pink_32x32.png".to_string()),
+            Transform::new_simple(Position::new(Vector2::new(-0.5, 0.0), Strategy::Normalized))
+        ));
+        commands.spawn(SpriteBundle::new(
+            Sprite::new("textures/lotus_white_32x32.png".to_string()),
+            Transform::new_simple(Position::new(Vector2::new(0.5, 0.0), Strategy::Normalized))
+        ));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let frame: image::RgbaImage = render_state.render_to_image(&mut world).unwrap();
+        GoldenImage::new("tests/golden/sprites.png".to_string()).compare(&frame).unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs a wgpu adapter, run with cargo test -- --ignored"]
+    fn headless_batches_keep_the_spawn_order_test() {
+        let mut render_state: RenderState = headless_render_state();
+        render_state.color = Some(Color::by_option(ColorOption::Black));
+
+        let mut world: World = World::new();
+        let mut commands: Commands = world.commands();
+        commands.spawn(SpriteBundle::new(
+            Sprite::new("textures/lotus_white_32x32.png".to_string()),
+            Transform::new(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized), 0.0, Vector2::new(1.0, 1.0))
+        ));
+        commands.spawn((
+            Shape::new(Orientation::Horizontal, GeometryType::Square, Color::by_option(ColorOption::Red)),
+            Transform::new(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized), 0.0, Vector2::new(0.25, 0.25))
+        ));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let batched: image::RgbaImage = render_state.render_to_image(&mut world).unwrap();
+        render_state.batching = false;
+        let unbatched: image::RgbaImage = render_state.render_to_image(&mut world).unwrap();
+
+        assert!(batched.get_pixel(32, 32).0 == [255, 0, 0, 255]);
+        assert!(count_different_pixels(&batched, &unbatched, 2) == 0);
+    }
+
+    #[test]
+    #[ignore = "needs a wgpu adapter, run with cargo test -- --ignored"]
+    fn headless_cameras_and_post_process_golden_test() {
+        let mut render_state: RenderState = headless_render_state();
+        render_state.color = Some(Color::by_option(ColorOption::White));
+
+        let mut world: World = World::new();
+        let mut commands: Commands = world.commands();
+        commands.spawn((
+            Shape::new(Orientation::Horizontal, GeometryType::Square, Color::by_option(ColorOption::Red)),
+            Transform::new(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized), 0.0, Vector2::new(0.25, 0.25))
+        ));
+        commands.spawn(Camera::new()
+            .viewport(Viewport::new(0.0, 0.0, 0.5, 1.0))
+            .clear_color(Some(Color::by_option(ColorOption::Black)))
+        );
+        commands.spawn(Camera::new()
+            .viewport(Viewport::new(0.5, 0.0, 0.5, 1.0))
+            .zoom(2.0)
+        );
+        commands.flush_commands(&mut world, &mut render_state);
+        world.add_resource(Box::new(PostProcess::new().effect(PostEffect::vignette(0.8, 0.5))));
+
+        let frame: image::RgbaImage = render_state.render_to_image(&mut world).unwrap();
+        GoldenImage::new("tests/golden/cameras_and_post_process.png".to_string()).compare(&frame).unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs a wgpu adapter, run with cargo test -- --ignored"]
+    fn headless_lighting_golden_test() {
+        let mut render_state: RenderState = headless_render_state();
+        render_state.color = Some(Color::by_option(ColorOption::White));
+
+        let mut world: World = World::new();
+        let mut commands: Commands = world.commands();
+        commands.spawn((
+            Shape::new(Orientation::Horizontal, GeometryType::Square, Color::by_option(ColorOption::Red)),
+            Transform::new(Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized), 0.0, Vector2::new(0.15, 0.15)),
+            Collision::new(Collider::new(GeometryType::Square, Vector2::new(0.0, 0.0), Vector2::new(0.15, 0.15))),
+            Occluder::new()
+        ));
+        commands.spawn(SpriteBundle::new(
+            Sprite::new("textures/lotus_white_32x32.png".to_string()).normal_map("textures/dome_normal_128x128.png".to_string()),
+            Transform::new_simple(Position::new(Vector2::new(0.4, 0.5), Strategy::Normalized))
+        ));
+        commands.spawn((
+            PointLight2d::new().radius(2.5).height(0.3).cast_shadows(true),
+            Transform::new_simple(Position::new(Vector2::new(-0.6, 0.6), Strategy::Normalized))
+        ));
+        commands.flush_commands(&mut world, &mut render_state);
+        world.add_resource(Box::new(AmbientLight::new(Color::by_option(ColorOption::Blue), 0.2)));
+
+        let frame: image::RgbaImage = render_state.render_to_image(&mut world).unwrap();
+        GoldenImage::new("tests/golden/lighting.png".to_string()).compare(&frame).unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs a wgpu adapter, run with cargo test -- --ignored"]
+    fn headless_particles_golden_test() {
+        let mut render_state: RenderState = headless_render_state();
+        render_state.color = Some(Color::by_option(ColorOption::White));
+
+        let mut shape_emitter: ParticleEmitter = ParticleEmitter::shape(GeometryType::Square)
+            .emitting(false)
+            .color(ParticleGradient::linear(Color::by_option(ColorOption::Red), Color::by_option(ColorOption::Blue)))
+            .size(ParticleCurve::linear(0.1, 0.05));
+        let mut texture_emitter: ParticleEmitter = ParticleEmitter::texture("textures/lotus_white_32x32.png".to_string())
+            .emitting(false)
+            .space(ParticleSpace::Local)
+            .size(ParticleCurve::constant(0.15));
+
+        for index in 0..4 {
+            shape_emitter.particles.push(Particle {
+                position: Vector2::new(-0.75 + 0.5 * index as f32, 0.5),
+                velocity: Vector2::new(0.0, 0.0),
+                age: index as f32,
+                lifetime: 3.0
+            });
+        }
+        texture_emitter.particles.push(Particle { position: Vector2::new(-0.25, 0.0), velocity: Vector2::new(0.0, 0.0), age: 0.0, lifetime: 1.0 });
+        texture_emitter.particles.push(Particle { position: Vector2::new(0.25, 0.0), velocity: Vector2::new(0.0, 0.0), age: 0.0, lifetime: 1.0 });
+
+        let mut world: World = World::new();
+        let mut commands: Commands = world.commands();
+        commands.spawn((shape_emitter, Transform::default()));
+        commands.spawn((texture_emitter, Transform::new_simple(Position::new(Vector2::new(0.0, -0.5), Strategy::Normalized))));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let frame: image::RgbaImage = render_state.render_to_image(&mut world).unwrap();
+        GoldenImage::new("tests/golden/particles.png".to_string()).compare(&frame).unwrap();
+    }
+
+    #[test]
+    fn frame_recorder_test() {
+        let mut frame_recorder: FrameRecorder = FrameRecorder::new("captures".to_string()).frame_interval(2).max_frames(2);
+
+        assert!(frame_recorder.next_frame() == Some(std::path::PathBuf::from("captures").join("frame_00000.png")));
+        assert!(frame_recorder.next_frame().is_none());
+        assert!(frame_recorder.next_frame() == Some(std::path::PathBuf::from("captures").join("frame_00001.png")));
+        assert!(frame_recorder.is_finished());
+        assert!(frame_recorder.next_frame().is_none());
+        assert!(FrameRecorder::new("captures".to_string()).frame_interval(0).frame_interval == 1);
+
+        let mut context: Context = Context::new(RenderState::dummy(), World::new(), WindowConfiguration::default(), 0.0);
+        context.capture_screenshot("screenshot.png".to_string());
+        context.start_recording(FrameRecorder::new("captures".to_string()));
+
+        assert!(context.render_state.screenshot_requests.len() == 1);
+        assert!(context.is_recording());
+        assert!(context.stop_recording() == 0);
+        assert!(!context.is_recording());
+
+        let mut finished_recorder: FrameRecorder = FrameRecorder::new("captures".to_string()).max_frames(1);
+        finished_recorder.next_frame();
+        context.start_recording(finished_recorder);
+        assert!(context.is_recording());
+        assert!(context.stop_recording() == 1);
+    }
+
+    #[test]
+    #[ignore = "needs a wgpu adapter, run with cargo test -- --ignored"]
+    fn headless_capture_test() {
+        let mut render_state: RenderState = headless_render_state();
+        render_state.color = Some(Color::by_option(ColorOption::Blue));
+
+        let directory: std::path::PathBuf = std::env::temp_dir().join("lotus_capture_test");
+        let _ = std::fs::remove_dir_all(&directory);
+        let screenshot: std::path::PathBuf = directory.join("screenshot.png");
+        render_state.screenshot_requests.push(screenshot.clone());
+        render_state.frame_recorder = Some(FrameRecorder::new(directory.join("frames").to_string_lossy().to_string()).max_frames(1));
+
+        let mut world: World = World::new();
+        let frame: image::RgbaImage = render_state.render_to_image(&mut world).unwrap();
+        let recorded_frame: std::path::PathBuf = directory.join("frames").join("frame_00000.png");
+
+        assert!(render_state.screenshot_requests.is_empty());
+        assert!(render_state.frame_recorder.as_ref().is_some_and(|frame_recorder| frame_recorder.is_finished() && frame_recorder.frames_recorded == 1));
+
+        for path in [screenshot, recorded_frame] {
+            let mut saved: Option<image::RgbaImage> = None;
+            for _ in 0..100 {
+                if let Ok(image) = image::open(&path) {
+                    saved = Some(image.to_rgba8());
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+            assert!(saved.is_some_and(|saved| count_different_pixels(&saved, &frame, 0) == 0));
+        }
+    }
+}
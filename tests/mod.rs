@@ -3,3 +3,27 @@ pub mod ecs;
 
 /// Module with binding related testing.
 pub mod bindings;
+
+/// Module with system scheduling related testing.
+pub mod schedule;
+
+/// Module with event channel related testing.
+pub mod event;
+
+/// Module with reflection related testing.
+pub mod reflect;
+
+/// Module with scene files related testing.
+pub mod scene;
+
+/// Module with save game related testing.
+pub mod save;
+
+/// Module with rendering related testing.
+pub mod render;
+
+/// Module with headless rendering and golden image related testing.
+pub mod headless;
+
+/// Module with particle emitters related testing.
+pub mod particle;
@@ -0,0 +1,71 @@
+#[cfg(test)]
+pub mod tests {
+    use lotus_engine::*;
+
+    #[test]
+    fn particle_curves_are_sampled_test() {
+        let curve: ParticleCurve = ParticleCurve::linear(1.0, 0.0).key(0.5, 2.0);
+
+        assert!(curve.keys == vec![(0.0, 1.0), (0.5, 2.0), (1.0, 0.0)]);
+        assert!(curve.sample(-1.0) == 1.0);
+        assert!(curve.sample(0.25) == 1.5);
+        assert!(curve.sample(0.75) == 1.0);
+        assert!(curve.sample(2.0) == 0.0);
+        assert!(ParticleCurve::constant(0.3).sample(0.7) == 0.3);
+
+        let gradient: ParticleGradient = ParticleGradient::linear(Color::by_option(ColorOption::Red), Color::by_option(ColorOption::Blue));
+        assert!(gradient.sample(0.0) == ColorOption::Red.to_rgba());
+        assert!(gradient.sample(0.5) == [0.5, 0.0, 0.5, 1.0]);
+        assert!(gradient.sample(1.0) == ColorOption::Blue.to_rgba());
+    }
+
+    #[test]
+    fn particle_emitter_spawns_and_simulates_test() {
+        let mut particle_emitter: ParticleEmitter = ParticleEmitter::shape(GeometryType::Square)
+            .spawn_rate(10.0)
+            .burst(5)
+            .lifetime(1.0, 1.0)
+            .velocity(Vector2::new(1.0, 0.0), Vector2::new(1.0, 0.0))
+            .gravity(Vector2::new(0.0, -2.0))
+            .max_particles(8);
+
+        particle_emitter.update(0.05, Vector2::new(0.5, 0.5));
+        assert!(particle_emitter.particles.len() == 5);
+        assert!(particle_emitter.particles.iter().all(|particle| particle.position == Vector2::new(0.5, 0.5)));
+
+        particle_emitter.update(0.5, Vector2::new(0.5, 0.5));
+        assert!(particle_emitter.particles.len() == 8);
+        assert!(particle_emitter.particles[0].velocity == Vector2::new(1.0, -1.0));
+        assert!(particle_emitter.particles[0].position == Vector2::new(1.0, 0.0));
+        assert!(particle_emitter.particles[0].progress() == 0.5);
+
+        particle_emitter.emitting = false;
+        particle_emitter.update(0.6, Vector2::new(0.5, 0.5));
+        assert!(particle_emitter.particles.len() == 3);
+        assert!(!particle_emitter.is_finished());
+
+        particle_emitter.update(1.0, Vector2::new(0.5, 0.5));
+        assert!(particle_emitter.is_finished());
+
+        particle_emitter.emit(2);
+        assert!(!particle_emitter.is_finished());
+        particle_emitter.update(0.0, Vector2::new(0.5, 0.5));
+        assert!(particle_emitter.particles.len() == 2);
+    }
+
+    #[test]
+    fn particle_emitter_spaces_test() {
+        let mut world_emitter: ParticleEmitter = ParticleEmitter::texture("textures/lotus_white_32x32.png".to_string())
+            .burst(3)
+            .spawn_radius(0.1);
+        let mut local_emitter: ParticleEmitter = world_emitter.clone().space(ParticleSpace::Local);
+
+        world_emitter.update(0.0, Vector2::new(2.0, 2.0));
+        local_emitter.update(0.0, Vector2::new(2.0, 2.0));
+
+        assert!(world_emitter.particles.iter().all(|particle| (particle.position - Vector2::new(2.0, 2.0)).magnitude() <= 0.1));
+        assert!(local_emitter.particles.iter().all(|particle| particle.position.magnitude() <= 0.1));
+        assert!(local_emitter.appearance.texture_key() == "textures/lotus_white_32x32.png");
+        assert!(local_emitter.appearance.geometry() == BatchGeometry::Quad);
+    }
+}
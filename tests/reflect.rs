@@ -0,0 +1,123 @@
+#[cfg(test)]
+pub mod tests {
+    use std::time::Duration;
+    use lotus_engine::*;
+
+    #[test]
+    fn reflect_fields_by_path_test() {
+        let mut transform: Transform = Transform::new(
+            Position::new(Vector2::new(0.5, -0.5), Strategy::Pixelated),
+            45.0,
+            Vector2::new(2.0, 2.0)
+        );
+
+        let fields: Vec<FieldInfo> = transform.fields();
+        assert!(fields == vec![
+            FieldInfo::new("position", "Position"),
+            FieldInfo::new("rotation", "f32"),
+            FieldInfo::new("scale", "Vector2")
+        ]);
+
+        assert!(transform.path("position.x").unwrap().downcast_ref::<f32>() == Some(&0.5));
+        assert!(transform.path("position.strategy").unwrap().to_value().unwrap() == ReflectValue::String("Pixelated".to_string()));
+        assert!(transform.path("dirty_position").is_err());
+
+        transform.set_path("scale.y", &ReflectValue::Float(3.0)).unwrap();
+        transform.set_path("position.strategy", &ReflectValue::String("Normalized".to_string())).unwrap();
+        assert!(transform.scale.y == 3.0);
+        assert!(transform.position.strategy == Strategy::Normalized);
+
+        assert!(transform.set_path("rotation", &ReflectValue::Bool(true)) == Err(ReflectError::TypeMismatch { expected: "float", found: "bool" }));
+        assert!(transform.set_path("position.z", &ReflectValue::Float(1.0)) == Err(ReflectError::FieldNotFound("z".to_string())));
+    }
+
+    #[test]
+    fn reflect_values_round_trip_test() {
+        let shape: Shape = Shape::new(Orientation::Vertical, GeometryType::Circle(Circle::new(32, 0.25)), Color::by_option(ColorOption::Red));
+        let value: ReflectValue = shape.to_value().unwrap();
+        assert!(value.get("geometry_type").unwrap().get("Circle").unwrap().get("radius") == Some(&ReflectValue::Float(0.25)));
+
+        let shape_from_value: Shape = Shape::from_value(&value).unwrap();
+        assert!(shape_from_value.geometry_type == GeometryType::Circle(Circle::new(32, 0.25)));
+        assert!(shape_from_value.color.to_array() == shape.color.to_array());
+
+        assert!(Visibility::new(false).to_value().unwrap() == ReflectValue::Bool(false));
+        assert!(u64::MAX.to_value() == Err(ReflectError::OutOfRange("u64".to_string())));
+        assert!(usize::MAX.to_value() == Err(ReflectError::OutOfRange("usize".to_string())));
+        assert!(u64::from_value(&(i64::MAX as u64).to_value().unwrap()).unwrap() == i64::MAX as u64);
+        assert!(Sprite::from_value(&Sprite::new("textures/lotus_pink_256x256.png".to_string()).to_value().unwrap()).unwrap().vertices.len() == 4);
+
+        let mut sprite: Sprite = Sprite::new("textures/lotus_pink_256x256.png".to_string());
+        sprite.set_value(&ReflectValue::Map(vec![("normal_map".to_string(), ReflectValue::String("textures/dome_normal_128x128.png".to_string()))])).unwrap();
+        assert!(sprite.path == "textures/lotus_pink_256x256.png" && sprite.normal_map == Some("textures/dome_normal_128x128.png".to_string()));
+        assert!(sprite.set_value(&ReflectValue::Map(vec![("scale".to_string(), ReflectValue::Float(1.0))])) == Err(ReflectError::FieldNotFound("scale".to_string())));
+
+        let mut timer: Timer = Timer::new(TimerType::Repeat, Duration::from_secs_f32(1.5));
+        timer.tick(0.5);
+        let timer_from_value: Timer = Timer::from_value(&timer.to_value().unwrap()).unwrap();
+        assert!(timer_from_value.timer_type == TimerType::Repeat && timer_from_value.elapsed == timer.elapsed && timer_from_value.duration == timer.duration);
+
+        let transform: Transform = Transform::from_value(&ReflectValue::Map(vec![("rotation".to_string(), ReflectValue::Integer(90))])).unwrap();
+        assert!(transform.rotation == 90.0 && transform.scale == Vector2::new(1.0, 1.0));
+        assert!(Velocity::from_value(&ReflectValue::Map(vec![("x".to_string(), ReflectValue::Float(1.0))])).err() == Some(ReflectError::MissingField("y".to_string())));
+    }
+
+    #[test]
+    fn world_reflection_by_type_name_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+
+        let entity: Entity = commands.spawn((
+            Transform::new_simple(Position::new(Vector2::new(0.1, 0.2), Strategy::Normalized)),
+            Velocity::new(Vector2::new(1.0, 0.0)),
+            DrawOrder::new(3)
+        ));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let components: Vec<&'static str> = world.reflect_entity(&entity).unwrap().into_iter().map(|(type_name, _)| type_name).collect();
+        assert!(components == vec!["DrawOrder", "Transform", "Velocity", "Visibility"]);
+
+        assert!(world.get_component_path(&entity, "DrawOrder", "").unwrap() == ReflectValue::Integer(3));
+        assert!(world.get_component_path(&entity, "Transform", "position.y").unwrap() == ReflectValue::Float(0.2));
+
+        world.increment_change_tick();
+        world.set_component_path(&entity, "Velocity", "y", &ReflectValue::Float(-2.0)).unwrap();
+        assert!(world.get_entity_component::<Velocity>(&entity).unwrap().y == -2.0);
+        assert!(world.query_filtered::<Entity, Changed<Velocity>>().any(|changed| changed == entity));
+
+        assert!(world.get_component_path(&entity, "Gravity", "value") == Err(ReflectError::MissingComponent("Gravity".to_string())));
+        assert!(world.get_component_path(&entity, "Player", "") == Err(ReflectError::UnknownType("Player".to_string())));
+
+        let component: Box<dyn Component> = world.type_registry.construct_component("Gravity", &ReflectValue::Map(vec![("value".to_string(), ReflectValue::Float(4.9))])).unwrap();
+        assert!(component.as_any().downcast_ref::<Gravity>().unwrap().value == 4.9);
+
+        world.add_resource(Box::new(Timer::new(TimerType::Once, Duration::from_secs(2))));
+        world.set_resource_path("Timer", "elapsed", &ReflectValue::Float(0.5)).unwrap();
+        assert!(world.get_resource_path("Timer", "timer_type").unwrap() == ReflectValue::String("Once".to_string()));
+        assert!(world.get_resource::<Timer>().unwrap().elapsed == Duration::from_millis(500));
+    }
+
+    #[test]
+    fn reflect_font_loading_errors_test() {
+        let mut text: Text = Text::from_value(&ReflectValue::Map(vec![
+            ("font".to_string(), Font::new(Fonts::RobotoMono.get_path(), 16.0).to_value().unwrap()),
+            ("position".to_string(), Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized).to_value().unwrap()),
+            ("color".to_string(), Color::by_option(ColorOption::White).to_value().unwrap()),
+            ("content".to_string(), ReflectValue::String("Score".to_string()))
+        ])).unwrap();
+        let missing: ReflectValue = ReflectValue::String("fonts/missing.ttf".to_string());
+
+        assert!(text.set_path("font.path", &missing) == Err(ReflectError::AssetNotLoaded("fonts/missing.ttf".to_string())));
+        assert!(text.font.path == Fonts::RobotoMono.get_path());
+
+        text.set_path("font.path", &ReflectValue::String(Fonts::UnderdogRegular.get_path())).unwrap();
+        text.set_path("font.size", &ReflectValue::Float(20.0)).unwrap();
+        assert!(text.font.bytes == Fonts::UnderdogRegular.get_bytes());
+        assert!(text.font.size == 20.0);
+
+        let missing_font: ReflectValue = ReflectValue::Map(vec![("path".to_string(), missing), ("size".to_string(), ReflectValue::Float(16.0))]);
+        assert!(Font::from_value(&missing_font).is_err());
+        assert!(Font::try_new("../../assets/fonts/Missing.ttf".to_string(), 16.0).is_err());
+    }
+}
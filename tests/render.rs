@@ -0,0 +1,194 @@
+#[cfg(test)]
+pub mod tests {
+    use lotus_engine::*;
+
+    fn instance(x: f32) -> BatchInstance {
+        let mut transform: [[f32; 4]; 4] = [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0]];
+        transform[3][0] = x;
+        return BatchInstance::new(transform, [0.0, 0.0, 1.0, 1.0], ColorOption::White.to_rgba(), 2);
+    }
+
+    #[test]
+    fn batch_builder_groups_instances_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+        let text: Entity = commands.spawn(Transform::default());
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let mut batch_builder: BatchBuilder = BatchBuilder::new();
+        let circle: GeometryType = GeometryType::Circle(Circle::new(64, 0.5));
+
+        batch_builder.push("textures/lotus_pink_256x256.png", BatchGeometry::Quad, instance(0.0));
+        batch_builder.push("textures/lotus_pink_256x256.png", BatchGeometry::Quad, instance(0.1));
+        batch_builder.push("textures/lotus_pink_128x128.png", BatchGeometry::Quad, instance(0.2));
+        batch_builder.push("dummy_texture", BatchGeometry::Shape(circle.clone()), instance(0.3));
+        batch_builder.push("dummy_texture", BatchGeometry::Shape(circle.clone()), instance(0.4));
+        batch_builder.push_text(text);
+        batch_builder.push("dummy_texture", BatchGeometry::Shape(circle.clone()), instance(0.5));
+
+        assert!(batch_builder.instances.len() == 6);
+        assert!(batch_builder.batches_count() == 4);
+        assert!(batch_builder.draws == vec![
+            BatchDraw::Instanced(Batch { texture: "textures/lotus_pink_256x256.png".to_string(), geometry: BatchGeometry::Quad, instances: 0..2, material: None }),
+            BatchDraw::Instanced(Batch { texture: "textures/lotus_pink_128x128.png".to_string(), geometry: BatchGeometry::Quad, instances: 2..3, material: None }),
+            BatchDraw::Instanced(Batch { texture: "dummy_texture".to_string(), geometry: BatchGeometry::Shape(circle.clone()), instances: 3..5, material: None }),
+            BatchDraw::Text(text),
+            BatchDraw::Instanced(Batch { texture: "dummy_texture".to_string(), geometry: BatchGeometry::Shape(circle.clone()), instances: 5..6, material: None })
+        ]);
+        assert!(batch_builder.instances[4].transform[3][0] == 0.4);
+
+        assert!(BatchGeometry::Quad.to_index_array().len() == 6);
+        assert!(BatchGeometry::Quad.key() != BatchGeometry::Shape(GeometryType::Square).key());
+        assert!(BatchGeometry::Shape(circle).to_vertex_array().iter().all(|vertex| vertex.color == ColorOption::White.to_rgba()));
+    }
+
+    #[test]
+    fn material_test() {
+        let mut flash: Material = Material::flash(Color::by_option(ColorOption::Red));
+        flash.set_param(2, 0.5);
+        flash.set_param(0, 1.0);
+
+        let uniforms: MaterialUniforms = flash.to_uniforms(2.5);
+        assert!(uniforms.time == 2.5);
+        assert!(uniforms.color == ColorOption::Red.to_rgba());
+        assert!(uniforms.params == [1.0, 0.0, 0.5, 0.0]);
+        assert!(std::mem::size_of::<MaterialUniforms>() == 48);
+
+        for material in [flash, Material::dissolve(0.3, Color::by_option(ColorOption::Black)), Material::outline(Color::by_option(ColorOption::White), 2.0)] {
+            let source: String = material.load_source().unwrap();
+            assert!(source.contains("fn vs_main") && source.contains("fn fs_main"));
+        }
+        assert!(Material::new("shaders/missing.wgsl".to_string()).load_source().is_err());
+
+        let textured: Material = Material::new("shaders/water.wgsl".to_string())
+            .texture("textures/lotus_pink_32x32.png".to_string())
+            .texture("textures/lotus_pink_64x64.png".to_string())
+            .texture("textures/lotus_pink_128x128.png".to_string())
+            .texture("textures/lotus_pink_256x256.png".to_string())
+            .texture("textures/lotus_white_32x32.png".to_string());
+        assert!(textured.bound_textures().len() == 4);
+        assert!(Material::from_value(&textured.to_value().unwrap()).unwrap().textures == textured.textures);
+
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+        let entity: Entity = commands.spawn(Material::outline(Color::by_option(ColorOption::White), 1.0));
+        commands.flush_commands(&mut world, &mut render_state);
+        assert!(world.reflect_entity(&entity).unwrap().iter().any(|(type_name, _)| *type_name == "Material"));
+
+        let mut batch_builder: BatchBuilder = BatchBuilder::new();
+        let material: BatchMaterial = BatchMaterial { entity, shader: textured.shader.clone(), textures: Vec::new() };
+        batch_builder.push("textures/lotus_pink_256x256.png", BatchGeometry::Quad, instance(0.0));
+        batch_builder.push_material("textures/lotus_pink_256x256.png", BatchGeometry::Quad, material.clone(), instance(0.1));
+        batch_builder.push_material("textures/lotus_pink_256x256.png", BatchGeometry::Quad, material, instance(0.2));
+        batch_builder.push("textures/lotus_pink_256x256.png", BatchGeometry::Quad, instance(0.3));
+        assert!(batch_builder.batches_count() == 4);
+    }
+
+    #[test]
+    fn post_process_test() {
+        let world: World = World::new();
+        assert!(*world.get_resource::<PostProcess>().unwrap() == PostProcess::default());
+        assert!(world.get_resource::<PostProcess>().unwrap().active_effects().is_empty());
+
+        let mut post_process: PostProcess = PostProcess::new()
+            .effect(PostEffect::bloom(0.8, 1.5))
+            .effect(PostEffect::crt(0.4, 0.1))
+            .effect(PostEffect::custom("shaders/grayscale.wgsl".to_string(), vec![1.0, 2.0, 3.0, 4.0, 5.0]));
+        assert!(post_process.active_effects().len() == 3);
+        assert!(post_process.effects[0].params() == [0.8, 1.5, 2.0, 0.0]);
+        assert!(post_process.effects[2].params() == [1.0, 2.0, 3.0, 4.0]);
+        assert!(post_process.effects[2].shader_key() == "shaders/grayscale.wgsl");
+        assert!(post_process.effects[2].load_source().unwrap().contains("fn fs_main"));
+        assert!(PostEffect::custom("shaders/missing.wgsl".to_string(), Vec::new()).load_source().is_err());
+
+        post_process.enabled = false;
+        assert!(post_process.active_effects().is_empty());
+        assert!(WindowConfiguration::default().post_process.is_none());
+        assert!(WindowConfiguration::default().post_process(post_process.clone()).clone().post_process == Some(post_process.clone()));
+
+        let color_grading: PostEffect = PostEffect::color_grading("textures/lut.png".to_string(), 0.5);
+        assert!(color_grading.texture() == Some("textures/lut.png"));
+        assert!(PostEffect::vignette(0.5, 0.3).texture().is_none());
+
+        let uniforms: PostProcessUniforms = color_grading.to_uniforms(1.5, (800, 600));
+        assert!(uniforms.time == 1.5);
+        assert!(uniforms.resolution == [800.0, 600.0]);
+        assert!(uniforms.params == [0.5, 0.0, 0.0, 0.0]);
+        assert!(std::mem::size_of::<PostProcessUniforms>() == 32);
+
+        for effect in [PostEffect::bloom(0.8, 1.0), PostEffect::vignette(0.5, 0.3), PostEffect::crt(0.4, 0.1), color_grading] {
+            let source: String = effect.load_source().unwrap();
+            assert!(source.contains("fn vs_main") && source.contains("fn fs_main"));
+        }
+    }
+
+    #[test]
+    fn camera_test() {
+        assert!(Viewport::full().to_pixels(800, 600) == (0.0, 0.0, 800.0, 600.0));
+        assert!(Viewport::new(0.5, 0.0, 0.5, 1.0).to_pixels(800, 600) == (400.0, 0.0, 400.0, 600.0));
+        assert!(Viewport::new(0.75, 0.75, 0.5, 0.5).to_pixels(800, 600) == (600.0, 450.0, 200.0, 150.0));
+
+        let minimap: Camera = Camera::new()
+            .target(CameraTarget::texture("minimap".to_string(), 256, 0))
+            .zoom(2.0)
+            .order(1);
+        assert!(!minimap.is_window_target());
+        assert!(minimap.target.texture_name() == Some("minimap"));
+        assert!(minimap.target == CameraTarget::Texture { name: "minimap".to_string(), width: 256, height: 1 });
+
+        let projection: Matrix4<f32> = minimap.projection_matrix(400.0, 200.0);
+        let corner: Vector4<f32> = projection * Vector4::new(4.0, 2.0, 0.0, 1.0);
+        assert!((corner.x - 1.0).abs() < f32::EPSILON && (corner.y - 1.0).abs() < f32::EPSILON);
+
+        let view: Matrix4<f32> = minimap.view_matrix(Vector2::new(0.5, -0.25));
+        assert!(view * Vector4::new(0.5, -0.25, 0.0, 1.0) == Vector4::new(0.0, 0.0, 0.0, 1.0));
+
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let mut commands: Commands = world.commands();
+        let player: Entity = commands.spawn(Transform::default());
+        let left: Entity = commands.spawn(Camera::new().viewport(Viewport::new(0.0, 0.0, 0.5, 1.0)).follow(player));
+        commands.spawn(Camera::new().active(false));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let cameras: Vec<(Entity, Camera)> = world.query::<(Entity, &Camera)>()
+            .filter(|(_, camera)| camera.active)
+            .map(|(entity, camera)| (entity, camera.clone()))
+            .collect();
+        assert!(cameras.len() == 1);
+        assert!(cameras[0].0 == left && cameras[0].1.follow == Some(player));
+
+        let mut batch_builder: BatchBuilder = BatchBuilder::new();
+        batch_builder.push("minimap", BatchGeometry::Quad, instance(0.0));
+        let clear: Batch = batch_builder.push_detached("dummy_texture", BatchGeometry::Shape(GeometryType::Square), instance(0.1));
+        assert!(clear.instances == (1..2));
+        assert!(batch_builder.instances.len() == 2 && batch_builder.draws.len() == 1);
+    }
+
+    #[test]
+    fn lighting_test() {
+        let edges: Vec<(Vector2<f32>, Vector2<f32>)> = Occluder::edges(&Collider::new(GeometryType::Square, Vector2::new(1.0, 0.0), Vector2::new(0.5, 0.25)));
+        assert!(edges.len() == 4);
+        assert!(edges[0] == (Vector2::new(0.5, -0.25), Vector2::new(1.5, -0.25)));
+        assert!(edges[3] == (Vector2::new(0.5, 0.25), Vector2::new(0.5, -0.25)));
+        assert!(Occluder::edges(&Collider::new_simple(GeometryType::Circle(Circle::new(64, 0.5)))).len() == 12);
+
+        let point_light: PointLight2d = PointLight2d::new().color(Color::by_option(ColorOption::Red)).intensity(2.0).cast_shadows(true);
+        let mut lighting_uniforms: LightingUniforms = LightingUniforms::new(&AmbientLight::new(Color::by_option(ColorOption::White), 0.5), [800.0, 600.0]);
+        assert!(lighting_uniforms.ambient == [0.5, 0.5, 0.5, 1.0]);
+        assert!(std::mem::size_of::<LightingUniforms>() == 2848);
+
+        for _ in 0..20 {
+            lighting_uniforms.push_light(&point_light, [400.0, 300.0], 100.0, 10.0);
+        }
+        assert!(lighting_uniforms.light_count == 16);
+        assert!(lighting_uniforms.lights[0].color == [2.0, 0.0, 0.0, 1.0] && lighting_uniforms.lights[0].cast_shadows == 1);
+        assert!(lighting_uniforms.push_edge([0.0, 0.0], [10.0, 0.0]));
+        assert!(lighting_uniforms.edges[0] == [0.0, 0.0, 10.0, 0.0] && lighting_uniforms.edge_count == 1);
+
+        let sprite: Sprite = Sprite::new("textures/lotus_pink_128x128.png".to_string()).normal_map("textures/dome_normal_128x128.png".to_string());
+        assert!(Sprite::from_value(&sprite.to_value().unwrap()).unwrap().normal_map == sprite.normal_map);
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+pub mod tests {
+    use std::{env, fs, path::PathBuf, time::Duration};
+    use lotus_engine::*;
+
+    fn spawn_checkpoint(world: &mut World, render_state: &mut RenderState) -> (Entity, Entity) {
+        let mut commands: Commands = world.commands();
+
+        let player: Entity = commands.spawn((
+            Transform::new_simple(Position::new(Vector2::new(0.5, 0.0), Strategy::Normalized)),
+            Velocity::new(Vector2::new(1.0, 0.0)),
+            Persistent
+        ));
+        commands.spawn_child(player, Gravity::new(9.8));
+        let wall: Entity = commands.spawn(Collision::new(Collider::new_simple(GeometryType::Rectangle)));
+        commands.add_resource(Box::new(Timer::new(TimerType::Repeat, Duration::from_secs(3))));
+        commands.flush_commands(world, render_state);
+        return (player, wall);
+    }
+
+    #[test]
+    fn save_game_snapshot_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        spawn_checkpoint(&mut world, &mut render_state);
+
+        let save_game: SaveGame = SaveGame::new(1).resource::<Timer>();
+        let save_data: SaveData = save_game.snapshot(&world).unwrap();
+
+        assert!(save_data.version == 1);
+        assert!(save_data.get_resource("Timer").unwrap().get("timer_type") == Some(&ReflectValue::String("Repeat".to_string())));
+        assert!(save_data.scene.entities.len() == 2);
+        assert!(save_data.scene.entities[1].parent == Some(0));
+        assert!(save_data.scene.entities[0].components.iter().any(|(type_name, _)| type_name == "Persistent"));
+
+        let json: serde_json::Value = save_data.to_json();
+        assert!(json["version"] == 1);
+        assert!(SaveData::from_json(&json).unwrap() == save_data);
+
+        let missing_resource: SaveGame = SaveGame::new(1).resource::<Timer>();
+        assert!(matches!(missing_resource.snapshot(&World::new()), Err(SaveError::Reflect(ReflectError::MissingResource(_)))));
+    }
+
+    #[test]
+    fn load_game_restores_the_world_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        let (player, wall) = spawn_checkpoint(&mut world, &mut render_state);
+
+        let path: PathBuf = env::temp_dir().join("lotus_save_test").join("slot_1.json");
+        let path: &str = path.to_str().unwrap();
+        let save_game: SaveGame = SaveGame::new(1).resource::<Timer>();
+        world.save_game(&save_game, path).unwrap();
+
+        let mut commands: Commands = world.commands();
+        commands.despawn(player);
+        commands.spawn((Velocity::new(Vector2::new(-1.0, 0.0)), Persistent));
+        commands.flush_commands(&mut world, &mut render_state);
+        world.get_resource_mut::<Timer>().unwrap().elapsed = Duration::from_secs(2);
+
+        let mut commands: Commands = world.commands();
+        let entities: Vec<Entity> = commands.load_game(&save_game, path).unwrap();
+        commands.flush_commands(&mut world, &mut render_state);
+
+        assert!(world.query_filtered::<Entity, With<Persistent>>().count() == 1);
+        assert!(world.get_entity_component::<Velocity>(&entities[0]).unwrap().x == 1.0);
+        assert!(world.get_entity_component::<Transform>(&entities[0]).unwrap().position.x == 0.5);
+        assert!(world.get_entity_component::<Parent>(&entities[1]).unwrap().0 == entities[0]);
+        assert!(world.get_entity_component::<Gravity>(&entities[1]).unwrap().value == 9.8);
+        assert!(world.is_entity_alive(wall));
+        assert!(world.get_resource::<Timer>().unwrap().elapsed == Duration::ZERO);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn save_game_migrations_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+
+        let old_timer: ReflectValue = ReflectValue::Map(vec![
+            ("kind".to_string(), ReflectValue::String("Once".to_string())),
+            ("duration".to_string(), ReflectValue::Integer(5)),
+            ("elapsed".to_string(), ReflectValue::Float(1.5)),
+            ("is_finished".to_string(), ReflectValue::Bool(false))
+        ]);
+        let save_data: SaveData = SaveData::new(1, vec![("Clock".to_string(), old_timer)], Scene::default());
+
+        let save_game: SaveGame = SaveGame::new(3)
+            .resource::<Timer>()
+            .migration(1, |save_data| {
+                for (type_name, _) in save_data.resources.iter_mut().filter(|(type_name, _)| type_name == "Clock") {
+                    *type_name = "Timer".to_string();
+                }
+                return Ok(());
+            })
+            .migration(2, |save_data| {
+                let ReflectValue::Map(fields) = save_data.get_resource_mut("Timer").unwrap() else {
+                    return Err(SaveError::Format("expected a map".to_string()));
+                };
+                fields.iter_mut().filter(|(name, _)| name == "kind").for_each(|(name, _)| *name = "timer_type".to_string());
+                return Ok(());
+            });
+
+        let mut commands: Commands = world.commands();
+        commands.restore_game(&save_game, save_data.clone()).unwrap();
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let timer: Timer = world.get_resource_cloned::<Timer>().unwrap();
+        assert!(timer.timer_type == TimerType::Once && timer.duration == Duration::from_secs(5) && timer.elapsed == Duration::from_millis(1500));
+
+        let without_migration: SaveGame = SaveGame::new(2).resource::<Timer>();
+        assert!(matches!(without_migration.migrate(&mut save_data.clone()), Err(SaveError::MissingMigration(1))));
+
+        let older_save_game: SaveGame = SaveGame::new(0);
+        assert!(matches!(older_save_game.migrate(&mut save_data.clone()), Err(SaveError::UnsupportedVersion(1))));
+    }
+}
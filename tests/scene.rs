@@ -0,0 +1,132 @@
+#[cfg(test)]
+pub mod tests {
+    use std::{env, fs, path::PathBuf};
+    use lotus_engine::*;
+
+    fn spawn_player(world: &mut World, render_state: &mut RenderState) {
+        let mut commands: Commands = world.commands();
+
+        let player: Entity = commands.spawn((
+            SpriteBundle::new(
+                Sprite::new("textures/lotus_pink_256x256.png".to_string()),
+                Transform::new_simple(Position::new(Vector2::new(-0.5, 0.25), Strategy::Normalized))
+            ).draw_order(DrawOrder::new(3)),
+            Velocity::new(Vector2::new(1.0, 0.0))
+        ));
+        commands.spawn_child(player, ShapeBundle::new(
+            Shape::new(Orientation::Horizontal, GeometryType::Square, Color::by_option(ColorOption::Black)),
+            Transform::new_simple(Position::new(Vector2::new(0.1, 0.0), Strategy::Normalized))
+        ));
+        commands.flush_commands(world, render_state);
+    }
+
+    #[test]
+    fn scene_json_round_trip_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        spawn_player(&mut world, &mut render_state);
+
+        let scene: Scene = Scene::from_world(&world).unwrap();
+        assert!(scene.entities.len() == 2);
+        assert!(scene.entities[0].parent.is_none());
+        assert!(scene.entities[1].parent == Some(0));
+
+        let type_names: Vec<&str> = scene.entities[0].components.iter().map(|(type_name, _)| type_name.as_str()).collect();
+        assert!(type_names == vec!["DrawOrder", "Sprite", "Transform", "Velocity", "Visibility"]);
+
+        let json: serde_json::Value = scene.to_json();
+        assert!(json["entities"][0]["components"]["Sprite"]["path"] == "textures/lotus_pink_256x256.png");
+        assert!(json["entities"][1]["parent"] == 0);
+        assert!(Scene::from_json(&json).unwrap() == scene);
+    }
+
+    #[test]
+    fn scene_from_cyclic_children_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        spawn_player(&mut world, &mut render_state);
+
+        let player: Entity = world.query_filtered::<Entity, With<Sprite>>().next().unwrap();
+        let child: Entity = world.get_entity_component::<Children>(&player).unwrap().0[0];
+        let mut commands: Commands = world.commands();
+        commands.insert(child, Box::new(Children(vec![player, child])));
+        commands.flush_commands(&mut world, &mut render_state);
+
+        let scene: Scene = Scene::from_entities(&world, &[player]).unwrap();
+        assert!(scene.entities.len() == 2);
+        assert!(scene.entities[1].parent == Some(0));
+    }
+
+    #[test]
+    fn spawn_scene_from_file_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+        spawn_player(&mut world, &mut render_state);
+
+        let path: PathBuf = env::temp_dir().join("lotus_scene_test").join("player.json");
+        let path: &str = path.to_str().unwrap();
+        world.save_scene(path).unwrap();
+
+        let mut loaded_world: World = World::new();
+        let mut commands: Commands = loaded_world.commands();
+        let entities: Vec<Entity> = commands.spawn_scene(path).unwrap();
+        commands.flush_commands(&mut loaded_world, &mut render_state);
+
+        assert!(entities.len() == 2);
+        assert!(loaded_world.get_entity_component::<Sprite>(&entities[0]).unwrap().path == "textures/lotus_pink_256x256.png");
+        assert!(loaded_world.get_entity_component::<DrawOrder>(&entities[0]).unwrap().0 == 3);
+        assert!(loaded_world.get_entity_component::<Velocity>(&entities[0]).unwrap().x == 1.0);
+        assert!(loaded_world.get_entity_component::<Transform>(&entities[0]).unwrap().position.y == 0.25);
+        assert!(loaded_world.get_entity_component::<Parent>(&entities[1]).unwrap().0 == entities[0]);
+        assert!(loaded_world.get_entity_component::<Children>(&entities[0]).unwrap().0 == vec![entities[1]]);
+        assert!(loaded_world.get_entity_component::<Shape>(&entities[1]).is_some());
+
+        let mut detached_world: World = World::new();
+        let mut detached_commands: Commands = Commands::new();
+        let detached_entities: Vec<Entity> = detached_commands.spawn_scene(path).unwrap();
+        detached_commands.despawn(detached_entities[1]);
+        detached_commands.flush_commands(&mut detached_world, &mut render_state);
+        assert!(detached_entities.iter().all(|entity| *entity == Entity::PLACEHOLDER));
+        assert!(detached_world.query::<&Sprite>().count() == 1);
+        assert!(detached_world.query::<&Shape>().count() == 1);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn scene_errors_test() {
+        let mut world: World = World::new();
+        let mut render_state: RenderState = RenderState::dummy();
+
+        let unknown: Scene = Scene::new(vec![
+            SceneEntity::new(vec![("Gravity".to_string(), Gravity::new(9.8).to_value().unwrap())]),
+            SceneEntity::new(vec![("Unknown".to_string(), ReflectValue::Unit)])
+        ]);
+        let mut commands: Commands = world.commands();
+        let entities: Vec<Entity> = commands.instantiate_scene(unknown);
+        commands.flush_commands(&mut world, &mut render_state);
+        assert!(entities.iter().all(|entity| !world.is_entity_alive(*entity)));
+        assert!(world.query::<&Gravity>().count() == 0);
+
+        let invalid_parent: Scene = Scene::new(vec![SceneEntity::new(Vec::new()).parent(0)]);
+        assert!(matches!(invalid_parent.validate(), Err(SceneError::InvalidParent(0))));
+
+        let missing_sprite: Scene = Scene::new(vec![
+            SceneEntity::new(vec![("Sprite".to_string(), Sprite::new("textures/missing.png".to_string()).to_value().unwrap())])
+        ]);
+        assert!(matches!(missing_sprite.validate(), Err(SceneError::MissingAsset(path)) if path == "textures/missing.png"));
+
+        let font: Font = Font::new(Fonts::RobotoMono.get_path(), 16.0);
+        let text: ReflectValue = ReflectValue::Map(vec![
+            ("font".to_string(), font.to_value().unwrap()),
+            ("position".to_string(), Position::new(Vector2::new(0.0, 0.0), Strategy::Normalized).to_value().unwrap()),
+            ("color".to_string(), Color::by_option(ColorOption::White).to_value().unwrap()),
+            ("content".to_string(), ReflectValue::String("Score".to_string()))
+        ]);
+        assert!(Scene::new(vec![SceneEntity::new(vec![("Text".to_string(), text.clone())])]).validate().is_ok());
+        assert!(Text::from_value(&text).unwrap().font.size == 16.0);
+
+        assert!(matches!(Scene::load("scenes/missing.json"), Err(SceneError::Io(_))));
+        assert!(matches!(Scene::from_json(&serde_json::json!({ "entities": 1 })), Err(SceneError::Format(_))));
+    }
+}
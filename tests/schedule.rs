@@ -0,0 +1,366 @@
+#[cfg(test)]
+pub mod tests {
+    use std::{cell::RefCell, rc::Rc, sync::{Arc, Mutex}};
+    use lotus_engine::*;
+
+    fn dummy_context() -> Context {
+        return Context::new(RenderState::dummy(), World::new(), WindowConfiguration::default(), 0.0);
+    }
+
+    fn recording_system(name: &str, record: &Rc<RefCell<Vec<String>>>) -> System {
+        let record: Rc<RefCell<Vec<String>>> = record.clone();
+        let name_to_record: String = name.to_string();
+        return System::new(name, move |_: &mut Context| record.borrow_mut().push(name_to_record.clone()));
+    }
+
+    #[test]
+    fn systems_run_by_stage_and_ordering_test() {
+        let mut context: Context = dummy_context();
+        let mut schedule: Schedule = Schedule::empty();
+        let record: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+
+        schedule
+            .add_system(Stage::PostUpdate, recording_system("late", &record))
+            .add_system(Stage::Update, recording_system("movement", &record).after("input"))
+            .add_system(Stage::Update, recording_system("input", &record))
+            .add_system(Stage::Update, recording_system("collision", &record))
+            .add_system(Stage::Update, recording_system("debug", &record).before("movement"))
+            .add_system(Stage::PreUpdate, recording_system("early", &record));
+
+        schedule.run(&mut context);
+        assert!(*record.borrow() == vec!["early", "input", "collision", "debug", "movement", "late"]);
+        assert!(schedule.get_system_names(Stage::Update) == vec!["input", "collision", "debug", "movement"]);
+    }
+
+    #[test]
+    fn systems_can_be_disabled_and_moved_test() {
+        let mut context: Context = dummy_context();
+        let mut schedule: Schedule = Schedule::empty();
+        let record: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+
+        schedule
+            .add_system(Stage::Update, recording_system("first", &record))
+            .add_system(Stage::Update, recording_system("second", &record));
+
+        assert!(schedule.disable_system("first"));
+        schedule.run(&mut context);
+        assert!(*record.borrow() == vec!["second"]);
+        assert!(!schedule.is_system_enabled("first"));
+
+        schedule.enable_system("first");
+        let second: System = schedule.remove_system("second").unwrap();
+        schedule.add_system(Stage::PreUpdate, second);
+        record.borrow_mut().clear();
+
+        schedule.run(&mut context);
+        assert!(*record.borrow() == vec!["second", "first"]);
+        assert!(schedule.get_system_stage("second") == Some(Stage::PreUpdate));
+    }
+
+    #[test]
+    fn systems_can_be_disabled_from_other_systems_test() {
+        let mut context: Context = dummy_context();
+        let record: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+
+        context.schedule = Schedule::empty();
+        context.schedule
+            .add_system(Stage::PreUpdate, System::new("toggle", |context: &mut Context| {
+                assert!(context.schedule.is_system_enabled("second"));
+                assert!(context.schedule.disable_system("second"));
+                assert!(!context.schedule.is_system_enabled("second"));
+                assert!(context.schedule.remove_system("first").is_none());
+                assert!(!context.schedule.contains_system("first"));
+                assert!(!context.schedule.disable_system("missing"));
+                context.schedule.remove_system("toggle");
+            }))
+            .add_system(Stage::Update, recording_system("first", &record))
+            .add_system(Stage::Update, recording_system("second", &record));
+
+        context.run_schedule();
+        assert!(*record.borrow() == vec!["first", "second"]);
+        assert!(!context.schedule.is_system_enabled("second"));
+        assert!(!context.schedule.contains_system("first"));
+        assert!(!context.schedule.contains_system("toggle"));
+
+        record.borrow_mut().clear();
+        context.schedule.add_system(Stage::Update, System::new("enabler", |context: &mut Context| {
+            context.schedule.enable_system("second");
+        }));
+
+        context.run_schedule();
+        assert!(record.borrow().is_empty());
+        assert!(context.schedule.is_system_enabled("second"));
+
+        context.run_schedule();
+        assert!(*record.borrow() == vec!["second"]);
+    }
+
+    #[test]
+    fn change_detection_follows_the_last_run_of_systems_test() {
+        let mut context: Context = dummy_context();
+        let mut schedule: Schedule = Schedule::empty();
+        let seen: Rc<RefCell<Vec<usize>>> = Rc::new(RefCell::new(Vec::new()));
+        let reader_seen: Rc<RefCell<Vec<usize>>> = seen.clone();
+        let target: Rc<RefCell<Option<Entity>>> = Rc::new(RefCell::new(None));
+        let writer_target: Rc<RefCell<Option<Entity>>> = target.clone();
+
+        let first: Entity = context.commands.spawn(Velocity::new(Vector2::new(0.0, 0.0)));
+        let second: Entity = context.commands.spawn(Velocity::new(Vector2::new(0.0, 0.0)));
+        context.commands.flush_commands(&mut context.world, &mut context.render_state);
+
+        schedule
+            .add_system(Stage::PreUpdate, System::new("reader", move |context: &mut Context| {
+                reader_seen.borrow_mut().push(context.world.query_filtered::<Entity, Changed<Velocity>>().count());
+            }))
+            .add_system(Stage::Update, System::new("writer", move |context: &mut Context| {
+                if let Some(target) = writer_target.borrow_mut().take() {
+                    context.world.get_entity_component_mut::<Velocity>(&target).unwrap().x += 1.0;
+                }
+            }));
+        *target.borrow_mut() = Some(first);
+
+        schedule.run(&mut context);
+        schedule.run(&mut context);
+        schedule.run(&mut context);
+        assert!(*seen.borrow() == vec![2, 1, 0]);
+
+        schedule.disable_system("reader");
+        *target.borrow_mut() = Some(second);
+        schedule.run(&mut context);
+        schedule.run(&mut context);
+
+        schedule.enable_system("reader");
+        schedule.run(&mut context);
+        schedule.run(&mut context);
+        assert!(*seen.borrow() == vec![2, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn built_in_systems_are_registered_test() {
+        let mut schedule: Schedule = Schedule::new();
+
+        assert!(schedule.get_system_names(Stage::PreUpdate) == vec!["events", "camera", "animations", "collisions", "gravity", "particles", "gamepad"]);
+        assert!(schedule.disable_system("gravity"));
+        assert!(!schedule.is_system_enabled("gravity"));
+        assert!(schedule.get_system_stage("transforms") == Some(Stage::PostUpdate));
+    }
+
+    #[test]
+    fn transforms_are_propagated_to_children_test() {
+        let mut context: Context = dummy_context();
+        let mut schedule: Schedule = Schedule::new();
+
+        context.commands.spawn(Transform::new(Position::new(Vector2::new(0.5, 0.0), Strategy::Normalized), 0.0, Vector2::new(2.0, 2.0)));
+        context.commands.flush_commands(&mut context.world, &mut context.render_state);
+        let parent: Entity = context.world.query::<(Entity, &Transform)>().next().unwrap().0;
+
+        context.commands.spawn_child(parent, Transform::new_simple(Position::new(Vector2::new(0.1, 0.2), Strategy::Normalized)));
+        context.commands.flush_commands(&mut context.world, &mut context.render_state);
+        let child: Entity = context.world.get_entity_component::<Children>(&parent).unwrap().0[0];
+
+        schedule.run_stage(Stage::PostUpdate, &mut context);
+        assert!(context.world.get_entity_component::<GlobalTransform>(&parent).unwrap().get_position() == Vector2::new(0.5, 0.0));
+        assert!(context.world.get_entity_component::<GlobalTransform>(&child).unwrap().get_position() == Vector2::new(0.7, 0.4));
+        assert!(context.world.get_entity_component::<GlobalTransform>(&child).unwrap().get_scale() == Vector2::new(2.0, 2.0));
+
+        context.world.get_entity_component_mut::<Transform>(&parent).unwrap().position.x = -0.5;
+        schedule.run_stage(Stage::PostUpdate, &mut context);
+        assert!(context.world.get_entity_component::<GlobalTransform>(&child).unwrap().get_position() == Vector2::new(-0.3, 0.4));
+    }
+
+    #[test]
+    fn transforms_propagation_stops_on_cycles_test() {
+        let mut context: Context = dummy_context();
+        let mut schedule: Schedule = Schedule::new();
+
+        context.commands.spawn(Transform::new_simple(Position::new(Vector2::new(0.5, 0.0), Strategy::Normalized)));
+        context.commands.flush_commands(&mut context.world, &mut context.render_state);
+        let parent: Entity = context.world.query::<(Entity, &Transform)>().next().unwrap().0;
+
+        context.commands.spawn_child(parent, Transform::new_simple(Position::new(Vector2::new(0.1, 0.2), Strategy::Normalized)));
+        context.commands.flush_commands(&mut context.world, &mut context.render_state);
+        let child: Entity = context.world.get_entity_component::<Children>(&parent).unwrap().0[0];
+
+        context.commands.insert(child, Box::new(Children(vec![parent, child])));
+        context.commands.flush_commands(&mut context.world, &mut context.render_state);
+
+        schedule.run_stage(Stage::PostUpdate, &mut context);
+        assert!(context.world.get_entity_component::<GlobalTransform>(&parent).unwrap().get_position() == Vector2::new(0.5, 0.0));
+        assert!(context.world.get_entity_component::<GlobalTransform>(&child).unwrap().get_position() == Vector2::new(0.6, 0.2));
+    }
+
+    #[test]
+    fn parallel_systems_are_batched_by_access_test() {
+        let mut schedule: Schedule = Schedule::empty();
+
+        schedule
+            .add_system(Stage::Update, System::parallel("physics", |_: &mut ParallelContext| {}).writes::<Velocity>().reads::<Gravity>())
+            .add_system(Stage::Update, System::parallel("ai", |_: &mut ParallelContext| {}).writes::<Shape>())
+            .add_system(Stage::Update, System::parallel("friction", |_: &mut ParallelContext| {}).writes::<Velocity>())
+            .add_system(Stage::Update, System::parallel("gravity_reader", |_: &mut ParallelContext| {}).reads::<Gravity>())
+            .add_system(Stage::Update, System::new("exclusive", |_: &mut Context| {}))
+            .add_system(Stage::Update, System::parallel("after_exclusive", |_: &mut ParallelContext| {}))
+            .add_system(Stage::Update, System::parallel("ordered", |_: &mut ParallelContext| {}).after("after_exclusive"));
+
+        assert!(schedule.get_system_batches(Stage::Update) == vec![
+            vec!["physics".to_string(), "ai".to_string()],
+            vec!["friction".to_string(), "gravity_reader".to_string()],
+            vec!["exclusive".to_string()],
+            vec!["after_exclusive".to_string()],
+            vec!["ordered".to_string()]
+        ]);
+    }
+
+    #[test]
+    fn parallel_systems_keep_transitive_ordering_test() {
+        let mut schedule: Schedule = Schedule::empty();
+
+        schedule
+            .add_system(Stage::Update, System::parallel("first", |_: &mut ParallelContext| {}))
+            .add_system(Stage::Update, System::parallel("second", |_: &mut ParallelContext| {}).after("first"))
+            .add_system(Stage::Update, System::parallel("third", |_: &mut ParallelContext| {}).after("second"))
+            .add_system(Stage::Update, System::parallel("unordered", |_: &mut ParallelContext| {}));
+
+        assert!(schedule.disable_system("second"));
+        assert!(schedule.get_system_batches(Stage::Update) == vec![
+            vec!["first".to_string()],
+            vec!["third".to_string(), "unordered".to_string()]
+        ]);
+    }
+
+    #[test]
+    fn parallel_systems_run_with_their_own_commands_test() {
+        let mut context: Context = dummy_context();
+        let mut schedule: Schedule = Schedule::empty();
+
+        context.commands.spawn(Velocity::new(Vector2::new(1.0, 0.0)));
+        context.commands.spawn((Velocity::new(Vector2::new(1.0, 0.0)), Gravity::new(2.0)));
+        context.commands.flush_commands(&mut context.world, &mut context.render_state);
+
+        schedule
+            .add_system(Stage::Update, System::parallel("accelerate", |parallel_context: &mut ParallelContext| {
+                for mut velocity in parallel_context.world.query::<&mut Velocity>() {
+                    velocity.x *= 2.0;
+                }
+            }).writes::<Velocity>())
+            .add_system(Stage::Update, System::parallel("spawner", |parallel_context: &mut ParallelContext| {
+                let gravities: usize = parallel_context.world.query::<&Gravity>().count();
+                parallel_context.commands.spawn(Gravity::new(gravities as f32));
+            }).reads::<Gravity>());
+
+        assert!(schedule.get_system_batches(Stage::Update).len() == 1);
+        schedule.run(&mut context);
+
+        assert!(context.world.query::<&Velocity>().all(|velocity| velocity.x == 2.0));
+        assert!(context.commands.commands.is_empty());
+        assert!(context.world.query::<&Gravity>().count() == 2);
+    }
+
+    #[test]
+    fn commands_are_applied_at_the_end_of_each_stage_test() {
+        let mut context: Context = dummy_context();
+        let mut schedule: Schedule = Schedule::empty();
+        let seen: Rc<RefCell<Vec<bool>>> = Rc::new(RefCell::new(Vec::new()));
+        let seen_by_system: Rc<RefCell<Vec<bool>>> = seen.clone();
+        let spawned: Arc<Mutex<Option<Entity>>> = Arc::new(Mutex::new(None));
+        let spawned_by_system: Arc<Mutex<Option<Entity>>> = spawned.clone();
+
+        schedule
+            .add_system(Stage::Update, System::parallel("spawner", move |parallel_context: &mut ParallelContext| {
+                let entity: Entity = parallel_context.commands.spawn(Velocity::new(Vector2::new(1.0, 0.0)));
+                parallel_context.commands.add(move |world: &mut World| {
+                    world.get_entity_component_mut::<Velocity>(&entity).unwrap().x = 3.0;
+                });
+                *spawned_by_system.lock().unwrap() = Some(entity);
+            }))
+            .add_system(Stage::Update, System::new("same_stage", move |context: &mut Context| {
+                let entity: Entity = context.world.query::<(Entity, &Velocity)>().next().map(|(entity, _)| entity).unwrap_or(Entity::PLACEHOLDER);
+                seen_by_system.borrow_mut().push(context.world.is_entity_alive(entity));
+            }).after("spawner"))
+            .add_system(Stage::PostUpdate, System::new("next_stage", |context: &mut Context| {
+                let entity: Entity = context.world.query::<(Entity, &Velocity)>().next().unwrap().0;
+                let child: Entity = context.commands.spawn_child(entity, ());
+                context.commands.despawn(child);
+            }));
+
+        schedule.run(&mut context);
+
+        let entity: Entity = spawned.lock().unwrap().unwrap();
+        assert!(*seen.borrow() == vec![false]);
+        assert!(context.world.is_entity_alive(entity));
+        assert!(context.world.get_entity_component::<Velocity>(&entity).unwrap().x == 3.0);
+        assert!(context.world.get_entity_component::<Children>(&entity).unwrap().get().is_empty());
+        assert!(context.world.entity_locations.len() == 1);
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum GameState {
+        Menu,
+        Playing
+    }
+
+    #[test]
+    fn state_transitions_and_run_conditions_test() {
+        let mut context: Context = dummy_context();
+        let record: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+        context.schedule = Schedule::empty();
+        context.init_state(GameState::Menu);
+
+        context.schedule
+            .add_system(OnEnter(GameState::Menu), recording_system("enter_menu", &record))
+            .add_system(OnExit(GameState::Menu), recording_system("exit_menu", &record))
+            .add_system(OnEnter(GameState::Playing), recording_system("enter_playing", &record))
+            .add_system(Stage::Update, recording_system("menu", &record).run_if(in_state(GameState::Menu)))
+            .add_system(Stage::Update, recording_system("playing", &record).run_if(in_state(GameState::Playing)));
+
+        let mut schedule: Schedule = std::mem::replace(&mut context.schedule, Schedule::empty());
+        schedule.run(&mut context);
+        assert!(*record.borrow() == vec!["enter_menu", "menu"]);
+
+        context.world.get_resource_mut::<NextState<GameState>>().unwrap().set(GameState::Playing);
+        assert!(*context.world.get_resource::<State<GameState>>().unwrap().get() == GameState::Menu);
+        record.borrow_mut().clear();
+
+        schedule.run(&mut context);
+        assert!(*record.borrow() == vec!["exit_menu", "enter_playing", "playing"]);
+        assert!(*context.world.get_resource::<State<GameState>>().unwrap().get() == GameState::Playing);
+        assert!(context.world.get_resource::<NextState<GameState>>().unwrap().get().is_none());
+        record.borrow_mut().clear();
+
+        schedule.run(&mut context);
+        assert!(*record.borrow() == vec!["playing"]);
+    }
+
+    #[test]
+    fn run_conditions_see_earlier_systems_of_the_stage_test() {
+        let mut context: Context = dummy_context();
+        let mut schedule: Schedule = Schedule::empty();
+        let record: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+
+        let entity: Entity = context.commands.spawn(Velocity::new(Vector2::new(0.0, 0.0)));
+        context.commands.flush_commands(&mut context.world, &mut context.render_state);
+
+        schedule
+            .add_system(Stage::Update, recording_system("moving", &record)
+                .after("push")
+                .run_if(|world: &World| world.query::<&Velocity>().any(|velocity| velocity.x > 0.0)))
+            .add_system(Stage::Update, System::new("push", move |context: &mut Context| {
+                context.world.get_entity_component_mut::<Velocity>(&entity).unwrap().x = 1.0;
+            }));
+
+        schedule.run(&mut context);
+        assert!(*record.borrow() == vec!["moving"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cyclic_ordering_panics_test() {
+        let mut context: Context = dummy_context();
+        let mut schedule: Schedule = Schedule::empty();
+
+        schedule
+            .add_system(Stage::Update, System::new("a", |_: &mut Context| {}).after("b"))
+            .add_system(Stage::Update, System::new("b", |_: &mut Context| {}).after("a"));
+        schedule.run(&mut context);
+    }
+}